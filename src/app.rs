@@ -9,12 +9,18 @@ use tokio::sync::mpsc;
 
 use crate::{
   configuration::app_configuration::Config,
+  data_services::pseudo_fs::PseudoFsRoots,
   tui::{
     self,
     action::Action,
     components::{
-      cpu::Cpu, disks::DiskTable, fps::FpsCounter, home::Home, memory::MemoryComponent, network::NetworkComponent,
-      process_table::ProcessTable, Component,
+      battery::BatteryComponent, block_devices::BlockDeviceTree, cgroups::CgroupTree, connections::ConnectionTable,
+      cpu::Cpu, device_power::DevicePowerComponent, disk_io::DiskIoComponent, disks::DiskTable, fps::FpsCounter,
+      frequency::FrequencyComponent, header::HeaderComponent, home::Home, idle_stats::IdleStatsComponent,
+      interrupts::InterruptsComponent, limits::LimitsComponent, memory::MemoryComponent, net_stats::NetStatsComponent,
+      network::NetworkComponent, overview::OverviewComponent, pressure::PressureComponent, process_table::ProcessTable,
+      raid::RaidComponent, rapl::RaplComponent, sensors::SensorsComponent, temperatures::TemperatureComponent,
+      tunables::TunablesComponent, vmstat::VmStatComponent, Component,
     },
     mode::Mode,
     ui::{Event, Tui},
@@ -25,6 +31,7 @@ pub struct App {
   pub config: Config,
   pub tick_rate: f64,
   pub frame_rate: f64,
+  pub roots: PseudoFsRoots,
   pub components: Vec<Box<dyn Component>>,
   pub should_quit: bool,
   pub should_suspend: bool,
@@ -33,25 +40,67 @@ pub struct App {
 }
 
 impl App {
-  pub fn new(tick_rate: f64, frame_rate: f64) -> Result<Self> {
+  pub fn new(tick_rate: f64, frame_rate: f64, read_only: bool, roots: PseudoFsRoots) -> Result<Self> {
     let home = Home::new();
     let cpu = Cpu::new();
     let process_table = ProcessTable::new();
     let disk_table = DiskTable::new();
     let network_component = NetworkComponent::new();
     let memory_component = MemoryComponent::new();
+    let connection_table = ConnectionTable::new();
+    let net_stats_component = NetStatsComponent::new();
+    let disk_io_component = DiskIoComponent::new();
+    let block_device_tree = BlockDeviceTree::new();
+    let raid_component = RaidComponent::new();
+    let temperature_component = TemperatureComponent::new();
+    let sensors_component = SensorsComponent::new();
+    let battery_component = BatteryComponent::new();
+    let idle_stats_component = IdleStatsComponent::new();
+    let frequency_component = FrequencyComponent::new();
+    let rapl_component = RaplComponent::new();
+    let overview_component = OverviewComponent::new();
+    let tunables_component = TunablesComponent::new(read_only, roots.clone());
+    let device_power_component = DevicePowerComponent::new();
+    let header_component = HeaderComponent::new(&roots);
+    let pressure_component = PressureComponent::new();
+    let vmstat_component = VmStatComponent::new();
+    let limits_component = LimitsComponent::new();
+    let interrupts_component = InterruptsComponent::new();
+    let cgroup_tree = CgroupTree::new();
 
     let config = Config::new()?;
     let mode = Mode::Home;
     Ok(App {
       tick_rate,
       frame_rate,
+      roots,
       components: vec![
         Box::new(cpu),
         Box::new(process_table),
         Box::new(disk_table),
         Box::new(network_component),
         Box::new(memory_component),
+        Box::new(connection_table),
+        Box::new(net_stats_component),
+        Box::new(disk_io_component),
+        Box::new(block_device_tree),
+        Box::new(temperature_component),
+        Box::new(sensors_component),
+        Box::new(battery_component),
+        Box::new(idle_stats_component),
+        Box::new(frequency_component),
+        Box::new(rapl_component),
+        Box::new(overview_component),
+        Box::new(tunables_component),
+        Box::new(device_power_component),
+        Box::new(header_component),
+        Box::new(pressure_component),
+        Box::new(limits_component),
+        Box::new(interrupts_component),
+        Box::new(cgroup_tree),
+        // Drawn last so their OOM and degraded array alerts stay on top of the full screen views
+        Box::new(vmstat_component),
+        Box::new(raid_component),
       ],
       should_quit: false,
       should_suspend: false,
@@ -64,7 +113,7 @@ impl App {
   pub async fn run(&mut self) -> Result<()> {
     let (action_tx, mut action_rx) = mpsc::unbounded_channel();
 
-    let mut tui = Tui::new()?.tick_rate(self.tick_rate).frame_rate(self.frame_rate);
+    let mut tui = Tui::new()?.tick_rate(self.tick_rate).frame_rate(self.frame_rate).roots(self.roots.clone());
     // tui.mouse(true);
     tui.enter()?;
 
@@ -88,22 +137,20 @@ impl App {
           Event::Render => action_tx.send(Action::Render)?,
           Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
           Event::Key(key) => {
-            if let Some(keymap) = self.config.keybindings.get(&self.mode) {
-              if let Some(action) = keymap.get(&vec![key]) {
-                log::info!("Got action: {action:?}");
-                action_tx.send(action.clone())?;
-              } else {
-                // If the key was not handled as a single key action,
-                // then consider it for multi-key combinations.
-                self.last_tick_key_events.push(key);
+            if let Some(action) = self.action_for_keys(&[key]) {
+              log::info!("Got action: {action:?}");
+              action_tx.send(action)?;
+            } else {
+              // If the key was not handled as a single key action,
+              // then consider it for multi-key combinations.
+              self.last_tick_key_events.push(key);
 
-                // Check for multi-key combinations
-                if let Some(action) = keymap.get(&self.last_tick_key_events) {
-                  log::info!("Got action: {action:?}");
-                  action_tx.send(action.clone())?;
-                }
+              // Check for multi-key combinations
+              if let Some(action) = self.action_for_keys(&self.last_tick_key_events) {
+                log::info!("Got action: {action:?}");
+                action_tx.send(action)?;
               }
-            };
+            }
           },
           Event::DataUpdate(ref data) => action_tx.send(Action::DataUpdate(data.clone()))?,
           _ => {},
@@ -126,6 +173,9 @@ impl App {
           Action::Quit => self.should_quit = true,
           Action::Suspend => self.should_suspend = true,
           Action::Resume => self.should_suspend = false,
+          Action::NextMode => action_tx.send(Action::SwitchMode(self.mode.next()))?,
+          Action::PreviousMode => action_tx.send(Action::SwitchMode(self.mode.previous()))?,
+          Action::SwitchMode(mode) => self.mode = mode,
           Action::Resize(w, h) => {
             tui.resize(Rect::new(0, 0, w, h))?;
             tui.draw(|f| {
@@ -160,7 +210,7 @@ impl App {
       if self.should_suspend {
         tui.suspend()?;
         action_tx.send(Action::Resume)?;
-        tui = Tui::new()?.tick_rate(self.tick_rate).frame_rate(self.frame_rate);
+        tui = Tui::new()?.tick_rate(self.tick_rate).frame_rate(self.frame_rate).roots(self.roots.clone());
         // tui.mouse(true);
         tui.enter()?;
       } else if self.should_quit {
@@ -171,4 +221,14 @@ impl App {
     tui.exit()?;
     Ok(())
   }
+
+  /// Looks up the action bound to a key sequence in the current mode, falling back to the `Home` keybindings so
+  /// global keys (quit, switching modes, ...) only need to be configured once.
+  fn action_for_keys(&self, keys: &[KeyEvent]) -> Option<Action> {
+    [self.mode, Mode::Home]
+      .iter()
+      .filter_map(|mode| self.config.keybindings.get(mode))
+      .find_map(|keymap| keymap.get(keys))
+      .cloned()
+  }
 }
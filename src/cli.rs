@@ -18,4 +18,23 @@ pub struct Cli {
     default_value_t = 4.0
   )]
   pub frame_rate: f64,
+
+  #[arg(long, help = "Only report power tunables, never write to sysfs")]
+  pub read_only: bool,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Where procfs is mounted, e.g. a copy captured on another machine",
+    default_value = "/proc"
+  )]
+  pub proc_root: PathBuf,
+
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Where sysfs is mounted, e.g. a copy captured on another machine",
+    default_value = "/sys"
+  )]
+  pub sys_root: PathBuf,
 }
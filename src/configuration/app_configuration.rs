@@ -83,6 +83,34 @@ pub struct AppConfig {
   pub _config_dir: PathBuf,
 }
 
+/// Rules for hiding pseudo and virtual file systems from the disk views.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DiskConfig {
+  // File system types that are never shown, e.g. `tmpfs`
+  pub hidden_file_systems: Vec<String>,
+  // Mount points starting with any of these prefixes are not shown, e.g. `/snap/` for snap package images
+  pub hidden_mount_prefixes: Vec<String>,
+}
+
+impl Default for DiskConfig {
+  fn default() -> Self {
+    Self {
+      hidden_file_systems: ["tmpfs", "devtmpfs", "overlay", "squashfs", "ramfs", "efivarfs", "nsfs"]
+        .map(String::from)
+        .to_vec(),
+      hidden_mount_prefixes: ["/snap/", "/var/lib/docker/", "/var/lib/containers/"].map(String::from).to_vec(),
+    }
+  }
+}
+
+impl DiskConfig {
+  pub fn is_hidden(&self, file_system: &str, mount_path: &str) -> bool {
+    self.hidden_file_systems.iter().any(|fs| fs == file_system)
+      || self.hidden_mount_prefixes.iter().any(|prefix| mount_path.starts_with(prefix.as_str()))
+  }
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct Config {
   #[serde(default, flatten)]
@@ -91,6 +119,8 @@ pub struct Config {
   pub keybindings: KeyBindings,
   #[serde(default)]
   pub styles: Styles,
+  #[serde(default)]
+  pub disks: DiskConfig,
 }
 
 impl Config {
@@ -158,4 +188,12 @@ mod tests {
     );
     Ok(())
   }
+
+  #[test]
+  fn test_default_disk_filters() {
+    let disks = DiskConfig::default();
+    assert!(disks.is_hidden("tmpfs", "/tmp"));
+    assert!(disks.is_hidden("squashfs", "/snap/core/123"));
+    assert!(!disks.is_hidden("ext4", "/"));
+  }
 }
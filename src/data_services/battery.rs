@@ -0,0 +1,199 @@
+use std::{collections::HashMap, fs, time::Duration};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::pseudo_fs::{list_dir, read_trimmed, PseudoFsRoots};
+
+const CLASS_POWER_SUPPLY: &str = "class/power_supply";
+
+// Energy, power, charge and voltage attributes are reported in micro units (µWh, µW, µAh, µV)
+const MICRO: f64 = 1_000_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum BatteryStatus {
+  Charging,
+  Discharging,
+  Full,
+  #[strum(serialize = "Not charging")]
+  NotCharging,
+  Unknown,
+}
+
+impl From<&str> for BatteryStatus {
+  fn from(status: &str) -> Self {
+    match status {
+      "Charging" => BatteryStatus::Charging,
+      "Discharging" => BatteryStatus::Discharging,
+      "Full" => BatteryStatus::Full,
+      "Not charging" => BatteryStatus::NotCharging,
+      _ => BatteryStatus::Unknown,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryData {
+  pub name: String,
+  pub model: Option<String>,
+  pub manufacturer: Option<String>,
+  pub status: BatteryStatus,
+  pub capacity_percent: Option<f64>,
+  // Energies in Wh. Batteries reporting charge (µAh) are converted using their voltage.
+  pub energy_now_wh: Option<f64>,
+  pub energy_full_wh: Option<f64>,
+  pub energy_full_design_wh: Option<f64>,
+  // Rate the battery is charged or discharged at, always positive
+  pub power_w: Option<f64>,
+  pub cycle_count: Option<u32>,
+  // Health as reported by the driver (e.g. `Good`, `Overheat`), most laptops do not report it
+  pub health: Option<String>,
+}
+
+pub type BatteryDataCollection = Vec<BatteryData>;
+
+impl BatteryData {
+  /// Full charge capacity relative to the design capacity, i.e. how much the battery has worn.
+  pub fn health_percent(&self) -> Option<f64> {
+    match (self.energy_full_wh, self.energy_full_design_wh) {
+      (Some(full), Some(design)) if design > 0.0 => Some(full / design * 100.0),
+      _ => None,
+    }
+  }
+
+  /// Time until the battery is empty (discharging) or full (charging) at the given rate.
+  pub fn time_remaining(&self, power_w: f64) -> Option<Duration> {
+    if power_w <= 0.0 {
+      return None;
+    }
+    let energy_now = self.energy_now_wh?;
+    let energy_left = match self.status {
+      BatteryStatus::Discharging => energy_now,
+      BatteryStatus::Charging => (self.energy_full_wh? - energy_now).max(0.0),
+      _ => return None,
+    };
+    Some(Duration::from_secs_f64(energy_left / power_w * 3600.0))
+  }
+}
+
+/// Parses the `POWER_SUPPLY_*=value` lines of a power supply's `uevent` file into a map keyed by the lowercase
+/// attribute name (`energy_now`, `status`, ...).
+pub fn parse_power_supply_uevent(contents: &str) -> HashMap<String, String> {
+  contents
+    .lines()
+    .filter_map(|line| {
+      let (key, value) = line.split_once('=')?;
+      Some((key.strip_prefix("POWER_SUPPLY_")?.to_ascii_lowercase(), value.trim().to_string()))
+    })
+    .collect()
+}
+
+/// Builds a battery from its attributes, or `None` if the power supply is not a battery (e.g. the AC adapter or the
+/// battery of a wireless mouse, which reports `scope=Device`).
+pub fn battery_from_attributes(name: &str, attributes: &HashMap<String, String>) -> Option<BatteryData> {
+  if attributes.get("type").map(String::as_str) != Some("Battery")
+    || attributes.get("scope").map(String::as_str) == Some("Device")
+  {
+    return None;
+  }
+  let micro = |key: &str| attributes.get(key)?.parse::<f64>().ok().map(|value| value / MICRO);
+  let voltage = micro("voltage_now").or_else(|| micro("voltage_min_design"));
+  // Batteries report either energy (µWh) or charge (µAh), the latter needs the voltage to be comparable
+  let energy =
+    |kind: &str| micro(&format!("energy_{}", kind)).or_else(|| Some(micro(&format!("charge_{}", kind))? * voltage?));
+  let power = micro("power_now").or_else(|| Some(micro("current_now")? * voltage?)).map(f64::abs);
+
+  Some(BatteryData {
+    name: name.to_string(),
+    model: attributes.get("model_name").cloned(),
+    manufacturer: attributes.get("manufacturer").cloned(),
+    status: attributes.get("status").map_or(BatteryStatus::Unknown, |status| status.as_str().into()),
+    capacity_percent: attributes.get("capacity").and_then(|capacity| capacity.parse().ok()),
+    energy_now_wh: energy("now"),
+    energy_full_wh: energy("full"),
+    energy_full_design_wh: energy("full_design"),
+    power_w: power,
+    // Drivers without cycle counting report 0
+    cycle_count: attributes.get("cycle_count").and_then(|count| count.parse().ok()).filter(|count| *count > 0),
+    health: attributes.get("health").cloned(),
+  })
+}
+
+pub fn get_battery_info(roots: &PseudoFsRoots) -> Result<BatteryDataCollection> {
+  let power_supply = roots.sys(CLASS_POWER_SUPPLY);
+  Ok(
+    list_dir(&power_supply)
+      .iter()
+      .filter_map(|name| {
+        let contents = fs::read_to_string(power_supply.join(name).join("uevent")).ok()?;
+        let mut attributes = parse_power_supply_uevent(&contents);
+        // Older kernels leave the type out of uevent
+        if !attributes.contains_key("type") {
+          if let Some(kind) = read_trimmed(power_supply.join(name).join("type")) {
+            attributes.insert("type".to_string(), kind);
+          }
+        }
+        battery_from_attributes(name, &attributes)
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_energy_battery() {
+    let uevent = "POWER_SUPPLY_NAME=BAT0
+POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Discharging
+POWER_SUPPLY_CYCLE_COUNT=187
+POWER_SUPPLY_POWER_NOW=7500000
+POWER_SUPPLY_ENERGY_FULL_DESIGN=57000000
+POWER_SUPPLY_ENERGY_FULL=51300000
+POWER_SUPPLY_ENERGY_NOW=30000000
+POWER_SUPPLY_CAPACITY=58
+POWER_SUPPLY_MODEL_NAME=5B10W13975
+";
+    let battery = battery_from_attributes("BAT0", &parse_power_supply_uevent(uevent)).unwrap();
+
+    assert_eq!(battery.status, BatteryStatus::Discharging);
+    assert_eq!(battery.capacity_percent, Some(58.0));
+    assert_eq!(battery.energy_now_wh, Some(30.0));
+    assert_eq!(battery.power_w, Some(7.5));
+    assert_eq!(battery.cycle_count, Some(187));
+    assert_eq!(battery.health_percent().map(f64::round), Some(90.0));
+    assert_eq!(battery.time_remaining(7.5), Some(Duration::from_secs(4 * 3600)));
+  }
+
+  #[test]
+  fn test_charge_battery() {
+    let uevent = "POWER_SUPPLY_TYPE=Battery
+POWER_SUPPLY_STATUS=Charging
+POWER_SUPPLY_VOLTAGE_NOW=12000000
+POWER_SUPPLY_CURRENT_NOW=-1000000
+POWER_SUPPLY_CHARGE_FULL=4000000
+POWER_SUPPLY_CHARGE_NOW=3000000
+POWER_SUPPLY_CYCLE_COUNT=0
+";
+    let battery = battery_from_attributes("BAT1", &parse_power_supply_uevent(uevent)).unwrap();
+
+    assert_eq!(battery.energy_full_wh, Some(48.0));
+    assert_eq!(battery.power_w, Some(12.0));
+    assert_eq!(battery.cycle_count, None);
+    assert_eq!(battery.time_remaining(12.0), Some(Duration::from_secs(3600)));
+  }
+
+  #[test]
+  fn test_non_batteries_are_skipped() {
+    let mains = parse_power_supply_uevent("POWER_SUPPLY_TYPE=Mains\nPOWER_SUPPLY_ONLINE=1\n");
+    let mouse = parse_power_supply_uevent("POWER_SUPPLY_TYPE=Battery\nPOWER_SUPPLY_SCOPE=Device\n");
+
+    assert_eq!(battery_from_attributes("AC", &mains), None);
+    assert_eq!(battery_from_attributes("hidpp_battery_0", &mouse), None);
+  }
+}
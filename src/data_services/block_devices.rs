@@ -0,0 +1,255 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use sysinfo::Disks;
+
+use super::{
+  disks::mount_points_by_device,
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots, CLASS_BLOCK, SECTOR_SIZE},
+};
+
+// Guards against holder cycles, which the kernel should never report
+const MAX_TREE_DEPTH: usize = 16;
+
+/// Block device types, named after the `TYPE` column of `lsblk`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum BlockDeviceKind {
+  Disk,
+  #[strum(serialize = "part")]
+  Partition,
+  Crypt,
+  Lvm,
+  #[strum(serialize = "dm")]
+  DeviceMapper,
+  Raid,
+  Loop,
+  Ram,
+}
+
+impl BlockDeviceKind {
+  /// Device mapper targets are told apart by the prefix their creator puts in front of the dm UUID.
+  pub(crate) fn from_dm_uuid(uuid: &str) -> Self {
+    match uuid.split('-').next() {
+      Some("CRYPT") => BlockDeviceKind::Crypt,
+      Some("LVM") => BlockDeviceKind::Lvm,
+      _ => BlockDeviceKind::DeviceMapper,
+    }
+  }
+}
+
+/// A single entry of `/sys/class/block` along with its relations to other block devices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockDeviceNode {
+  pub name: String,
+  pub kind: BlockDeviceKind,
+  pub size_bytes: u64,
+  pub model: Option<String>,
+  pub rotational: Option<bool>,
+  pub removable: bool,
+  pub read_only: bool,
+  // Name from `dm/name` that users know the device by, e.g. `vg0-root` for `dm-1`
+  pub label: Option<String>,
+  // Disk a partition belongs to
+  pub parent: Option<String>,
+  // Devices stacked on top of this one (`holders/`), e.g. the dm-crypt mapping of a LUKS partition
+  pub holders: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockDevice {
+  pub name: String,
+  pub kind: BlockDeviceKind,
+  pub size_bytes: u64,
+  pub model: Option<String>,
+  pub rotational: Option<bool>,
+  pub removable: bool,
+  pub read_only: bool,
+  pub label: Option<String>,
+  // Mount points of the device as listed by the disk table, empty for unmounted devices
+  pub mount_points: Vec<String>,
+  pub children: Vec<BlockDevice>,
+}
+
+pub type BlockDeviceCollection = Vec<BlockDevice>;
+
+impl BlockDeviceNode {
+  fn read(name: &str, roots: &PseudoFsRoots) -> Self {
+    let path = roots.sys(CLASS_BLOCK).join(name);
+    let dm_uuid = read_trimmed(path.join("dm/uuid"));
+    let is_partition = path.join("partition").exists();
+
+    let kind = if is_partition {
+      BlockDeviceKind::Partition
+    } else if let Some(uuid) = &dm_uuid {
+      BlockDeviceKind::from_dm_uuid(uuid)
+    } else if path.join("md").exists() {
+      BlockDeviceKind::Raid
+    } else if name.starts_with("loop") {
+      BlockDeviceKind::Loop
+    } else if name.starts_with("zram") || name.starts_with("ram") {
+      BlockDeviceKind::Ram
+    } else {
+      BlockDeviceKind::Disk
+    };
+
+    // Partitions live below their disk in sysfs, e.g. `.../block/nvme0n1/nvme0n1p1`
+    let parent = if is_partition {
+      fs::canonicalize(&path)
+        .ok()
+        .and_then(|resolved| resolved.parent()?.file_name().map(|parent| parent.to_string_lossy().into_owned()))
+    } else {
+      None
+    };
+
+    BlockDeviceNode {
+      name: name.to_string(),
+      kind,
+      size_bytes: read_value::<u64>(path.join("size")).unwrap_or_default() * SECTOR_SIZE,
+      model: read_trimmed(path.join("device/model")).filter(|model| !model.is_empty()),
+      // Partitions have no queue of their own and take the flag of their disk when building the tree
+      rotational: read_value::<u8>(path.join("queue/rotational")).map(|rotational| rotational == 1),
+      removable: read_value::<u8>(path.join("removable")) == Some(1),
+      read_only: read_value::<u8>(path.join("ro")) == Some(1),
+      label: read_trimmed(path.join("dm/name")).filter(|name| !name.is_empty()),
+      parent,
+      holders: list_dir(path.join("holders")),
+    }
+  }
+}
+
+/// Arranges block devices into the tree shown by `lsblk`: disks at the top, their partitions below them and devices
+/// stacked on top of a device (dm-crypt, LVM, md RAID) below that device. A device stacked on several devices, e.g. a
+/// RAID 1 array, appears below each of them. Unused loop and ram devices (size 0) are left out.
+pub fn build_block_device_tree(
+  nodes: &[BlockDeviceNode],
+  mount_points: &HashMap<String, Vec<String>>,
+) -> BlockDeviceCollection {
+  let by_name: HashMap<&str, &BlockDeviceNode> = nodes.iter().map(|node| (node.name.as_str(), node)).collect();
+  let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+  for node in nodes {
+    if let Some(parent) = &node.parent {
+      children.entry(parent.as_str()).or_default().push(&node.name);
+    }
+    children.entry(&node.name).or_default().extend(node.holders.iter().map(String::as_str));
+  }
+  let is_child = |name: &str| children.values().any(|names| names.contains(&name));
+
+  nodes
+    .iter()
+    .filter(|node| node.size_bytes > 0 && !is_child(&node.name))
+    .map(|node| to_block_device(node, None, &by_name, &children, mount_points, 0))
+    .collect()
+}
+
+fn to_block_device(
+  node: &BlockDeviceNode,
+  parent_rotational: Option<bool>,
+  by_name: &HashMap<&str, &BlockDeviceNode>,
+  children: &HashMap<&str, Vec<&str>>,
+  mount_points: &HashMap<String, Vec<String>>,
+  depth: usize,
+) -> BlockDevice {
+  let rotational = node.rotational.or(parent_rotational);
+  let child_devices = if depth < MAX_TREE_DEPTH {
+    children
+      .get(node.name.as_str())
+      .into_iter()
+      .flatten()
+      .filter_map(|name| by_name.get(name))
+      .map(|child| to_block_device(child, rotational, by_name, children, mount_points, depth + 1))
+      .collect()
+  } else {
+    Vec::new()
+  };
+
+  BlockDevice {
+    name: node.name.clone(),
+    kind: node.kind,
+    size_bytes: node.size_bytes,
+    model: node.model.clone(),
+    rotational,
+    removable: node.removable,
+    read_only: node.read_only,
+    label: node.label.clone(),
+    mount_points: mount_points.get(&node.name).cloned().unwrap_or_default(),
+    children: child_devices,
+  }
+}
+
+pub fn get_block_device_info(disks: &Disks, roots: &PseudoFsRoots) -> Result<BlockDeviceCollection> {
+  // Fail when sysfs is unavailable rather than reporting a machine without block devices
+  let class_block = roots.sys(CLASS_BLOCK);
+  fs::metadata(&class_block)?;
+  let nodes: Vec<BlockDeviceNode> =
+    list_dir(&class_block).iter().map(|name| BlockDeviceNode::read(name, roots)).collect();
+  Ok(build_block_device_tree(&nodes, &mount_points_by_device(disks)))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn node(name: &str, kind: BlockDeviceKind) -> BlockDeviceNode {
+    BlockDeviceNode {
+      name: name.to_string(),
+      kind,
+      size_bytes: 1024,
+      model: None,
+      rotational: None,
+      removable: false,
+      read_only: false,
+      label: None,
+      parent: None,
+      holders: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_dm_kind_from_uuid() {
+    assert_eq!(BlockDeviceKind::from_dm_uuid("CRYPT-LUKS2-4f3c-luks-4f3c"), BlockDeviceKind::Crypt);
+    assert_eq!(BlockDeviceKind::from_dm_uuid("LVM-Xk2vQ"), BlockDeviceKind::Lvm);
+    assert_eq!(BlockDeviceKind::from_dm_uuid("mpath-3600"), BlockDeviceKind::DeviceMapper);
+  }
+
+  #[test]
+  fn test_luks_lvm_tree() {
+    let disk = BlockDeviceNode { rotational: Some(false), ..node("nvme0n1", BlockDeviceKind::Disk) };
+    let boot = BlockDeviceNode { parent: Some("nvme0n1".into()), ..node("nvme0n1p1", BlockDeviceKind::Partition) };
+    let luks = BlockDeviceNode {
+      parent: Some("nvme0n1".into()),
+      holders: vec!["dm-0".into()],
+      ..node("nvme0n1p2", BlockDeviceKind::Partition)
+    };
+    let crypt = BlockDeviceNode { holders: vec!["dm-1".into()], ..node("dm-0", BlockDeviceKind::Crypt) };
+    let root = BlockDeviceNode { label: Some("vg0-root".into()), ..node("dm-1", BlockDeviceKind::Lvm) };
+    let unused_loop = BlockDeviceNode { size_bytes: 0, ..node("loop0", BlockDeviceKind::Loop) };
+    let mount_points = HashMap::from([("dm-1".to_string(), vec!["/".to_string()])]);
+
+    let tree = build_block_device_tree(&[crypt, root, unused_loop, disk, boot, luks], &mount_points);
+
+    assert_eq!(tree.len(), 1);
+    let disk = &tree[0];
+    assert_eq!(disk.children.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["nvme0n1p1", "nvme0n1p2"]);
+    let root = &disk.children[1].children[0].children[0];
+    assert_eq!(root.name, "dm-1");
+    assert_eq!(root.mount_points, vec!["/".to_string()]);
+    assert_eq!(root.rotational, Some(false));
+  }
+
+  #[test]
+  fn test_raid_member_of_several_devices() {
+    let sda = BlockDeviceNode { holders: vec!["md0".into()], ..node("sda", BlockDeviceKind::Disk) };
+    let sdb = BlockDeviceNode { holders: vec!["md0".into()], ..node("sdb", BlockDeviceKind::Disk) };
+    let md0 = node("md0", BlockDeviceKind::Raid);
+
+    let tree = build_block_device_tree(&[md0, sda, sdb], &HashMap::new());
+
+    assert_eq!(tree.len(), 2);
+    assert!(tree.iter().all(|disk| disk.children[0].name == "md0"));
+  }
+}
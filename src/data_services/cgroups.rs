@@ -0,0 +1,352 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fmt, fs,
+  path::Path,
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use super::{
+  counters::{rate, CounterHistory},
+  pressure::{parse_pressure, PressureResource},
+  pseudo_fs::{cgroup2_root, list_dir, read_trimmed, read_value, PseudoFsRoots},
+};
+
+const CPU_STAT: &str = "cpu.stat";
+const IO_STAT: &str = "io.stat";
+const MEMORY_CURRENT: &str = "memory.current";
+const MEMORY_MAX: &str = "memory.max";
+const PIDS_CURRENT: &str = "pids.current";
+const CGROUP_PROCS: &str = "cgroup.procs";
+// Path of the root cgroup in `CgroupNode::path`
+pub const ROOT_PATH: &str = "/";
+// Container ids are shortened like `docker ps` does
+const CONTAINER_ID_LENGTH: usize = 12;
+// Scope prefixes container runtimes use with the systemd cgroup driver, e.g. `docker-<id>.scope`
+const CONTAINER_SCOPES: [(&str, &str); 4] =
+  [("docker-", "docker"), ("libpod-", "podman"), ("cri-containerd-", "containerd"), ("crio-", "cri-o")];
+
+/// Cumulative counters of one cgroup, the CPU time in microseconds and the bytes read and written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CgroupCounters {
+  pub cpu_usage_us: Option<u64>,
+  pub read_bytes: Option<u64>,
+  pub write_bytes: Option<u64>,
+}
+
+/// Counters keyed by cgroup path.
+pub type CgroupCounterMap = HashMap<String, CgroupCounters>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupNode {
+  // Path relative to the cgroup v2 root, e.g. `system.slice/nginx.service`, or `/` for the root itself
+  pub path: String,
+  pub name: String,
+  pub depth: usize,
+  pub has_children: bool,
+  // Share of one CPU used since the previous collection, in percent
+  pub cpu_percent: Option<f64>,
+  // Memory use and limit in bytes, the limit is `None` when unlimited or the memory controller is not enabled
+  pub memory_current: Option<u64>,
+  pub memory_max: Option<u64>,
+  // Bytes per second since the previous collection, summed over all devices
+  pub read_rate: Option<f64>,
+  pub write_rate: Option<f64>,
+  pub pids_current: Option<u64>,
+  // Share of time some tasks of the cgroup were stalled over the last 10 seconds, per resource
+  pub pressure: Vec<(PressureResource, f64)>,
+  // Processes directly in this cgroup, not in its descendants
+  pub pids: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CgroupData {
+  // Depth first with children sorted by name, so a cgroup is always followed by its descendants
+  pub nodes: Vec<CgroupNode>,
+}
+
+impl CgroupData {
+  /// PIDs of the processes in the cgroup at `path` and all of its descendants.
+  pub fn member_pids(&self, path: &str) -> HashSet<u32> {
+    let prefix = format!("{}/", path);
+    self
+      .nodes
+      .iter()
+      .filter(|node| path == ROOT_PATH || node.path == path || node.path.starts_with(&prefix))
+      .flat_map(|node| node.pids.iter().copied())
+      .collect()
+  }
+}
+
+/// What a process belongs to according to its cgroup: a container or, for everything else, a systemd unit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Attribution {
+  // Service, scope or slice, e.g. `nginx.service` or `user@1000.service`
+  Unit(String),
+  Container { runtime: String, id: String },
+}
+
+impl fmt::Display for Attribution {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Attribution::Unit(unit) => write!(f, "{}", unit),
+      Attribution::Container { runtime, id } => write!(f, "{}:{}", runtime, id),
+    }
+  }
+}
+
+/// Parses the cgroup path out of `/proc/<pid>/cgroup`, preferring the cgroup v2 line (`0::/system.slice/...`) and
+/// falling back to the systemd hierarchy of a hybrid setup.
+pub fn parse_proc_cgroup(contents: &str) -> Option<&str> {
+  let path = |prefix: &str| contents.lines().find_map(|line| line.strip_prefix(prefix));
+  path("0::").filter(|path| *path != ROOT_PATH).or_else(|| {
+    contents.lines().find_map(|line| {
+      let (_, rest) = line.split_once(':')?;
+      rest.strip_prefix("name=systemd:")
+    })
+  })
+}
+
+fn container_id(id: &str) -> Option<String> {
+  let id = id.strip_suffix(".scope").unwrap_or(id);
+  (id.len() >= CONTAINER_ID_LENGTH && id.chars().all(|c| c.is_ascii_hexdigit()))
+    .then(|| id[..CONTAINER_ID_LENGTH].to_string())
+}
+
+/// Shortened pod UID of a pod cgroup, `kubepods-burstable-pod<uid>.slice` with systemd or `pod<uid>` with cgroupfs.
+fn pod_id(segment: &str) -> Option<String> {
+  let uid = segment.strip_prefix("pod").or_else(|| segment.rsplit_once("-pod").map(|(_, uid)| uid))?;
+  let uid = uid.trim_end_matches(".slice");
+  Some(format!("pod{}", uid.chars().take(CONTAINER_ID_LENGTH).collect::<String>()))
+}
+
+/// Derives what a cgroup path belongs to, without asking any container runtime. Containers are recognized by the
+/// scopes or directories their runtime creates, Kubernetes pods by the `kubepods` slices. Everything else is
+/// attributed to the deepest systemd unit, except for the `init.scope` holding a service manager's own process.
+pub fn attribute_cgroup(path: &str) -> Option<Attribution> {
+  let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+  let scoped_container = segments.iter().rev().find_map(|segment| {
+    CONTAINER_SCOPES.iter().find_map(|(prefix, runtime)| Some((*runtime, container_id(segment.strip_prefix(prefix)?)?)))
+  });
+  if segments.first().is_some_and(|segment| segment.starts_with("kubepods")) {
+    // The cgroupfs driver names the container directory after the bare id, e.g. `/kubepods/burstable/pod<uid>/<id>`
+    let id = scoped_container
+      .map(|(_, id)| id)
+      .or_else(|| segments.last().and_then(|segment| container_id(segment)))
+      .or_else(|| segments.iter().find_map(|segment| pod_id(segment)));
+    return id.map(|id| Attribution::Container { runtime: "kubepods".to_string(), id });
+  }
+  if let Some((runtime, id)) = scoped_container {
+    return Some(Attribution::Container { runtime: runtime.to_string(), id });
+  }
+  // Docker's cgroupfs driver, `/docker/<id>`
+  if let [.., "docker", id] = segments[..] {
+    if let Some(id) = container_id(id) {
+      return Some(Attribution::Container { runtime: "docker".to_string(), id });
+    }
+  }
+
+  let units: Vec<&str> =
+    segments.into_iter().filter(|segment| segment.ends_with(".service") || segment.ends_with(".scope")).collect();
+  match units[..] {
+    [] => None,
+    [.., parent, "init.scope"] => Some(Attribution::Unit(parent.to_string())),
+    [.., unit] => Some(Attribution::Unit(unit.to_string())),
+  }
+}
+
+/// Parses the CPU time in microseconds from `cpu.stat`, e.g. `usage_usec 1316357469`.
+pub fn parse_cpu_stat(contents: &str) -> Option<u64> {
+  contents.lines().find_map(|line| line.strip_prefix("usage_usec ")?.trim().parse().ok())
+}
+
+/// Parses `io.stat`, e.g. `8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0`, into the bytes
+/// read and written summed over all devices.
+pub fn parse_io_stat(contents: &str) -> (u64, u64) {
+  let mut totals = (0, 0);
+  for (key, value) in contents.split_whitespace().filter_map(|field| field.split_once('=')) {
+    match key {
+      "rbytes" => totals.0 += value.parse::<u64>().unwrap_or_default(),
+      "wbytes" => totals.1 += value.parse::<u64>().unwrap_or_default(),
+      _ => {},
+    }
+  }
+  totals
+}
+
+/// Reads one cgroup directory, `path` being relative to the cgroup v2 root.
+fn read_node(dir: &Path, path: &str, depth: usize, counters: &mut CgroupCounterMap) -> CgroupNode {
+  let io = fs::read_to_string(dir.join(IO_STAT)).ok().map(|contents| parse_io_stat(&contents));
+  counters.insert(path.to_string(), CgroupCounters {
+    cpu_usage_us: fs::read_to_string(dir.join(CPU_STAT)).ok().as_deref().and_then(parse_cpu_stat),
+    read_bytes: io.map(|(read, _)| read),
+    write_bytes: io.map(|(_, write)| write),
+  });
+  let pressure = PressureResource::iter()
+    .filter_map(|resource| {
+      let contents = fs::read_to_string(dir.join(format!("{}.pressure", resource.file_name()))).ok()?;
+      Some((resource, parse_pressure(&contents).0?.avg10))
+    })
+    .collect();
+
+  CgroupNode {
+    path: path.to_string(),
+    name: if path == ROOT_PATH { path.to_string() } else { path.rsplit('/').next().unwrap_or(path).to_string() },
+    depth,
+    has_children: false,
+    cpu_percent: None,
+    memory_current: read_value(dir.join(MEMORY_CURRENT)),
+    // `max` when unlimited
+    memory_max: read_value(dir.join(MEMORY_MAX)),
+    read_rate: None,
+    write_rate: None,
+    pids_current: read_value(dir.join(PIDS_CURRENT)),
+    pressure,
+    pids: read_trimmed(dir.join(CGROUP_PROCS))
+      .map(|procs| procs.lines().filter_map(|pid| pid.parse().ok()).collect())
+      .unwrap_or_default(),
+  }
+}
+
+/// Appends the cgroups below `dir` in depth first order.
+fn walk(dir: &Path, parent: &str, depth: usize, nodes: &mut Vec<CgroupNode>, counters: &mut CgroupCounterMap) {
+  for name in list_dir(dir) {
+    let child = dir.join(&name);
+    if !child.is_dir() {
+      continue;
+    }
+    let path = if parent == ROOT_PATH { name } else { format!("{}/{}", parent, name) };
+    nodes.push(read_node(&child, &path, depth, counters));
+    let index = nodes.len() - 1;
+    walk(&child, &path, depth + 1, nodes, counters);
+    nodes[index].has_children = nodes.len() > index + 1;
+  }
+}
+
+pub fn get_cgroup_info(history: &mut CounterHistory<CgroupCounterMap>, roots: &PseudoFsRoots) -> Result<CgroupData> {
+  let root = cgroup2_root(roots).ok_or_else(|| eyre!("no cgroup v2 hierarchy mounted"))?;
+  let mut counters = CgroupCounterMap::new();
+  let mut nodes = vec![read_node(&root, ROOT_PATH, 0, &mut counters)];
+  walk(&root, ROOT_PATH, 1, &mut nodes, &mut counters);
+  nodes[0].has_children = nodes.len() > 1;
+
+  if let Some((previous, elapsed)) = history.record(&counters) {
+    for node in &mut nodes {
+      let (Some(current), Some(previous)) = (counters.get(&node.path), previous.get(&node.path)) else {
+        continue;
+      };
+      let counter_rate = |previous: Option<u64>, current: Option<u64>| Some(rate(previous?, current?, elapsed));
+      // Microseconds of CPU time per second, divided by 10^4 to get a percentage of one CPU
+      node.cpu_percent = counter_rate(previous.cpu_usage_us, current.cpu_usage_us).map(|rate| rate / 10_000.0);
+      node.read_rate = counter_rate(previous.read_bytes, current.read_bytes);
+      node.write_rate = counter_rate(previous.write_bytes, current.write_bytes);
+    }
+  }
+
+  Ok(CgroupData { nodes })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_cpu_and_io_stat() {
+    assert_eq!(parse_cpu_stat("usage_usec 1316357469\nuser_usec 1187247102\n"), Some(1316357469));
+    assert_eq!(
+      parse_io_stat(
+        "8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0
+253:0 rbytes=800 wbytes=200 rios=2 wios=1 dbytes=0 dios=0
+"
+      ),
+      (1460000, 314773704)
+    );
+  }
+
+  #[test]
+  fn test_attribute_cgroup() {
+    let unit = |unit: &str| Some(Attribution::Unit(unit.to_string()));
+    let container =
+      |runtime: &str, id: &str| Some(Attribution::Container { runtime: runtime.to_string(), id: id.to_string() });
+    let id = "3f2a9c1b7d4e8a0f3f2a9c1b7d4e8a0f3f2a9c1b7d4e8a0f3f2a9c1b7d4e8a0f";
+
+    assert_eq!(attribute_cgroup("/system.slice/nginx.service"), unit("nginx.service"));
+    assert_eq!(attribute_cgroup("/user.slice/user-1000.slice/user@1000.service/init.scope"), unit("user@1000.service"));
+    assert_eq!(attribute_cgroup("/user.slice/user-1000.slice/session-2.scope"), unit("session-2.scope"));
+    assert_eq!(attribute_cgroup("/init.scope"), unit("init.scope"));
+    assert_eq!(attribute_cgroup(&format!("/system.slice/docker-{}.scope", id)), container("docker", "3f2a9c1b7d4e"));
+    assert_eq!(attribute_cgroup(&format!("/docker/{}", id)), container("docker", "3f2a9c1b7d4e"));
+    assert_eq!(
+      attribute_cgroup(&format!("/machine.slice/libpod-{}.scope/container", id)),
+      container("podman", "3f2a9c1b7d4e")
+    );
+    assert_eq!(
+      attribute_cgroup(&format!(
+        "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod0a1b2c3d_4e5f.slice/cri-containerd-{}.scope",
+        id
+      )),
+      container("kubepods", "3f2a9c1b7d4e")
+    );
+    assert_eq!(
+      attribute_cgroup("/kubepods/besteffort/pod0a1b2c3d-4e5f-6789"),
+      container("kubepods", "pod0a1b2c3d-4e5")
+    );
+    // Names are cut by characters, not bytes
+    assert_eq!(attribute_cgroup("/kubepods/podé7é7é7é7é7é7é7"), container("kubepods", "podé7é7é7é7é7é7"));
+    assert_eq!(attribute_cgroup("/"), None);
+    assert_eq!(
+      parse_proc_cgroup("12:pids:/user.slice\n1:name=systemd:/system.slice/cron.service\n0::/\n"),
+      Some("/system.slice/cron.service")
+    );
+  }
+
+  #[test]
+  fn test_get_cgroup_info_from_fixture() {
+    let fixture = FixtureTree::new("cgroups");
+    fixture
+      .write("sys/fs/cgroup/cgroup.controllers", "cpu io memory pids\n")
+      .write("sys/fs/cgroup/cgroup.procs", "1\n")
+      .write("sys/fs/cgroup/system.slice/cpu.stat", "usage_usec 1000000\n")
+      .write("sys/fs/cgroup/system.slice/memory.max", "max\n")
+      .write("sys/fs/cgroup/system.slice/nginx.service/cgroup.procs", "812\n813\n")
+      .write("sys/fs/cgroup/system.slice/nginx.service/memory.current", "52428800\n")
+      .write("sys/fs/cgroup/system.slice/nginx.service/memory.max", "104857600\n")
+      .write(
+        "sys/fs/cgroup/system.slice/nginx.service/io.pressure",
+        "some avg10=1.50 avg60=0.80 avg300=0.20 total=5000\n",
+      )
+      .write("sys/fs/cgroup/user.slice/cgroup.procs", "2041\n");
+    let mut history = CounterHistory::default();
+    let cgroups = get_cgroup_info(&mut history, &fixture.roots()).unwrap();
+
+    assert_eq!(
+      cgroups.nodes.iter().map(|node| (node.path.as_str(), node.depth, node.has_children)).collect::<Vec<_>>(),
+      vec![
+        ("/", 0, true),
+        ("system.slice", 1, true),
+        ("system.slice/nginx.service", 2, false),
+        ("user.slice", 1, false)
+      ]
+    );
+    let nginx = &cgroups.nodes[2];
+    assert_eq!(
+      (nginx.name.as_str(), nginx.memory_current, nginx.memory_max),
+      ("nginx.service", Some(52428800), Some(104857600))
+    );
+    assert_eq!(nginx.pressure, vec![(PressureResource::Io, 1.5)]);
+    assert_eq!(cgroups.nodes[1].memory_max, None);
+    assert_eq!(cgroups.member_pids("system.slice"), HashSet::from([812, 813]));
+    assert_eq!(cgroups.member_pids(ROOT_PATH).len(), 4);
+
+    fixture.write("sys/fs/cgroup/system.slice/cpu.stat", "usage_usec 1000000\n");
+    let cgroups = get_cgroup_info(&mut history, &fixture.roots()).unwrap();
+    assert_eq!(cgroups.nodes[1].cpu_percent, Some(0.0));
+    assert_eq!(cgroups.nodes[2].cpu_percent, None);
+  }
+}
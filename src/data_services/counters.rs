@@ -0,0 +1,53 @@
+use std::time::Instant;
+
+/// Remembers the previous reading of a source of cumulative kernel counters (e.g. `/proc/net/snmp`) so the change
+/// between two collections can be turned into a per second rate.
+#[derive(Debug, Clone)]
+pub struct CounterHistory<T> {
+  previous: Option<(Instant, T)>,
+}
+
+impl<T> Default for CounterHistory<T> {
+  fn default() -> Self {
+    Self { previous: None }
+  }
+}
+
+impl<T: Clone> CounterHistory<T> {
+  /// Stores `current` as the latest reading and returns the reading it replaced along with the seconds elapsed
+  /// since it was taken. Returns `None` for the first reading.
+  pub fn record(&mut self, current: &T) -> Option<(T, f64)> {
+    let now = Instant::now();
+    let previous = self.previous.replace((now, current.clone()));
+    previous.map(|(taken_at, value)| (value, now.duration_since(taken_at).as_secs_f64()))
+  }
+}
+
+/// Per second rate of a cumulative counter. Counter resets (current lower than previous) yield a rate of 0.
+pub fn rate(previous: u64, current: u64, elapsed_secs: f64) -> f64 {
+  if elapsed_secs <= 0.0 {
+    return 0.0;
+  }
+  current.saturating_sub(previous) as f64 / elapsed_secs
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_first_reading_has_no_previous() {
+    let mut history = CounterHistory::default();
+    assert_eq!(history.record(&1), None);
+    assert_eq!(history.record(&2).map(|(previous, _)| previous), Some(1));
+  }
+
+  #[test]
+  fn test_rate() {
+    assert_eq!(rate(100, 300, 2.0), 100.0);
+    assert_eq!(rate(300, 100, 2.0), 0.0);
+    assert_eq!(rate(100, 300, 0.0), 0.0);
+  }
+}
@@ -0,0 +1,172 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::{
+  counters::CounterHistory,
+  cpu_idle::cpu_number,
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots, DEVICES_CPU},
+};
+
+// Sampled frequencies are grouped in steps of 100 MHz, they rarely repeat exactly on hardware managed P-states
+const SAMPLE_STEP_KHZ: u64 = 100_000;
+
+/// Time spent at each frequency in kHz, in 10 ms units, as read from `cpufreq/stats/time_in_state`.
+pub type TimeInState = BTreeMap<u64, u64>;
+
+/// `time_in_state` tables keyed by CPU number.
+pub type CpuFreqCounters = BTreeMap<u32, TimeInState>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum FrequencySource {
+  #[strum(serialize = "time_in_state")]
+  TimeInState,
+  // The driver keeps no statistics (e.g. `intel_pstate`), the current frequency is sampled at every collection
+  #[strum(serialize = "sampled")]
+  Sampled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrequencyResidency {
+  pub frequency_khz: u64,
+  pub percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuFreqCore {
+  pub cpu: u32,
+  pub source: FrequencySource,
+  pub current_khz: Option<u64>,
+  pub min_khz: Option<u64>,
+  pub max_khz: Option<u64>,
+  pub governor: Option<String>,
+  // Share of the interval spent at each frequency, lowest frequency first. Only filled for `TimeInState` cores from
+  // the second collection on.
+  pub residency: Vec<FrequencyResidency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CpuFreqData {
+  // Scaling driver, e.g. `acpi-cpufreq` or `intel_pstate`
+  pub driver: Option<String>,
+  // Empty when the kernel exposes no cpufreq directory, as in most virtual machines
+  pub cores: Vec<CpuFreqCore>,
+}
+
+/// Parses the `<frequency in kHz> <time in 10 ms units>` lines of a `time_in_state` file.
+pub fn parse_time_in_state(contents: &str) -> TimeInState {
+  contents
+    .lines()
+    .filter_map(|line| {
+      let (frequency, time) = line.split_once(' ')?;
+      Some((frequency.trim().parse().ok()?, time.trim().parse().ok()?))
+    })
+    .collect()
+}
+
+/// Share of the time between two `time_in_state` readings spent at each frequency. Empty if no time was accounted,
+/// e.g. after the statistics were reset.
+pub fn interval_residency(previous: &TimeInState, current: &TimeInState) -> Vec<FrequencyResidency> {
+  let deltas: Vec<(u64, u64)> = current
+    .iter()
+    .map(|(frequency, time)| (*frequency, time.saturating_sub(previous.get(frequency).copied().unwrap_or_default())))
+    .collect();
+  let total: u64 = deltas.iter().map(|(_, delta)| delta).sum();
+  if total == 0 {
+    return Vec::new();
+  }
+  deltas
+    .into_iter()
+    .map(|(frequency_khz, delta)| FrequencyResidency { frequency_khz, percent: delta as f64 / total as f64 * 100.0 })
+    .collect()
+}
+
+/// Histogram of sampled frequencies in 100 MHz steps, for drivers without `time_in_state`.
+pub fn sampled_residency(samples: impl IntoIterator<Item = u64>) -> Vec<FrequencyResidency> {
+  let mut counts: BTreeMap<u64, u64> = BTreeMap::new();
+  for sample in samples {
+    let step = (sample + SAMPLE_STEP_KHZ / 2) / SAMPLE_STEP_KHZ * SAMPLE_STEP_KHZ;
+    *counts.entry(step).or_default() += 1;
+  }
+  let total: u64 = counts.values().sum();
+  counts
+    .into_iter()
+    .map(|(frequency_khz, count)| FrequencyResidency { frequency_khz, percent: count as f64 / total as f64 * 100.0 })
+    .collect()
+}
+
+fn read_counters(devices_cpu: &Path) -> CpuFreqCounters {
+  list_dir(devices_cpu)
+    .iter()
+    .filter_map(|name| {
+      let path = devices_cpu.join(name).join("cpufreq/stats/time_in_state");
+      let time_in_state = parse_time_in_state(&fs::read_to_string(path).ok()?);
+      // Drivers that only support stats partially leave the table empty
+      (!time_in_state.is_empty()).then_some((cpu_number(name)?, time_in_state))
+    })
+    .collect()
+}
+
+pub fn get_cpu_freq_info(history: &mut CounterHistory<CpuFreqCounters>, roots: &PseudoFsRoots) -> Result<CpuFreqData> {
+  let devices_cpu = roots.sys(DEVICES_CPU);
+  let current = read_counters(&devices_cpu);
+  let previous = history.record(&current).map(|(previous, _)| previous);
+
+  let cores = list_dir(&devices_cpu)
+    .iter()
+    .filter_map(|name| {
+      let cpu = cpu_number(name)?;
+      let cpufreq = devices_cpu.join(name).join("cpufreq");
+      if !cpufreq.exists() {
+        return None;
+      }
+      let (source, residency) = match (current.get(&cpu), previous.as_ref().and_then(|previous| previous.get(&cpu))) {
+        (Some(current), Some(previous)) => (FrequencySource::TimeInState, interval_residency(previous, current)),
+        (Some(_), None) => (FrequencySource::TimeInState, Vec::new()),
+        (None, _) => (FrequencySource::Sampled, Vec::new()),
+      };
+      Some(CpuFreqCore {
+        cpu,
+        source,
+        current_khz: read_value(cpufreq.join("scaling_cur_freq")),
+        min_khz: read_value(cpufreq.join("scaling_min_freq")),
+        max_khz: read_value(cpufreq.join("scaling_max_freq")),
+        governor: read_trimmed(cpufreq.join("scaling_governor")),
+        residency,
+      })
+    })
+    .collect();
+
+  Ok(CpuFreqData { driver: read_trimmed(devices_cpu.join("cpu0/cpufreq/scaling_driver")), cores })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_interval_residency() {
+    let previous = parse_time_in_state("800000 1000\n1600000 200\n2400000 50\n");
+    let current = parse_time_in_state("800000 1150\n1600000 200\n2400000 100\n");
+
+    assert_eq!(interval_residency(&previous, &current), vec![
+      FrequencyResidency { frequency_khz: 800_000, percent: 75.0 },
+      FrequencyResidency { frequency_khz: 1_600_000, percent: 0.0 },
+      FrequencyResidency { frequency_khz: 2_400_000, percent: 25.0 },
+    ]);
+    assert_eq!(interval_residency(&current, &current), vec![]);
+  }
+
+  #[test]
+  fn test_sampled_residency() {
+    let residency = sampled_residency([799_998, 2_793_120, 2_800_000, 2_812_000]);
+
+    assert_eq!(residency.len(), 2);
+    assert_eq!(residency[0], FrequencyResidency { frequency_khz: 800_000, percent: 25.0 });
+    assert_eq!(residency[1], FrequencyResidency { frequency_khz: 2_800_000, percent: 75.0 });
+  }
+}
@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, path::Path};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots, DEVICES_CPU},
+};
+
+/// Cumulative counters of one idle state of a CPU from `cpuN/cpuidle/stateK`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IdleStateSample {
+  pub name: String,
+  pub description: String,
+  // Exit latency in microseconds
+  pub latency_us: u64,
+  pub disabled: bool,
+  // Times the state was entered
+  pub usage: u64,
+  // Total time spent in the state in microseconds
+  pub time_us: u64,
+}
+
+/// Idle state counters keyed by CPU number.
+pub type CpuIdleCounters = BTreeMap<u32, Vec<IdleStateSample>>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdleStateResidency {
+  pub name: String,
+  pub description: String,
+  pub latency_us: u64,
+  pub disabled: bool,
+  // Share of the interval spent in the state
+  pub residency_percent: f64,
+  pub entries_per_sec: f64,
+  // Average time spent in the state per entry during the interval
+  pub average_residency_us: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuIdleCore {
+  pub cpu: u32,
+  // Share of the interval not spent in any idle state (C0)
+  pub active_percent: f64,
+  pub states: Vec<IdleStateResidency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CpuIdleData {
+  // cpuidle driver and governor, e.g. `intel_idle` and `menu`. The driver is `none` in most virtual machines.
+  pub driver: Option<String>,
+  pub governor: Option<String>,
+  // Empty on the first collection and when the driver exposes no idle states
+  pub cores: Vec<CpuIdleCore>,
+}
+
+/// Parses the CPU number out of a `cpuN` directory name, skipping entries such as `cpufreq` and `cpuidle`.
+pub(crate) fn cpu_number(name: &str) -> Option<u32> {
+  name.strip_prefix("cpu")?.parse().ok()
+}
+
+/// Residency of each idle state of a CPU between two samples taken `elapsed_secs` apart.
+pub fn core_residency(
+  cpu: u32,
+  previous: &[IdleStateSample],
+  current: &[IdleStateSample],
+  elapsed_secs: f64,
+) -> CpuIdleCore {
+  let elapsed_us = elapsed_secs * 1_000_000.0;
+  let states: Vec<IdleStateResidency> = current
+    .iter()
+    .map(|state| {
+      let previous = previous.iter().find(|p| p.name == state.name).unwrap_or(state);
+      let entries = state.usage.saturating_sub(previous.usage);
+      let time_us = state.time_us.saturating_sub(previous.time_us);
+      IdleStateResidency {
+        name: state.name.clone(),
+        description: state.description.clone(),
+        latency_us: state.latency_us,
+        disabled: state.disabled,
+        residency_percent: if elapsed_us > 0.0 { (time_us as f64 / elapsed_us * 100.0).min(100.0) } else { 0.0 },
+        entries_per_sec: rate(previous.usage, state.usage, elapsed_secs),
+        average_residency_us: (entries > 0).then(|| time_us as f64 / entries as f64),
+      }
+    })
+    .collect();
+  let idle_percent: f64 = states.iter().map(|state| state.residency_percent).sum();
+
+  CpuIdleCore { cpu, active_percent: (100.0 - idle_percent).max(0.0), states }
+}
+
+fn read_idle_states(cpu_path: &Path) -> Vec<IdleStateSample> {
+  let cpuidle = cpu_path.join("cpuidle");
+  let mut states: Vec<(u32, IdleStateSample)> = list_dir(&cpuidle)
+    .iter()
+    .filter_map(|name| {
+      let index = name.strip_prefix("state")?.parse::<u32>().ok()?;
+      let path = cpuidle.join(name);
+      Some((index, IdleStateSample {
+        name: read_trimmed(path.join("name")).unwrap_or_else(|| name.clone()),
+        description: read_trimmed(path.join("desc")).unwrap_or_default(),
+        latency_us: read_value(path.join("latency")).unwrap_or_default(),
+        disabled: read_value::<u8>(path.join("disable")) == Some(1),
+        usage: read_value(path.join("usage")).unwrap_or_default(),
+        time_us: read_value(path.join("time")).unwrap_or_default(),
+      }))
+    })
+    .collect();
+  // list_dir sorts by name, which puts state10 before state2
+  states.sort_by_key(|(index, _)| *index);
+  states.into_iter().map(|(_, state)| state).collect()
+}
+
+fn read_counters(devices_cpu: &Path) -> CpuIdleCounters {
+  list_dir(devices_cpu)
+    .iter()
+    .filter_map(|name| {
+      let states = read_idle_states(&devices_cpu.join(name));
+      // Offline CPUs have no cpuidle directory
+      (!states.is_empty()).then_some((cpu_number(name)?, states))
+    })
+    .collect()
+}
+
+pub fn get_cpu_idle_info(history: &mut CounterHistory<CpuIdleCounters>, roots: &PseudoFsRoots) -> Result<CpuIdleData> {
+  let devices_cpu = roots.sys(DEVICES_CPU);
+  let cpuidle = devices_cpu.join("cpuidle");
+  let current = read_counters(&devices_cpu);
+  let cores = match history.record(&current) {
+    Some((previous, elapsed_secs)) => {
+      current
+        .iter()
+        .map(|(cpu, states)| {
+          core_residency(*cpu, previous.get(cpu).map_or(&[][..], |states| states), states, elapsed_secs)
+        })
+        .collect()
+    },
+    None => Vec::new(),
+  };
+
+  Ok(CpuIdleData {
+    driver: read_trimmed(cpuidle.join("current_driver")),
+    governor: read_trimmed(cpuidle.join("current_governor"))
+      .or_else(|| read_trimmed(cpuidle.join("current_governor_ro"))),
+    cores,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn state(name: &str, usage: u64, time_us: u64) -> IdleStateSample {
+    IdleStateSample { name: name.into(), usage, time_us, ..Default::default() }
+  }
+
+  #[test]
+  fn test_core_residency() {
+    let previous = vec![state("POLL", 10, 1_000), state("C1", 100, 50_000), state("C6", 20, 200_000)];
+    let current = vec![state("POLL", 10, 1_000), state("C1", 300, 250_000), state("C6", 40, 1_000_000)];
+
+    let core = core_residency(3, &previous, &current, 2.0);
+
+    assert_eq!(core.cpu, 3);
+    assert_eq!(core.states[1].residency_percent, 10.0);
+    assert_eq!(core.states[1].entries_per_sec, 100.0);
+    assert_eq!(core.states[1].average_residency_us, Some(1_000.0));
+    assert_eq!(core.states[2].residency_percent, 40.0);
+    assert_eq!(core.states[0].average_residency_us, None);
+    assert_eq!(core.active_percent, 50.0);
+  }
+
+  #[test]
+  fn test_cpu_number() {
+    assert_eq!(cpu_number("cpu12"), Some(12));
+    assert_eq!(cpu_number("cpufreq"), None);
+  }
+}
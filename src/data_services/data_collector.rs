@@ -2,11 +2,34 @@ use color_eyre::eyre::{ErrReport, Result};
 use serde::{Deserialize, Serialize};
 
 use super::{
+  battery::{get_battery_info, BatteryDataCollection},
+  block_devices::{get_block_device_info, BlockDeviceCollection},
+  cgroups::{get_cgroup_info, CgroupCounterMap, CgroupData},
+  counters::CounterHistory,
   cpu::{get_cpu_info, CpuDataCollection},
+  cpu_freq::{get_cpu_freq_info, CpuFreqCounters, CpuFreqData},
+  cpu_idle::{get_cpu_idle_info, CpuIdleCounters, CpuIdleData},
+  disk_io::{get_disk_io_info, DiskIoDataCollection, DiskStats},
   disks::{get_disk_info, DiskDataCollection},
+  host::{get_host_info, HostData},
+  hwmon::read_hwmon_chips,
+  interrupts::{get_interrupt_info, InterruptCounters, InterruptData},
+  limits::{get_limits_info, KernelLimitCollection},
   memory::{get_memory_info, MemoryData},
+  net_stats::{get_net_stats_info, NetStatCounters, NetStatsDataCollection},
   network::{get_network_info, NetworkDataCollection},
+  pressure::{get_pressure_info, PressureCounters, PressureData},
   processes::{get_process_info, ProcessDataCollection},
+  pseudo_fs::PseudoFsRoots,
+  raid::{get_raid_info, RaidData},
+  rapl::{get_rapl_info, RaplCounters, RaplData},
+  runtime_pm::{get_runtime_pm_info, PmDeviceCollection},
+  sensors::{get_sensor_info, SensorDataCollection},
+  sockets::{get_socket_info, SocketDataCollection},
+  temperatures::{get_temperature_info, TemperatureDataCollection},
+  tunables::{get_tunable_info, TunableCollection},
+  vmstat::{get_vmstat_info, VmStatCounters, VmStatData},
+  wakeups::{get_wakeup_info, ProcessWakeupsCollection, WakeupCounters},
 };
 
 // TODO Should the data collection be broken into some combination if Traits?
@@ -17,16 +40,19 @@ use super::{
 //   fn collect(&self, params: Self::Params) -> Self::Output;
 // }
 
-/// Represents the source of system information, including system, disk, and network data.
+/// Represents the source of system information, including system, disk, network and sensor data, and the procfs and
+/// sysfs roots read directly. sysinfo always reads the live `/proc`, so it is left empty when reading a capture.
 #[derive(Debug)]
 pub struct SysinfoSource {
   pub(crate) system: sysinfo::System,
   pub(crate) disks: sysinfo::Disks,
   pub(crate) networks: sysinfo::Networks,
+  pub(crate) components: sysinfo::Components,
+  pub(crate) roots: PseudoFsRoots,
 }
 
 impl Default for SysinfoSource {
-  /// Creates a new `SysinfoSource` with refreshed lists of disks and networks.
+  /// Creates a new `SysinfoSource` with refreshed lists of disks, networks and components.
   fn default() -> Self {
     use sysinfo::*;
     Self {
@@ -38,10 +64,44 @@ impl Default for SysinfoSource {
       ),
       disks: Disks::new_with_refreshed_list(),
       networks: Networks::new_with_refreshed_list(),
+      components: Components::new_with_refreshed_list(),
+      roots: PseudoFsRoots::default(),
     }
   }
 }
 
+impl SysinfoSource {
+  /// Creates a source reading procfs and sysfs below `roots`. Captures get empty sysinfo data, which would otherwise
+  /// describe the machine running the monitor rather than the captured one.
+  pub fn with_roots(roots: PseudoFsRoots) -> Self {
+    if roots.is_live() {
+      return Self { roots, ..Self::default() };
+    }
+    Self {
+      system: sysinfo::System::new(),
+      disks: sysinfo::Disks::new(),
+      networks: sysinfo::Networks::new(),
+      components: sysinfo::Components::new(),
+      roots,
+    }
+  }
+}
+
+/// Holds the previous readings of cumulative kernel counters so collectors can report rates.
+#[derive(Debug, Default)]
+pub struct CounterSource {
+  pub(crate) net_stats: CounterHistory<NetStatCounters>,
+  pub(crate) disk_stats: CounterHistory<DiskStats>,
+  pub(crate) cpu_idle: CounterHistory<CpuIdleCounters>,
+  pub(crate) cpu_freq: CounterHistory<CpuFreqCounters>,
+  pub(crate) rapl: CounterHistory<RaplCounters>,
+  pub(crate) wakeups: CounterHistory<WakeupCounters>,
+  pub(crate) pressure: CounterHistory<PressureCounters>,
+  pub(crate) vmstat: CounterHistory<VmStatCounters>,
+  pub(crate) interrupts: CounterHistory<InterruptCounters>,
+  pub(crate) cgroups: CounterHistory<CgroupCounterMap>,
+}
+
 /// A structure holding collected data from various system components.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct DataCollected {
@@ -50,6 +110,26 @@ pub struct DataCollected {
   pub disk: Option<DiskDataCollection>,
   pub networks: Option<NetworkDataCollection>,
   pub memory: Option<MemoryData>,
+  pub sockets: Option<SocketDataCollection>,
+  pub net_stats: Option<NetStatsDataCollection>,
+  pub disk_io: Option<DiskIoDataCollection>,
+  pub block_devices: Option<BlockDeviceCollection>,
+  pub raid: Option<RaidData>,
+  pub temperatures: Option<TemperatureDataCollection>,
+  pub sensors: Option<SensorDataCollection>,
+  pub batteries: Option<BatteryDataCollection>,
+  pub cpu_idle: Option<CpuIdleData>,
+  pub cpu_freq: Option<CpuFreqData>,
+  pub rapl: Option<RaplData>,
+  pub wakeups: Option<ProcessWakeupsCollection>,
+  pub tunables: Option<TunableCollection>,
+  pub runtime_pm: Option<PmDeviceCollection>,
+  pub host: Option<HostData>,
+  pub pressure: Option<PressureData>,
+  pub vmstat: Option<VmStatData>,
+  pub limits: Option<KernelLimitCollection>,
+  pub interrupts: Option<InterruptData>,
+  pub cgroups: Option<CgroupData>,
 }
 
 /// Manages the collection of data from the system, including CPU, processes, disks, and networks.
@@ -57,6 +137,7 @@ pub struct DataCollected {
 pub struct DataCollector {
   pub data: DataCollected,
   sys: SysinfoSource,
+  counters: CounterSource,
 }
 
 impl Default for DataCollector {
@@ -69,22 +150,96 @@ impl Default for DataCollector {
 impl DataCollector {
   /// Creates a new `DataCollector` instance with default data and system information source.
   pub fn new() -> Self {
-    DataCollector { data: DataCollected::default(), sys: SysinfoSource::default() }
+    Self::with_roots(PseudoFsRoots::default())
+  }
+
+  /// Creates a new `DataCollector` reading procfs and sysfs below the given roots.
+  pub fn with_roots(roots: PseudoFsRoots) -> Self {
+    DataCollector {
+      data: DataCollected::default(),
+      sys: SysinfoSource::with_roots(roots),
+      counters: CounterSource::default(),
+    }
   }
 
   /// Updates all the collected data by refreshing system information and then collecting
-  /// data for CPU, processes, disks, networks and memory.
+  /// data for CPU, processes, disks, networks, memory, sockets, network stack counters, disk I/O,
+  /// block devices, RAID, temperatures, other hardware sensors, batteries, CPU idle states,
+  /// CPU frequencies, RAPL energy counters, per process wakeups, power tunables, device
+  /// runtime power management, host identity, uptime and load, pressure stall information,
+  /// virtual memory activity, kernel resource limits, interrupt rates and the cgroup hierarchy. CPU usage, processes,
+  /// disks and networks only come from sysinfo and are not collected when reading a capture.
   pub fn update_data(&mut self) {
-    self.refresh_sysinfo();
+    if self.sys.roots.is_live() {
+      self.refresh_sysinfo();
 
-    self.data.cpu = self.update_info(|sys: &SysinfoSource| get_cpu_info(&sys.system), "CPU");
-    self.data.processes = self.update_info(|sys: &SysinfoSource| get_process_info(&sys.system), "Process");
-    self.data.disk = self.update_info(|sys: &SysinfoSource| get_disk_info(&sys.disks), "Disk");
-    self.data.networks = self.update_info(|sys: &SysinfoSource| get_network_info(&sys.networks), "Network");
-    self.data.memory = self.update_info(|sys: &SysinfoSource| get_memory_info(&sys.system), "Memory");
+      self.data.cpu = self.update_info(|sys: &SysinfoSource| get_cpu_info(&sys.system), "CPU");
+      self.data.processes =
+        self.update_info(|sys: &SysinfoSource| get_process_info(&sys.system, &sys.roots), "Process");
+      self.data.disk = self.update_info(|sys: &SysinfoSource| get_disk_info(&sys.disks), "Disk");
+      self.data.networks =
+        self.update_info(|sys: &SysinfoSource| get_network_info(&sys.networks, &sys.roots), "Network");
+    }
+    self.data.memory = self.update_info(|sys: &SysinfoSource| get_memory_info(&sys.system, &sys.roots), "Memory");
+    self.data.sockets = self.update_info(|sys: &SysinfoSource| get_socket_info(&sys.system, &sys.roots), "Socket");
+    self.data.net_stats = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_net_stats_info(&mut counters.net_stats, &sys.roots),
+      "NetStats",
+    );
+    self.data.disk_io = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| {
+        get_disk_io_info(&mut counters.disk_stats, &sys.disks, &sys.roots)
+      },
+      "Disk I/O",
+    );
+    self.data.block_devices =
+      self.update_info(|sys: &SysinfoSource| get_block_device_info(&sys.disks, &sys.roots), "Block Device");
+    self.data.raid = self.update_info(|sys: &SysinfoSource| get_raid_info(&sys.roots), "RAID");
+    // Temperatures and the other hardware sensors share the hwmon chips
+    let chips = read_hwmon_chips(&self.sys.roots);
+    self.data.temperatures =
+      self.update_info(|sys: &SysinfoSource| get_temperature_info(&sys.components, &chips, &sys.roots), "Temperature");
+    self.data.sensors = self.update_info(|_: &SysinfoSource| get_sensor_info(&chips), "Sensor");
+    self.data.batteries = self.update_info(|sys: &SysinfoSource| get_battery_info(&sys.roots), "Battery");
+    self.data.cpu_idle = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_cpu_idle_info(&mut counters.cpu_idle, &sys.roots),
+      "CPU Idle",
+    );
+    self.data.cpu_freq = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_cpu_freq_info(&mut counters.cpu_freq, &sys.roots),
+      "CPU Frequency",
+    );
+    self.data.rapl = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_rapl_info(&mut counters.rapl, &sys.roots),
+      "RAPL",
+    );
+    self.data.wakeups = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_wakeup_info(&mut counters.wakeups, &sys.roots),
+      "Wakeups",
+    );
+    self.data.tunables = self.update_info(|sys: &SysinfoSource| get_tunable_info(&sys.roots), "Tunable");
+    self.data.runtime_pm = self.update_info(|sys: &SysinfoSource| get_runtime_pm_info(&sys.roots), "Runtime PM");
+    self.data.host = self.update_info(|sys: &SysinfoSource| get_host_info(&sys.roots), "Host");
+    self.data.pressure = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_pressure_info(&mut counters.pressure, &sys.roots),
+      "Pressure",
+    );
+    self.data.vmstat = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_vmstat_info(&mut counters.vmstat, &sys.roots),
+      "VM Stat",
+    );
+    self.data.limits = self.update_info(|sys: &SysinfoSource| get_limits_info(&sys.roots), "Limits");
+    self.data.interrupts = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_interrupt_info(&mut counters.interrupts, &sys.roots),
+      "Interrupts",
+    );
+    self.data.cgroups = self.update_counter_info(
+      |sys: &SysinfoSource, counters: &mut CounterSource| get_cgroup_info(&mut counters.cgroups, &sys.roots),
+      "Cgroups",
+    );
   }
 
-  /// Refreshes system information, including networks, CPU, processes, and disks.
+  /// Refreshes system information, including networks, CPU, processes, disks and components.
   fn refresh_sysinfo(&mut self) {
     self.sys.networks.refresh();
     // self.sys.networks.refresh_list();
@@ -101,6 +256,9 @@ impl DataCollector {
 
     // memory (RAM & SWAP)
     self.sys.system.refresh_memory();
+
+    // temperature sensors
+    self.sys.components.refresh();
   }
 
   /// Collects information using the provided function and logs the result.
@@ -118,15 +276,40 @@ impl DataCollector {
     F: Fn(&SysinfoSource) -> Result<T, ErrReport>,
     T: std::fmt::Debug,
   {
-    match get_info(&self.sys) {
-      Ok(info) => {
-        log::debug!("Collected {} Data: {:?}", info_type, info);
-        Some(info)
-      },
-      Err(_) => {
-        log::warn!("Failed to collect {} Data", info_type);
-        None
-      },
-    }
+    log_collected(get_info(&self.sys), info_type)
+  }
+
+  /// Collects information from sources of cumulative counters, which need to remember their previous reading in
+  /// order to compute rates.
+  ///
+  /// # Arguments
+  ///
+  /// * `get_info` - A function that collects the information from the `SysinfoSource` and updates the
+  ///   `CounterSource`.
+  /// * `info_type` - A string representing the type of information being collected.
+  ///
+  /// # Returns
+  ///
+  /// An `Option` containing the collected data if successful, or `None` if there was an error.
+  fn update_counter_info<F, T>(&mut self, get_info: F, info_type: &str) -> Option<T>
+  where
+    F: FnOnce(&SysinfoSource, &mut CounterSource) -> Result<T, ErrReport>,
+    T: std::fmt::Debug,
+  {
+    log_collected(get_info(&self.sys, &mut self.counters), info_type)
+  }
+}
+
+/// Logs the outcome of a collection, converting it to an `Option`.
+fn log_collected<T: std::fmt::Debug>(result: Result<T, ErrReport>, info_type: &str) -> Option<T> {
+  match result {
+    Ok(info) => {
+      log::debug!("Collected {} Data: {:?}", info_type, info);
+      Some(info)
+    },
+    Err(e) => {
+      log::warn!("Failed to collect {} Data: {}", info_type, e);
+      None
+    },
   }
 }
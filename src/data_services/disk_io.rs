@@ -0,0 +1,170 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+
+use super::{
+  counters::{rate, CounterHistory},
+  disks::mount_points_by_device,
+  pseudo_fs::{PseudoFsRoots, SECTOR_SIZE},
+};
+
+const DISKSTATS: &str = "diskstats";
+
+/// Cumulative counters of a single block device from `/proc/diskstats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiskStatsSample {
+  pub reads: u64,
+  pub sectors_read: u64,
+  pub read_ms: u64,
+  pub writes: u64,
+  pub sectors_written: u64,
+  pub write_ms: u64,
+  pub in_flight: u64,
+  pub io_ms: u64,
+  pub weighted_io_ms: u64,
+}
+
+/// Raw `/proc/diskstats` counters keyed by kernel device name (e.g. `nvme0n1p2`, `dm-0`).
+pub type DiskStats = HashMap<String, DiskStatsSample>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct DiskIoData {
+  pub device: String,
+  // Mount points of the device as listed by the disk table, empty for unmounted devices
+  pub mount_points: Vec<String>,
+  pub read_bytes_per_sec: f64,
+  pub write_bytes_per_sec: f64,
+  pub read_iops: f64,
+  pub write_iops: f64,
+  // Average time in milliseconds an I/O request took to be served (`await` in iostat)
+  pub await_ms: f64,
+  // Average number of requests queued or being served (`aqu-sz` in iostat)
+  pub queue_depth: f64,
+  pub utilization_percent: f64,
+}
+
+pub type DiskIoDataCollection = Vec<DiskIoData>;
+
+/// Parses `/proc/diskstats`. Lines with fewer than the 11 original counter columns are skipped.
+pub fn parse_diskstats(contents: &str) -> DiskStats {
+  contents
+    .lines()
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      if fields.len() < 14 {
+        return None;
+      }
+      let value = |i: usize| fields[i].parse::<u64>().unwrap_or_default();
+      Some((fields[2].to_string(), DiskStatsSample {
+        reads: value(3),
+        sectors_read: value(5),
+        read_ms: value(6),
+        writes: value(7),
+        sectors_written: value(9),
+        write_ms: value(10),
+        in_flight: value(11),
+        io_ms: value(12),
+        weighted_io_ms: value(13),
+      }))
+    })
+    .collect()
+}
+
+impl DiskIoData {
+  /// Computes the rates of a device between two samples taken `elapsed_secs` apart.
+  pub fn from_samples(device: &str, previous: &DiskStatsSample, current: &DiskStatsSample, elapsed_secs: f64) -> Self {
+    let elapsed_ms = elapsed_secs * 1000.0;
+    let ios = current.reads.saturating_sub(previous.reads) + current.writes.saturating_sub(previous.writes);
+    let io_wait_ms =
+      current.read_ms.saturating_sub(previous.read_ms) + current.write_ms.saturating_sub(previous.write_ms);
+
+    DiskIoData {
+      device: device.to_string(),
+      mount_points: Vec::new(),
+      read_bytes_per_sec: rate(previous.sectors_read, current.sectors_read, elapsed_secs) * SECTOR_SIZE as f64,
+      write_bytes_per_sec: rate(previous.sectors_written, current.sectors_written, elapsed_secs) * SECTOR_SIZE as f64,
+      read_iops: rate(previous.reads, current.reads, elapsed_secs),
+      write_iops: rate(previous.writes, current.writes, elapsed_secs),
+      await_ms: if ios > 0 { io_wait_ms as f64 / ios as f64 } else { 0.0 },
+      queue_depth: if elapsed_ms > 0.0 {
+        current.weighted_io_ms.saturating_sub(previous.weighted_io_ms) as f64 / elapsed_ms
+      } else {
+        0.0
+      },
+      utilization_percent: if elapsed_ms > 0.0 {
+        (current.io_ms.saturating_sub(previous.io_ms) as f64 / elapsed_ms * 100.0).min(100.0)
+      } else {
+        0.0
+      },
+    }
+  }
+}
+
+pub fn get_disk_io_info(
+  history: &mut CounterHistory<DiskStats>,
+  disks: &Disks,
+  roots: &PseudoFsRoots,
+) -> Result<DiskIoDataCollection> {
+  let current = parse_diskstats(&fs::read_to_string(roots.proc(DISKSTATS))?);
+  let Some((previous, elapsed_secs)) = history.record(&current) else {
+    return Ok(Vec::new());
+  };
+  let mut mount_points = mount_points_by_device(disks);
+
+  let mut devices: DiskIoDataCollection = current
+    .iter()
+    // Skip devices that never did any I/O, e.g. unused loop and ram devices
+    .filter(|(_, sample)| sample.reads + sample.writes > 0)
+    .map(|(device, sample)| {
+      let mut data = DiskIoData::from_samples(device, previous.get(device).unwrap_or(sample), sample, elapsed_secs);
+      data.mount_points = mount_points.remove(device).unwrap_or_default();
+      data
+    })
+    .collect();
+  devices.sort_by(|a, b| a.device.cmp(&b.device));
+
+  Ok(devices)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_diskstats() {
+    let contents = "   7       0 loop0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 259       0 nvme0n1 7169 5205 2521474 11275 5170 24913 4970400 23939 2 5068 35775 2034 0 1019992 559 57 1";
+    let stats = parse_diskstats(contents);
+
+    let nvme = stats.get("nvme0n1").unwrap();
+    assert_eq!(nvme.reads, 7169);
+    assert_eq!(nvme.sectors_written, 4970400);
+    assert_eq!(nvme.in_flight, 2);
+    assert_eq!(nvme.weighted_io_ms, 35775);
+    assert_eq!(stats.len(), 2);
+  }
+
+  #[test]
+  fn test_rates_between_samples() {
+    let previous = DiskStatsSample { reads: 100, sectors_read: 1000, read_ms: 50, io_ms: 1000, ..Default::default() };
+    let current = DiskStatsSample {
+      reads: 300,
+      sectors_read: 5000,
+      read_ms: 450,
+      io_ms: 1500,
+      weighted_io_ms: 1000,
+      ..Default::default()
+    };
+    let data = DiskIoData::from_samples("sda", &previous, &current, 2.0);
+
+    assert_eq!(data.read_iops, 100.0);
+    assert_eq!(data.read_bytes_per_sec, 2000.0 * 512.0);
+    assert_eq!(data.await_ms, 2.0);
+    assert_eq!(data.utilization_percent, 25.0);
+    assert_eq!(data.queue_depth, 0.5);
+  }
+}
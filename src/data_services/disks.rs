@@ -1,4 +1,4 @@
-use std::{any::Any, collections::HashMap, ffi::OsStr, path::Path};
+use std::{any::Any, collections::HashMap, ffi::OsStr, fs, path::Path};
 
 use color_eyre::eyre::Result;
 use serde::{Deserialize, Serialize};
@@ -13,9 +13,32 @@ pub struct DiskData {
   pub available_space: u64,
   pub is_removable: bool,
   pub mount_path: String,
+  // Inode counts from statvfs, `None` for file systems without a fixed inode table (e.g. btrfs)
+  pub total_inodes: Option<u64>,
+  pub free_inodes: Option<u64>,
 }
 pub type DiskDataCollection = Vec<DiskData>;
 
+impl DiskData {
+  pub fn used_space(&self) -> u64 {
+    self.total_space.saturating_sub(self.available_space)
+  }
+
+  pub fn used_percentage(&self) -> f64 {
+    if self.total_space == 0 {
+      return 0.0;
+    }
+    self.used_space() as f64 / self.total_space as f64 * 100.0
+  }
+
+  pub fn inode_used_percentage(&self) -> Option<f64> {
+    match (self.total_inodes, self.free_inodes) {
+      (Some(total), Some(free)) if total > 0 => Some(total.saturating_sub(free) as f64 / total as f64 * 100.0),
+      _ => None,
+    }
+  }
+}
+
 impl From<&Disk> for DiskData {
   fn from(disk: &Disk) -> Self {
     DiskData {
@@ -27,6 +50,8 @@ impl From<&Disk> for DiskData {
       available_space: disk.available_space(), // in bytes
       is_removable: disk.is_removable(),
       mount_path: disk.mount_point().to_string_lossy().into_owned(),
+      total_inodes: None,
+      free_inodes: None,
     }
   }
 }
@@ -37,10 +62,60 @@ struct DisksWrapper<'a> {
 }
 impl<'a> From<DisksWrapper<'a>> for DiskDataCollection {
   fn from(wrapper: DisksWrapper<'a>) -> Self {
-    wrapper.disks.iter().map(|disk| disk.into()).collect()
+    wrapper
+      .disks
+      .iter()
+      .map(|disk| {
+        let mut data: DiskData = disk.into();
+        if let Some((total, free)) = inode_usage(disk.mount_point()) {
+          data.total_inodes = Some(total);
+          data.free_inodes = Some(free);
+        }
+        data
+      })
+      .collect()
   }
 }
 
+/// Maps kernel device names to the mount points of the disks sysinfo reports. Disk names such as
+/// `/dev/mapper/root` are symlinks, so they are resolved to the kernel name (`dm-0`) used by `/proc/diskstats`
+/// and `/sys/class/block`.
+pub(crate) fn mount_points_by_device(disks: &Disks) -> HashMap<String, Vec<String>> {
+  let mut mount_points: HashMap<String, Vec<String>> = HashMap::new();
+  for disk in disks.iter() {
+    let name = Path::new(disk.name());
+    let resolved = fs::canonicalize(name).unwrap_or_else(|_| name.to_path_buf());
+    if let Some(device) = resolved.file_name() {
+      mount_points
+        .entry(device.to_string_lossy().into_owned())
+        .or_default()
+        .push(disk.mount_point().to_string_lossy().into_owned());
+    }
+  }
+  mount_points
+}
+
+/// Returns the total and free inode counts of the file system mounted at `mount_point` using `statvfs(3)`.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // fsfilcnt_t is not 64 bit on every platform
+pub(crate) fn inode_usage(mount_point: &Path) -> Option<(u64, u64)> {
+  use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+  let path = CString::new(mount_point.as_os_str().as_bytes()).ok()?;
+  // SAFETY: statvfs is plain old data, zero is a valid bit pattern for all of its fields.
+  let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+  // SAFETY: `path` is NUL terminated and `stat` is a valid, writable statvfs.
+  if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 || stat.f_files == 0 {
+    return None;
+  }
+  Some((stat.f_files as u64, stat.f_ffree as u64))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn inode_usage(mount_point: &Path) -> Option<(u64, u64)> {
+  None
+}
+
 pub fn get_disk_info(disks: &Disks) -> Result<DiskDataCollection> {
   Ok(DisksWrapper { disks }.into())
 }
@@ -0,0 +1,178 @@
+use std::{fs, time::Duration};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+use super::pseudo_fs::{list_dir, read_trimmed, PseudoFsRoots, DEVICES_CPU};
+
+pub(crate) const LOADAVG: &str = "loadavg";
+const UPTIME: &str = "uptime";
+const STAT: &str = "stat";
+const HOSTNAME: &str = "sys/kernel/hostname";
+const OSRELEASE: &str = "sys/kernel/osrelease";
+// os-release lives outside procfs and sysfs, so the distribution always describes the machine running the monitor and
+// is left out for a capture, as is the architecture sysinfo reports
+const OS_RELEASE: [&str; 2] = ["/etc/os-release", "/usr/lib/os-release"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LoadAverage {
+  pub one: f64,
+  pub five: f64,
+  pub fifteen: f64,
+}
+
+impl LoadAverage {
+  /// Load divided by the number of CPUs, so 1.0 means every CPU was busy or had work waiting on average.
+  pub fn per_core(&self, cpus: usize) -> LoadAverage {
+    let cpus = cpus.max(1) as f64;
+    LoadAverage { one: self.one / cpus, five: self.five / cpus, fifteen: self.fifteen / cpus }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostData {
+  pub hostname: Option<String>,
+  pub kernel: Option<String>,
+  pub distribution: Option<String>,
+  pub architecture: Option<String>,
+  pub uptime: Duration,
+  // Seconds since the Unix epoch
+  pub boot_time: Option<u64>,
+  pub load: LoadAverage,
+  pub cpus: usize,
+  pub processes: usize,
+  // Threads of all processes, kernel threads included
+  pub threads: u64,
+  // Threads currently runnable
+  pub running: u64,
+}
+
+/// Parses `/proc/loadavg`, e.g. `0.52 0.40 0.33 2/1204 91234`, into the load averages and the runnable and total
+/// thread counts.
+pub fn parse_loadavg(contents: &str) -> Option<(LoadAverage, u64, u64)> {
+  let mut fields = contents.split_whitespace();
+  let mut next_load = || fields.next()?.parse::<f64>().ok();
+  let load = LoadAverage { one: next_load()?, five: next_load()?, fifteen: next_load()? };
+  let (running, threads) = fields.next()?.split_once('/')?;
+  Some((load, running.parse().ok()?, threads.parse().ok()?))
+}
+
+/// Parses the seconds since boot, the first field of `/proc/uptime`.
+pub fn parse_uptime(contents: &str) -> Option<Duration> {
+  Some(Duration::from_secs_f64(contents.split_whitespace().next()?.parse().ok()?))
+}
+
+/// Parses the boot time in seconds since the Unix epoch from the `btime` line of `/proc/stat`.
+pub fn parse_boot_time(stat: &str) -> Option<u64> {
+  stat.lines().find_map(|line| line.strip_prefix("btime")?.trim().parse().ok())
+}
+
+/// Parses the distribution name out of an `os-release` file, preferring `PRETTY_NAME` over `NAME`.
+pub fn parse_os_release(contents: &str) -> Option<String> {
+  let value = |key: &str| {
+    contents
+      .lines()
+      .find_map(|line| Some(line.strip_prefix(key)?.strip_prefix('=')?.trim().trim_matches('"').to_string()))
+  };
+  value("PRETTY_NAME").or_else(|| value("NAME")).filter(|name| !name.is_empty())
+}
+
+/// Counts the CPUs in a kernel CPU list such as `0-3,6,8-9`.
+pub fn parse_cpu_list(list: &str) -> usize {
+  list
+    .split(',')
+    .filter_map(|range| {
+      match range.trim().split_once('-') {
+        Some((first, last)) => Some(last.parse::<usize>().ok()?.checked_sub(first.parse().ok()?)? + 1),
+        None => range.trim().parse::<usize>().ok().map(|_| 1),
+      }
+    })
+    .sum()
+}
+
+pub fn get_host_info(roots: &PseudoFsRoots) -> Result<HostData> {
+  let (load, running, threads) = parse_loadavg(&fs::read_to_string(roots.proc(LOADAVG))?).unwrap_or_default();
+  let cpus = match read_trimmed(roots.sys(DEVICES_CPU).join("online")).map(|list| parse_cpu_list(&list)) {
+    Some(cpus) if cpus > 0 => cpus,
+    _ => std::thread::available_parallelism().map_or(1, |cpus| cpus.get()),
+  };
+
+  let live = roots.is_live();
+  Ok(HostData {
+    hostname: read_trimmed(roots.proc(HOSTNAME)).or_else(|| live.then(System::host_name).flatten()),
+    kernel: read_trimmed(roots.proc(OSRELEASE)),
+    distribution: live
+      .then(|| OS_RELEASE.iter().find_map(|path| parse_os_release(&fs::read_to_string(path).ok()?)))
+      .flatten(),
+    architecture: live.then(System::cpu_arch).flatten(),
+    uptime: fs::read_to_string(roots.proc(UPTIME)).ok().as_deref().and_then(parse_uptime).unwrap_or_default(),
+    boot_time: fs::read_to_string(roots.proc(STAT)).ok().as_deref().and_then(parse_boot_time),
+    load,
+    cpus,
+    processes: list_dir(&roots.proc_root).iter().filter(|name| name.parse::<u32>().is_ok()).count(),
+    threads,
+    running,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_loadavg() {
+    let (load, running, threads) = parse_loadavg("0.52 0.40 0.33 2/1204 91234\n").unwrap();
+
+    assert_eq!(load, LoadAverage { one: 0.52, five: 0.40, fifteen: 0.33 });
+    assert_eq!((running, threads), (2, 1204));
+    assert_eq!(load.per_core(4).one, 0.13);
+    assert_eq!(parse_loadavg("0.52 0.40"), None);
+  }
+
+  #[test]
+  fn test_parse_uptime_and_boot_time() {
+    assert_eq!(parse_uptime("350735.47 234388.90\n"), Some(Duration::from_secs_f64(350735.47)));
+    assert_eq!(
+      parse_boot_time("cpu  2255 34 2290 22625563\nintr 114930548 113199788\nbtime 1760688000\n"),
+      Some(1760688000)
+    );
+  }
+
+  #[test]
+  fn test_parse_os_release() {
+    let os_release = "NAME=\"Debian GNU/Linux\"
+VERSION_ID=\"12\"
+PRETTY_NAME=\"Debian GNU/Linux 12 (bookworm)\"
+ID=debian
+";
+    assert_eq!(parse_os_release(os_release), Some("Debian GNU/Linux 12 (bookworm)".to_string()));
+    assert_eq!(parse_os_release("NAME=Arch Linux\nID=arch\n"), Some("Arch Linux".to_string()));
+  }
+
+  #[test]
+  fn test_get_host_info_from_capture() {
+    let fixture = FixtureTree::new("host");
+    fixture
+      .write("proc/loadavg", "0.52 0.40 0.33 2/1204 91234\n")
+      .write("proc/sys/kernel/osrelease", "6.1.0-13-amd64\n")
+      .write("sys/devices/system/cpu/online", "0-3\n")
+      .create_dir("proc/1");
+    let host = get_host_info(&fixture.roots()).unwrap();
+
+    assert_eq!(host.kernel.as_deref(), Some("6.1.0-13-amd64"));
+    // Nothing about the machine running the tests leaks into the capture
+    assert_eq!((host.hostname, host.distribution, host.architecture), (None, None, None));
+    assert_eq!((host.cpus, host.processes, host.threads), (4, 1, 1204));
+  }
+
+  #[test]
+  fn test_parse_cpu_list() {
+    assert_eq!(parse_cpu_list("0-3,6,8-9\n"), 7);
+    assert_eq!(parse_cpu_list("0"), 1);
+    assert_eq!(parse_cpu_list(""), 0);
+  }
+}
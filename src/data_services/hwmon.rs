@@ -0,0 +1,116 @@
+use std::{collections::BTreeMap, path::Path};
+
+use super::pseudo_fs::{list_dir, read_trimmed, PseudoFsRoots};
+
+const CLASS_HWMON: &str = "class/hwmon";
+
+// Attribute prefixes of the sensor channels we read, see the kernel's Documentation/hwmon/sysfs-interface.rst
+const SENSOR_KINDS: [&str; 5] = ["temp", "fan", "in", "power", "curr"];
+
+/// A hardware monitoring chip (`/sys/class/hwmon/hwmonN`) with the raw contents of its sensor attributes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HwmonChip {
+  // Directory name, e.g. `hwmon2`. Stable for the lifetime of the driver, unlike the order of chips.
+  pub id: String,
+  // Driver name, e.g. `coretemp`, `nct6775`, `nvme`
+  pub name: String,
+  // Attribute file name (`temp1_input`) to its contents
+  pub attributes: BTreeMap<String, String>,
+}
+
+/// All attributes of one sensor of a chip, e.g. `temp1_input`, `temp1_crit` and `temp1_label`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HwmonChannel {
+  pub index: u32,
+  pub label: String,
+  // Numeric attributes keyed by their suffix (`input`, `max`, `crit`), in the raw sysfs units
+  pub values: BTreeMap<String, i64>,
+}
+
+/// Splits an attribute name such as `temp1_input` into its kind, channel index and item.
+fn parse_attribute_name(name: &str) -> Option<(&str, u32, &str)> {
+  let (channel, item) = name.split_once('_')?;
+  let index_start = channel.find(|c: char| c.is_ascii_digit())?;
+  let (kind, index) = channel.split_at(index_start);
+  Some((kind, index.parse().ok()?, item))
+}
+
+impl HwmonChip {
+  fn read(hwmon: &Path, id: &str) -> Self {
+    let path = hwmon.join(id);
+    let attributes = list_dir(&path)
+      .into_iter()
+      .filter(|name| parse_attribute_name(name).is_some_and(|(kind, _, _)| SENSOR_KINDS.contains(&kind)))
+      // Some attributes are write only (e.g. `*_reset_history`) and fail to read
+      .filter_map(|name| Some((name.clone(), read_trimmed(path.join(&name))?)))
+      .collect();
+
+    HwmonChip {
+      id: id.to_string(),
+      name: read_trimmed(path.join("name")).unwrap_or_else(|| id.to_string()),
+      attributes,
+    }
+  }
+
+  /// Groups the attributes of the given kind (`temp`, `fan`, `in`, `power`, `curr`) into channels, ordered by index.
+  /// Channels without an `input` reading are left out. Unlabeled channels are named after their attribute prefix,
+  /// e.g. `temp2`.
+  pub fn channels(&self, kind: &str) -> Vec<HwmonChannel> {
+    let mut channels: BTreeMap<u32, HwmonChannel> = BTreeMap::new();
+    for (name, contents) in &self.attributes {
+      let Some((attribute_kind, index, item)) = parse_attribute_name(name) else {
+        continue;
+      };
+      if attribute_kind != kind {
+        continue;
+      }
+      let channel = channels
+        .entry(index)
+        .or_insert_with(|| HwmonChannel { index, label: format!("{}{}", kind, index), values: BTreeMap::new() });
+      if item == "label" {
+        channel.label = contents.clone();
+      } else if let Ok(value) = contents.parse() {
+        channel.values.insert(item.to_string(), value);
+      }
+    }
+    channels.into_values().filter(|channel| channel.values.contains_key("input")).collect()
+  }
+}
+
+/// Reads all hardware monitoring chips. Returns an empty list when the kernel exposes none, e.g. in virtual machines.
+pub fn read_hwmon_chips(roots: &PseudoFsRoots) -> Vec<HwmonChip> {
+  let hwmon = roots.sys(CLASS_HWMON);
+  list_dir(&hwmon).iter().map(|id| HwmonChip::read(&hwmon, id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_channels() {
+    let chip = HwmonChip {
+      id: "hwmon3".into(),
+      name: "coretemp".into(),
+      attributes: BTreeMap::from([
+        ("temp1_input".into(), "45000".into()),
+        ("temp1_crit".into(), "100000".into()),
+        ("temp1_label".into(), "Package id 0".into()),
+        ("temp2_input".into(), "43000".into()),
+        ("temp3_crit".into(), "100000".into()),
+        ("fan1_input".into(), "1200".into()),
+      ]),
+    };
+
+    let temperatures = chip.channels("temp");
+
+    assert_eq!(temperatures.len(), 2);
+    assert_eq!(temperatures[0].label, "Package id 0");
+    assert_eq!(temperatures[0].values.get("crit"), Some(&100000));
+    assert_eq!(temperatures[1].label, "temp2");
+    assert_eq!(chip.channels("fan")[0].values.get("input"), Some(&1200));
+    assert_eq!(parse_attribute_name("in10_input"), Some(("in", 10, "input")));
+  }
+}
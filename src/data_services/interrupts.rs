@@ -0,0 +1,261 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::PseudoFsRoots,
+};
+
+const INTERRUPTS: &str = "interrupts";
+const SOFTIRQS: &str = "softirqs";
+// Counter key of lines with a single system-wide count, e.g. `ERR` and `MIS`. CPU names always start with `CPU`.
+const SYSTEM_WIDE: &str = "all";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+pub enum InterruptKind {
+  #[strum(serialize = "IRQ")]
+  Hardware,
+  #[strum(serialize = "softirq")]
+  Soft,
+}
+
+/// Cumulative counts of one line of `/proc/interrupts` or `/proc/softirqs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterruptLine {
+  // IRQ number or name, e.g. `24`, `LOC` or `NET_RX`
+  pub id: String,
+  pub per_cpu: Vec<u64>,
+  // Interrupt controller, trigger and device names, e.g. `IO-APIC 4-edge ttyS0`
+  pub description: String,
+}
+
+/// Counts keyed by `<kind>/<id>` and then by CPU name, e.g. `CPU3`, or by `SYSTEM_WIDE`.
+pub type InterruptCounters = HashMap<String, HashMap<String, u64>>;
+
+impl InterruptLine {
+  /// Pairs the counts with the CPU names of the header of the file the line was read from. A line with fewer counts
+  /// than CPUs only has a system-wide count, which is not attributed to any CPU.
+  fn counts_by_cpu(&self, cpus: &[String]) -> HashMap<String, u64> {
+    if self.per_cpu.len() < cpus.len() {
+      return HashMap::from([(SYSTEM_WIDE.to_string(), self.per_cpu.iter().sum())]);
+    }
+    cpus.iter().cloned().zip(self.per_cpu.iter().copied()).collect()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterruptSource {
+  pub kind: InterruptKind,
+  pub id: String,
+  pub description: String,
+  // Interrupts per second on each CPU since the previous collection, all 0 on the first collection. Empty for sources
+  // with only a system-wide count.
+  pub per_cpu_rates: Vec<f64>,
+  pub total_rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct InterruptData {
+  // CPU column names from `/proc/interrupts`, which only lists online CPUs
+  pub cpus: Vec<String>,
+  // Busiest sources first
+  pub sources: Vec<InterruptSource>,
+}
+
+/// Parses `/proc/interrupts` or `/proc/softirqs` into the CPU names of the header and one entry per line. Lines with
+/// fewer counts than CPUs, e.g. `ERR` and `MIS` which only have a system-wide count, keep the counts they have.
+pub fn parse_interrupts(contents: &str) -> (Vec<String>, Vec<InterruptLine>) {
+  let mut lines = contents.lines();
+  let cpus: Vec<String> = lines.next().unwrap_or_default().split_whitespace().map(str::to_string).collect();
+  let interrupts = lines
+    .filter_map(|line| {
+      let (id, rest) = line.split_once(':')?;
+      let mut fields = rest.split_whitespace().peekable();
+      let mut per_cpu = Vec::with_capacity(cpus.len());
+      while per_cpu.len() < cpus.len() {
+        match fields.peek().and_then(|field| field.parse::<u64>().ok()) {
+          Some(count) => per_cpu.push(count),
+          None => break,
+        }
+        fields.next();
+      }
+      Some(InterruptLine { id: id.trim().to_string(), per_cpu, description: fields.collect::<Vec<_>>().join(" ") })
+    })
+    .collect();
+  (cpus, interrupts)
+}
+
+fn counter_key(kind: InterruptKind, id: &str) -> String {
+  format!("{}/{}", kind, id)
+}
+
+pub fn get_interrupt_info(
+  history: &mut CounterHistory<InterruptCounters>,
+  roots: &PseudoFsRoots,
+) -> Result<InterruptData> {
+  let (cpus, hardware) = parse_interrupts(&fs::read_to_string(roots.proc(INTERRUPTS))?);
+  // softirqs has no description, the names speak for themselves. It lists every possible CPU rather than only the
+  // online ones, so counts are matched up by the CPU names of each file's own header.
+  let (soft_cpus, soft) =
+    fs::read_to_string(roots.proc(SOFTIRQS)).map(|contents| parse_interrupts(&contents)).unwrap_or_default();
+  let lines = hardware
+    .into_iter()
+    .map(|line| (InterruptKind::Hardware, line.counts_by_cpu(&cpus), line))
+    .chain(soft.into_iter().map(|line| (InterruptKind::Soft, line.counts_by_cpu(&soft_cpus), line)))
+    .collect::<Vec<_>>();
+
+  let current: InterruptCounters =
+    lines.iter().map(|(kind, counts, line)| (counter_key(*kind, &line.id), counts.clone())).collect();
+  let previous = history.record(&current);
+
+  let mut sources: Vec<InterruptSource> = lines
+    .into_iter()
+    .map(|(kind, counts, line)| {
+      let previous_counts =
+        previous.as_ref().and_then(|(previous, elapsed)| Some((previous.get(&counter_key(kind, &line.id))?, *elapsed)));
+      if let Some(count) = counts.get(SYSTEM_WIDE) {
+        let total_rate = previous_counts
+          .and_then(|(previous_counts, elapsed)| Some(rate(*previous_counts.get(SYSTEM_WIDE)?, *count, elapsed)))
+          .unwrap_or_default();
+        return InterruptSource {
+          kind,
+          id: line.id,
+          description: line.description,
+          per_cpu_rates: Vec::new(),
+          total_rate,
+        };
+      }
+      // One rate per online CPU. CPUs without a count in the previous collection, e.g. because they just came online,
+      // start at 0, and softirq counts of offline CPUs are left out.
+      let per_cpu_rates: Vec<f64> = cpus
+        .iter()
+        .map(|cpu| {
+          match (counts.get(cpu), previous_counts) {
+            (Some(count), Some((previous_counts, elapsed))) => {
+              previous_counts.get(cpu).map_or(0.0, |previous| rate(*previous, *count, elapsed))
+            },
+            _ => 0.0,
+          }
+        })
+        .collect();
+      InterruptSource {
+        kind,
+        id: line.id,
+        description: line.description,
+        total_rate: per_cpu_rates.iter().sum(),
+        per_cpu_rates,
+      }
+    })
+    .collect();
+  sources.sort_by(|a, b| b.total_rate.total_cmp(&a.total_rate));
+
+  Ok(InterruptData { cpus, sources })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_interrupts() {
+    let (cpus, lines) = parse_interrupts(
+      "           CPU0       CPU1
+  0:         22          0   IO-APIC   2-edge      timer
+ 29:     114293     982731   PCI-MSIX-0000:00:03.0   1-edge      virtio1-input.0
+LOC:    3371843    3092641   Local timer interrupts
+ERR:          0
+",
+    );
+
+    assert_eq!(cpus, vec!["CPU0", "CPU1"]);
+    assert_eq!(lines[1], InterruptLine {
+      id: "29".to_string(),
+      per_cpu: vec![114293, 982731],
+      description: "PCI-MSIX-0000:00:03.0 1-edge virtio1-input.0".to_string(),
+    });
+    assert_eq!(lines[2].description, "Local timer interrupts");
+    assert_eq!((lines[3].id.as_str(), lines[3].per_cpu.as_slice()), ("ERR", [0].as_slice()));
+  }
+
+  #[test]
+  fn test_get_interrupt_info_from_fixture() {
+    let fixture = FixtureTree::new("interrupts");
+    let mut history = CounterHistory::default();
+    fixture
+      .write(
+        "proc/interrupts",
+        "      CPU0 CPU1\n 29: 100 200 PCI-MSIX 1-edge eth0\nLOC: 50 50 Local timer interrupts\n",
+      )
+      .write("proc/softirqs", "      CPU0 CPU1\n NET_RX: 10 10\n");
+    let first = get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+    fixture.write(
+      "proc/interrupts",
+      "      CPU0 CPU1\n 29: 100 90200 PCI-MSIX 1-edge eth0\nLOC: 60 60 Local timer interrupts\n",
+    );
+    let second = get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+
+    assert_eq!(first.sources.len(), 3);
+    assert!(first.sources.iter().all(|source| source.total_rate == 0.0));
+    // All of eth0's interrupts land on CPU1
+    assert_eq!((second.sources[0].kind, second.sources[0].id.as_str()), (InterruptKind::Hardware, "29"));
+    assert_eq!(second.sources[0].per_cpu_rates[0], 0.0);
+    assert!(second.sources[0].per_cpu_rates[1] > second.sources[1].per_cpu_rates[1]);
+    assert_eq!(second.sources[2].kind, InterruptKind::Soft);
+  }
+
+  #[test]
+  fn test_get_interrupt_info_system_wide_counts() {
+    let fixture = FixtureTree::new("interrupts-system-wide");
+    let mut history = CounterHistory::default();
+    fixture.write(
+      "proc/interrupts",
+      "      CPU0 CPU1
+LOC: 50 50 Local timer interrupts
+ERR: 3
+",
+    );
+    get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+    fixture.write(
+      "proc/interrupts",
+      "      CPU0 CPU1
+LOC: 50 50 Local timer interrupts
+ERR: 90003
+",
+    );
+    let data = get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+
+    // The error count is not filed under CPU0
+    let err = &data.sources[0];
+    assert_eq!((err.id.as_str(), err.per_cpu_rates.as_slice()), ("ERR", [].as_slice()));
+    assert!(err.total_rate > 0.0);
+  }
+
+  #[test]
+  fn test_get_interrupt_info_matches_cpus_by_name() {
+    let fixture = FixtureTree::new("interrupts-offline");
+    let mut history = CounterHistory::default();
+    // CPU1 is offline, softirqs still lists it
+    fixture
+      .write("proc/interrupts", "      CPU0 CPU2\n 29: 100 100 PCI-MSIX 1-edge eth0\n")
+      .write("proc/softirqs", "      CPU0 CPU1 CPU2\n NET_RX: 10 10 10\n");
+    get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+    // CPU1 comes online between the collections
+    fixture
+      .write("proc/interrupts", "      CPU0 CPU1 CPU2\n 29: 100 5000 100 PCI-MSIX 1-edge eth0\n")
+      .write("proc/softirqs", "      CPU0 CPU1 CPU2\n NET_RX: 10 10 90010\n");
+    let data = get_interrupt_info(&mut history, &fixture.roots()).unwrap();
+
+    assert_eq!(data.cpus, vec!["CPU0", "CPU1", "CPU2"]);
+    let net_rx = &data.sources[0];
+    assert_eq!((net_rx.kind, net_rx.per_cpu_rates[0], net_rx.per_cpu_rates[1]), (InterruptKind::Soft, 0.0, 0.0));
+    assert!(net_rx.per_cpu_rates[2] > 0.0);
+    // CPU1 has no earlier count to diff against
+    assert_eq!(data.sources[1].per_cpu_rates, vec![0.0, 0.0, 0.0]);
+  }
+}
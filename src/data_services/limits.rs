@@ -0,0 +1,171 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  disks::inode_usage,
+  host::{parse_loadavg, LOADAVG},
+  pseudo_fs::{read_value, PseudoFsRoots},
+};
+
+const FILE_NR: &str = "sys/fs/file-nr";
+const PID_MAX: &str = "sys/kernel/pid_max";
+const THREADS_MAX: &str = "sys/kernel/threads-max";
+const MOUNTS: &str = "mounts";
+// Only present while the nf_conntrack module is loaded
+const CONNTRACK_COUNT: &str = "sys/net/netfilter/nf_conntrack_count";
+const CONNTRACK_MAX: &str = "sys/net/netfilter/nf_conntrack_max";
+
+/// Share of a limit in use from which it is reported as close to exhaustion.
+pub const NEAR_LIMIT: f64 = 0.9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KernelLimit {
+  pub name: String,
+  // What runs out, e.g. the sysctl or mount point the limit belongs to
+  pub source: String,
+  pub used: u64,
+  pub max: u64,
+}
+
+impl KernelLimit {
+  /// Share of the limit in use between 0 and 1.
+  pub fn usage_ratio(&self) -> f64 {
+    if self.max == 0 {
+      return 0.0;
+    }
+    (self.used as f64 / self.max as f64).min(1.0)
+  }
+
+  pub fn is_near_limit(&self) -> bool {
+    self.usage_ratio() >= NEAR_LIMIT
+  }
+}
+
+pub type KernelLimitCollection = Vec<KernelLimit>;
+
+/// Parses `/proc/sys/fs/file-nr`, e.g. `9024 0 9223372036854775807`, into the file handles in use and the
+/// maximum. The second field counts allocated but unused handles, it has been 0 since 2.6.
+pub fn parse_file_nr(contents: &str) -> Option<(u64, u64)> {
+  let fields: Vec<u64> = contents.split_whitespace().filter_map(|field| field.parse().ok()).collect();
+  let [allocated, unused, max] = fields[..] else {
+    return None;
+  };
+  Some((allocated.saturating_sub(unused), max))
+}
+
+/// Mount points of block device backed filesystems in `/proc/mounts`, in mount order without duplicates. Network
+/// and virtual filesystems are left out, a hung NFS server would block `statvfs`.
+pub fn parse_block_mounts(contents: &str) -> Vec<String> {
+  let mut seen = HashSet::new();
+  contents
+    .lines()
+    .filter_map(|line| {
+      let mut fields = line.split_whitespace();
+      let (device, mount_point) = (fields.next()?, fields.next()?);
+      device.starts_with("/dev/").then(|| mount_point.replace("\\040", " "))
+    })
+    .filter(|mount_point| seen.insert(mount_point.clone()))
+    .collect()
+}
+
+pub fn get_limits_info(roots: &PseudoFsRoots) -> Result<KernelLimitCollection> {
+  let mut limits = Vec::new();
+
+  if let Some((used, max)) = parse_file_nr(&fs::read_to_string(roots.proc(FILE_NR))?) {
+    limits.push(KernelLimit { name: "Open files".to_string(), source: "fs.file-max".to_string(), used, max });
+  }
+
+  // Every thread takes a PID, so threads count against both limits
+  let threads = fs::read_to_string(roots.proc(LOADAVG)).ok().as_deref().and_then(parse_loadavg).map(|(_, _, t)| t);
+  if let Some(threads) = threads {
+    if let Some(max) = read_value(roots.proc(PID_MAX)) {
+      limits.push(KernelLimit { name: "PIDs".to_string(), source: "kernel.pid_max".to_string(), used: threads, max });
+    }
+    if let Some(max) = read_value(roots.proc(THREADS_MAX)) {
+      limits.push(KernelLimit {
+        name: "Threads".to_string(),
+        source: "kernel.threads-max".to_string(),
+        used: threads,
+        max,
+      });
+    }
+  }
+
+  if let (Some(used), Some(max)) = (read_value(roots.proc(CONNTRACK_COUNT)), read_value(roots.proc(CONNTRACK_MAX))) {
+    limits.push(KernelLimit {
+      name: "Conntrack entries".to_string(),
+      source: "net.netfilter.nf_conntrack_max".to_string(),
+      used,
+      max,
+    });
+  }
+
+  // statvfs checks paths on the running machine, the mount points of a capture would be looked up in the wrong
+  // filesystems
+  let mounts = if roots.is_live() {
+    fs::read_to_string(roots.proc(MOUNTS)).map(|mounts| parse_block_mounts(&mounts)).unwrap_or_default()
+  } else {
+    Vec::new()
+  };
+  for mount_point in mounts {
+    if let Some((total, free)) = inode_usage(Path::new(&mount_point)) {
+      limits.push(KernelLimit {
+        name: "Inodes".to_string(),
+        source: mount_point,
+        used: total.saturating_sub(free),
+        max: total,
+      });
+    }
+  }
+
+  Ok(limits)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_file_nr_and_mounts() {
+    assert_eq!(parse_file_nr("9024\t0\t9223372036854775807\n"), Some((9024, 9223372036854775807)));
+    assert_eq!(parse_file_nr("9024\t0\n"), None);
+    assert_eq!(
+      parse_block_mounts(
+        "proc /proc proc rw,relatime 0 0
+/dev/vda / ext4 rw,relatime 0 0
+server:/export /mnt/nfs nfs4 rw 0 0
+/dev/sdb1 /mnt/backup\\040disk xfs rw 0 0
+/dev/vda / ext4 rw,relatime 0 0
+"
+      ),
+      vec!["/", "/mnt/backup disk"]
+    );
+  }
+
+  #[test]
+  fn test_get_limits_info_from_fixture() {
+    let fixture = FixtureTree::new("limits");
+    fixture
+      .write("proc/sys/fs/file-nr", "9500\t0\t10000\n")
+      .write("proc/loadavg", "0.52 0.40 0.33 2/1204 91234\n")
+      .write("proc/sys/kernel/pid_max", "32768\n")
+      .write("proc/sys/kernel/threads-max", "1300\n")
+      .write("proc/mounts", "/dev/vda / ext4 rw,relatime 0 0\n");
+    let limits = get_limits_info(&fixture.roots()).unwrap();
+
+    // The captured root mount is not looked up in the local filesystem
+    assert_eq!(limits.iter().map(|limit| (limit.name.as_str(), limit.used, limit.max)).collect::<Vec<_>>(), vec![
+      ("Open files", 9500, 10000),
+      ("PIDs", 1204, 32768),
+      ("Threads", 1204, 1300)
+    ]);
+    assert!(limits[0].is_near_limit());
+    assert!(!limits[1].is_near_limit());
+    assert!(limits[2].is_near_limit());
+  }
+}
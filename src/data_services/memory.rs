@@ -1,7 +1,17 @@
+use std::fs;
+
 use color_eyre::eyre::Result;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
+use super::pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots};
+
+const SWAPS: &str = "swaps";
+const MEMINFO: &str = "meminfo";
+const BLOCK: &str = "block";
+const ZRAM_PREFIX: &str = "zram";
+const ZSWAP_PARAMETERS: &str = "module/zswap/parameters";
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemoryData {
   // the amount of free RAM in bytes
@@ -10,26 +20,247 @@ pub struct MemoryData {
   // the amount of free SWAP in bytes
   pub free_swap: u64,
   pub total_swap: u64,
+  // Active swap areas in the order the kernel lists them
+  pub swap_devices: Vec<SwapDevice>,
+  // Initialized zram devices, whether or not they are used for swap
+  pub zram_devices: Vec<ZramDevice>,
+  // None when the kernel was built without zswap
+  pub zswap: Option<ZswapData>,
 }
 
 impl MemoryData {
   pub fn usage_percentages(&self) -> (f64, f64) {
-    let used_ram = self.total_ram - self.free_ram;
-    let ram_usage = (used_ram as f64 / self.total_ram as f64) * 100.0;
+    // A capture without meminfo has no totals, which is nothing in use rather than an undefined share. A truncated
+    // meminfo can still have the free amount, so it may exceed the total.
+    let used_ram = self.total_ram.saturating_sub(self.free_ram);
+    let ram_usage = if self.total_ram == 0 { 0.0 } else { (used_ram as f64 / self.total_ram as f64) * 100.0 };
 
-    let used_swap = self.total_swap - self.free_swap;
-    let swap_usage = (used_swap as f64 / self.total_swap as f64) * 100.0;
+    // Without any swap there is nothing in use rather than an undefined share
+    let used_swap = self.total_swap.saturating_sub(self.free_swap);
+    let swap_usage = if self.total_swap == 0 { 0.0 } else { (used_swap as f64 / self.total_swap as f64) * 100.0 };
 
     (ram_usage, swap_usage)
   }
 }
 
-pub fn get_memory_info(system: &System) -> Result<MemoryData> {
+/// One line of `/proc/swaps`, sizes converted to bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapDevice {
+  pub path: String,
+  // `partition` or `file`
+  pub kind: String,
+  pub size: u64,
+  pub used: u64,
+  // Higher priority areas are used first, areas of equal priority are used round robin
+  pub priority: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZramDevice {
+  pub name: String,
+  pub algorithm: Option<String>,
+  // Uncompressed capacity of the device
+  pub disk_size: u64,
+  // Data stored before and after compression
+  pub original_size: u64,
+  pub compressed_size: u64,
+  // Memory the device actually takes, including allocator overhead
+  pub memory_used: u64,
+}
+
+impl ZramDevice {
+  /// How many times smaller the stored data got, `None` while the device is empty.
+  pub fn compression_ratio(&self) -> Option<f64> {
+    compression_ratio(self.original_size, self.compressed_size)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZswapData {
+  pub enabled: bool,
+  pub compressor: Option<String>,
+  // Share of RAM the compressed pool may grow to
+  pub max_pool_percent: Option<u64>,
+  // Size of the compressed pool and of the pages it holds, from `/proc/meminfo` on kernels 5.19 and newer
+  pub pool_size: Option<u64>,
+  pub stored_size: Option<u64>,
+}
+
+impl ZswapData {
+  pub fn compression_ratio(&self) -> Option<f64> {
+    compression_ratio(self.stored_size?, self.pool_size?)
+  }
+}
+
+fn compression_ratio(original: u64, compressed: u64) -> Option<f64> {
+  (original > 0 && compressed > 0).then(|| original as f64 / compressed as f64)
+}
+
+/// Parses `/proc/swaps`, skipping its header line. Sizes are listed in KiB and spaces in paths are escaped as `\040`.
+pub fn parse_swaps(contents: &str) -> Vec<SwapDevice> {
+  contents
+    .lines()
+    .skip(1)
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      let [path, kind, size, used, priority] = fields[..] else {
+        return None;
+      };
+      Some(SwapDevice {
+        path: path.replace("\\040", " "),
+        kind: kind.to_string(),
+        size: size.parse::<u64>().ok()? * 1024,
+        used: used.parse::<u64>().ok()? * 1024,
+        priority: priority.parse().ok()?,
+      })
+    })
+    .collect()
+}
+
+/// Parses a zram `mm_stat` file into the original data size, the compressed size and the memory used, in bytes.
+pub fn parse_mm_stat(contents: &str) -> Option<(u64, u64, u64)> {
+  let mut fields = contents.split_whitespace().map(|field| field.parse::<u64>().ok());
+  Some((fields.next()??, fields.next()??, fields.next()??))
+}
+
+/// Reads a `/proc/meminfo` value in bytes, e.g. `Zswap:     1024 kB`.
+pub fn parse_meminfo_value(meminfo: &str, key: &str) -> Option<u64> {
+  meminfo.lines().find_map(|line| {
+    let value = line.strip_prefix(key)?.strip_prefix(':')?.trim();
+    Some(value.strip_suffix("kB").unwrap_or(value).trim().parse::<u64>().ok()? * 1024)
+  })
+}
+
+/// The selected entry of a sysfs choice list such as `[lzo-rle] lzo lz4`, or the whole value if there is no choice.
+fn selected_choice(value: &str) -> String {
+  value.split_once('[').and_then(|(_, rest)| rest.split_once(']')).map_or(value, |(selected, _)| selected).to_string()
+}
+
+fn read_zram_devices(roots: &PseudoFsRoots) -> Vec<ZramDevice> {
+  list_dir(roots.sys(BLOCK))
+    .into_iter()
+    .filter(|name| name.starts_with(ZRAM_PREFIX))
+    .filter_map(|name| {
+      let device = roots.sys(BLOCK).join(&name);
+      // Devices that were never given a size hold no data
+      let disk_size = read_value::<u64>(device.join("disksize")).filter(|size| *size > 0)?;
+      let (original_size, compressed_size, memory_used) =
+        parse_mm_stat(&fs::read_to_string(device.join("mm_stat")).ok()?)?;
+      Some(ZramDevice {
+        algorithm: read_trimmed(device.join("comp_algorithm")).map(|algorithm| selected_choice(&algorithm)),
+        name,
+        disk_size,
+        original_size,
+        compressed_size,
+        memory_used,
+      })
+    })
+    .collect()
+}
+
+fn read_zswap(meminfo: Option<&str>, roots: &PseudoFsRoots) -> Option<ZswapData> {
+  let parameters = roots.sys(ZSWAP_PARAMETERS);
+  let enabled = read_trimmed(parameters.join("enabled"))?;
+  Some(ZswapData {
+    enabled: matches!(enabled.as_str(), "Y" | "1"),
+    compressor: read_trimmed(parameters.join("compressor")),
+    max_pool_percent: read_value(parameters.join("max_pool_percent")),
+    pool_size: meminfo.and_then(|meminfo| parse_meminfo_value(meminfo, "Zswap")),
+    stored_size: meminfo.and_then(|meminfo| parse_meminfo_value(meminfo, "Zswapped")),
+  })
+}
+
+/// Reads RAM and swap totals from sysinfo, or from `/proc/meminfo` below the roots for a capture as sysinfo always
+/// describes the running machine.
+pub fn get_memory_info(system: &System, roots: &PseudoFsRoots) -> Result<MemoryData> {
+  let meminfo = fs::read_to_string(roots.proc(MEMINFO)).ok();
+  let (free_ram, total_ram, free_swap, total_swap) = match meminfo.as_deref() {
+    Some(meminfo) if !roots.is_live() => {
+      let value = |key: &str| parse_meminfo_value(meminfo, key).unwrap_or_default();
+      (value("MemFree"), value("MemTotal"), value("SwapFree"), value("SwapTotal"))
+    },
+    _ => (system.free_memory(), system.total_memory(), system.free_swap(), system.total_swap()),
+  };
   Ok(MemoryData {
     // available_ram: system.available_memory(),
-    free_ram: system.free_memory(),
-    total_ram: system.total_memory(),
-    free_swap: system.free_swap(),
-    total_swap: system.total_swap(),
+    free_ram,
+    total_ram,
+    free_swap,
+    total_swap,
+    swap_devices: fs::read_to_string(roots.proc(SWAPS)).map(|swaps| parse_swaps(&swaps)).unwrap_or_default(),
+    zram_devices: read_zram_devices(roots),
+    zswap: read_zswap(meminfo.as_deref(), roots),
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_swaps() {
+    let swaps = parse_swaps(
+      "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/zram0                              partition\t4194300\t\t1048576\t\t100
+/swap\\040file                           file\t\t2097148\t\t0\t\t-2
+",
+    );
+
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(swaps[0], SwapDevice {
+      path: "/dev/zram0".to_string(),
+      kind: "partition".to_string(),
+      size: 4194300 * 1024,
+      used: 1073741824,
+      priority: 100,
+    });
+    assert_eq!((swaps[1].path.as_str(), swaps[1].priority), ("/swap file", -2));
+  }
+
+  #[test]
+  fn test_get_memory_info_from_fixture() {
+    let fixture = FixtureTree::new("memory");
+    fixture
+      .write("proc/swaps", "Filename\tType\tSize\tUsed\tPriority\n")
+      .write("proc/meminfo", "MemTotal:       16318452 kB\nZswap:             10240 kB\nZswapped:          40960 kB\n")
+      .write("sys/block/zram0/disksize", "4294967296\n")
+      .write("sys/block/zram0/comp_algorithm", "lzo lzo-rle [zstd] lz4\n")
+      .write(
+        "sys/block/zram0/mm_stat",
+        "1073741824 268435456 276824064        0 276824064     1632        0     0     0\n",
+      )
+      .write("sys/block/zram1/disksize", "0\n")
+      .write(
+        "sys/block/zram1/mm_stat",
+        "       0        0        0        0        0        0        0        0        0\n",
+      )
+      .write("sys/module/zswap/parameters/enabled", "Y\n")
+      .write("sys/module/zswap/parameters/compressor", "lz4\n")
+      .write("sys/module/zswap/parameters/max_pool_percent", "20\n");
+    let memory = get_memory_info(&System::new(), &fixture.roots()).unwrap();
+
+    // Totals of a capture come from its meminfo
+    assert_eq!((memory.total_ram, memory.total_swap), (16318452 * 1024, 0));
+    assert_eq!(memory.swap_devices, vec![]);
+    assert_eq!(memory.zram_devices.len(), 1);
+    assert_eq!(memory.zram_devices[0].algorithm.as_deref(), Some("zstd"));
+    assert_eq!(memory.zram_devices[0].compression_ratio(), Some(4.0));
+    let zswap = memory.zswap.unwrap();
+    assert!(zswap.enabled);
+    assert_eq!((zswap.pool_size, zswap.max_pool_percent), (Some(10485760), Some(20)));
+    assert_eq!(zswap.compression_ratio(), Some(4.0));
+  }
+
+  #[test]
+  fn test_usage_percentages_of_truncated_meminfo() {
+    let fixture = FixtureTree::new("memory-truncated");
+    fixture.write("proc/swaps", "Filename\tType\tSize\tUsed\tPriority\n").write("proc/meminfo", "MemFree:  1024 kB\n");
+    let memory = get_memory_info(&System::new(), &fixture.roots()).unwrap();
+
+    assert_eq!((memory.total_ram, memory.free_ram), (0, 1024 * 1024));
+    assert_eq!(memory.usage_percentages(), (0.0, 0.0));
+  }
+}
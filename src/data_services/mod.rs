@@ -1,6 +1,29 @@
+pub mod battery;
+pub mod block_devices;
+pub mod cgroups;
+pub mod counters;
 pub mod cpu;
+pub mod cpu_freq;
+pub mod cpu_idle;
 pub mod data_collector;
+pub mod disk_io;
 pub mod disks;
+pub mod host;
+pub mod hwmon;
+pub mod interrupts;
+pub mod limits;
 pub mod memory;
+pub mod net_stats;
 pub mod network;
+pub mod pressure;
 pub mod processes;
+pub mod pseudo_fs;
+pub mod raid;
+pub mod rapl;
+pub mod runtime_pm;
+pub mod sensors;
+pub mod sockets;
+pub mod temperatures;
+pub mod tunables;
+pub mod vmstat;
+pub mod wakeups;
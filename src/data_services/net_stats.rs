@@ -0,0 +1,145 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::PseudoFsRoots,
+};
+
+const NET_SNMP: &str = "net/snmp";
+const NET_NETSTAT: &str = "net/netstat";
+
+/// Counters tracked from `/proc/net/snmp` and `/proc/net/netstat` as (`Group.Name`, label) pairs.
+const TRACKED_COUNTERS: [(&str, &str); 12] = [
+  ("Tcp.RetransSegs", "TCP retransmits"),
+  ("Tcp.OutRsts", "TCP resets sent"),
+  ("Tcp.EstabResets", "TCP estab resets"),
+  ("Tcp.AttemptFails", "TCP failed conns"),
+  ("Tcp.InErrs", "TCP bad segments"),
+  ("TcpExt.ListenOverflows", "Listen overflows"),
+  ("TcpExt.ListenDrops", "Listen drops"),
+  ("TcpExt.TCPTimeouts", "TCP timeouts"),
+  ("Udp.InErrors", "UDP recv errors"),
+  ("Udp.RcvbufErrors", "UDP rcvbuf errors"),
+  ("Udp.SndbufErrors", "UDP sndbuf errors"),
+  ("Udp.NoPorts", "UDP no port"),
+];
+
+/// Raw cumulative counters keyed by `Group.Name` (e.g. `Tcp.RetransSegs`).
+pub type NetStatCounters = HashMap<String, u64>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetStatCounter {
+  pub key: String,
+  pub label: String,
+  pub total: u64,
+  // Events per second since the previous collection, 0 on the first collection
+  pub rate: f64,
+}
+
+pub type NetStatsDataCollection = Vec<NetStatCounter>;
+
+/// Parses the header/value line pairs used by `/proc/net/snmp` and `/proc/net/netstat`:
+///
+/// ```text
+/// Tcp: RtoAlgorithm RtoMin ...
+/// Tcp: 1 200 ...
+/// ```
+///
+/// Negative values (e.g. `Tcp.MaxConn` of `-1`) are not counters and are skipped.
+pub fn parse_keyed_counters(contents: &str) -> NetStatCounters {
+  let mut counters = HashMap::new();
+  let mut lines = contents.lines();
+  while let (Some(header), Some(values)) = (lines.next(), lines.next()) {
+    let (Some((group, names)), Some((_, values))) = (header.split_once(':'), values.split_once(':')) else {
+      continue;
+    };
+    for (name, value) in names.split_whitespace().zip(values.split_whitespace()) {
+      if let Ok(value) = value.parse::<u64>() {
+        counters.insert(format!("{}.{}", group, name), value);
+      }
+    }
+  }
+  counters
+}
+
+fn read_counters(roots: &PseudoFsRoots) -> Result<NetStatCounters> {
+  let mut counters = parse_keyed_counters(&fs::read_to_string(roots.proc(NET_SNMP))?);
+  // netstat holds the TcpExt/IpExt extensions, which older kernels or restricted containers may not expose
+  if let Ok(contents) = fs::read_to_string(roots.proc(NET_NETSTAT)) {
+    counters.extend(parse_keyed_counters(&contents));
+  }
+  Ok(counters)
+}
+
+pub fn get_net_stats_info(
+  history: &mut CounterHistory<NetStatCounters>,
+  roots: &PseudoFsRoots,
+) -> Result<NetStatsDataCollection> {
+  let current = read_counters(roots)?;
+  let previous = history.record(&current);
+
+  Ok(
+    TRACKED_COUNTERS
+      .iter()
+      .filter_map(|(key, label)| {
+        let total = *current.get(*key)?;
+        let rate = match &previous {
+          Some((previous, elapsed)) => previous.get(*key).map_or(0.0, |p| rate(*p, total, *elapsed)),
+          None => 0.0,
+        };
+        Some(NetStatCounter { key: key.to_string(), label: label.to_string(), total, rate })
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_keyed_counters() {
+    let contents = "Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens RetransSegs
+Tcp: 1 200 120000 -1 4 17
+Udp: InDatagrams NoPorts InErrors
+Udp: 2 0 5
+";
+    let counters = parse_keyed_counters(contents);
+
+    assert_eq!(counters.get("Tcp.RetransSegs"), Some(&17));
+    assert_eq!(counters.get("Udp.InErrors"), Some(&5));
+    assert_eq!(counters.get("Tcp.MaxConn"), None);
+  }
+
+  #[test]
+  fn test_get_net_stats_info_from_fixture() {
+    let fixture = FixtureTree::new("net-stats");
+    // Containers without TcpExt have no net/netstat
+    fixture.write(
+      "proc/net/snmp",
+      "Tcp: RtoAlgorithm RetransSegs InErrs OutRsts
+Tcp: 1 42 0 7
+Udp: InDatagrams NoPorts InErrors
+Udp: 100 3 0
+",
+    );
+    let counters = get_net_stats_info(&mut CounterHistory::default(), &fixture.roots()).unwrap();
+
+    assert_eq!(counters.iter().map(|counter| (counter.key.as_str(), counter.total)).collect::<Vec<_>>(), vec![
+      ("Tcp.RetransSegs", 42),
+      ("Tcp.OutRsts", 7),
+      ("Tcp.InErrs", 0),
+      ("Udp.InErrors", 0),
+      ("Udp.NoPorts", 3)
+    ]);
+    assert!(
+      get_net_stats_info(&mut CounterHistory::default(), &PseudoFsRoots::new("/nonexistent", "/nonexistent")).is_err()
+    );
+  }
+}
@@ -1,9 +1,22 @@
-use std::{any::Any, collections::HashMap, ffi::OsStr, path::Path};
+use std::{
+  any::Any,
+  collections::HashMap,
+  ffi::OsStr,
+  fmt,
+  net::{IpAddr, Ipv4Addr, Ipv6Addr},
+  path::Path,
+};
 
 use color_eyre::eyre::Result;
 use serde::{Deserialize, Serialize};
+use strum::Display;
 use sysinfo::Networks;
 
+use super::pseudo_fs::{read_trimmed, read_value, PseudoFsRoots, CLASS_NET};
+
+// ARPHRD_LOOPBACK from linux/if_arp.h, reported in /sys/class/net/<iface>/type
+const ARPHRD_LOOPBACK: u32 = 772;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NetworkData {
   pub interface_name: String,
@@ -17,10 +30,128 @@ pub struct NetworkData {
   pub transmitted: u64,
   pub total_transmitted: u64,
   pub total_packets_transmitted: u64,
+
+  pub addresses: Vec<InterfaceAddress>,
+  pub link: LinkInfo,
 }
 
 pub type NetworkDataCollection = Vec<NetworkData>;
 
+/// An IPv4 or IPv6 address assigned to an interface along with its prefix length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterfaceAddress {
+  pub address: IpAddr,
+  pub prefix_len: u8,
+}
+
+impl fmt::Display for InterfaceAddress {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}/{}", self.address, self.prefix_len)
+  }
+}
+
+/// Operational state of an interface as reported by `/sys/class/net/<iface>/operstate` (RFC 2863).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, Default)]
+pub enum OperState {
+  Up,
+  Down,
+  Dormant,
+  LowerLayerDown,
+  NotPresent,
+  Testing,
+  #[default]
+  Unknown,
+}
+
+impl From<&str> for OperState {
+  fn from(value: &str) -> Self {
+    match value {
+      "up" => OperState::Up,
+      "down" => OperState::Down,
+      "dormant" => OperState::Dormant,
+      "lowerlayerdown" => OperState::LowerLayerDown,
+      "notpresent" => OperState::NotPresent,
+      "testing" => OperState::Testing,
+      _ => OperState::Unknown,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, Default)]
+pub enum InterfaceKind {
+  Physical,
+  Wireless,
+  Loopback,
+  Bridge,
+  Bond,
+  Veth,
+  Tun,
+  #[default]
+  Virtual,
+}
+
+/// Link level details of an interface read from sysfs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LinkInfo {
+  pub oper_state: OperState,
+  pub kind: InterfaceKind,
+  pub mtu: Option<u32>,
+  // Negotiated link speed in Mb/s, only known for physical links that are up.
+  pub speed_mbps: Option<u32>,
+  pub duplex: Option<String>,
+}
+
+impl LinkInfo {
+  /// Reads the link details of `interface_name` from `/sys/class/net`. Fields that are unavailable (e.g. on
+  /// non-Linux systems or for virtual interfaces without a speed) are left as `None`/`Unknown`.
+  pub fn read(interface_name: &str, roots: &PseudoFsRoots) -> Self {
+    let interface_dir = roots.sys(CLASS_NET).join(interface_name);
+
+    let oper_state =
+      read_trimmed(interface_dir.join("operstate")).map(|s| OperState::from(s.as_str())).unwrap_or_default();
+    let speed_mbps = read_value::<i64>(interface_dir.join("speed")).filter(|s| *s > 0).map(|s| s as u32);
+    let duplex = read_trimmed(interface_dir.join("duplex")).filter(|d| d != "unknown");
+
+    LinkInfo {
+      oper_state,
+      kind: InterfaceKind::detect(interface_name, &interface_dir),
+      mtu: read_value(interface_dir.join("mtu")),
+      speed_mbps,
+      duplex,
+    }
+  }
+}
+
+impl InterfaceKind {
+  fn detect(interface_name: &str, interface_dir: &Path) -> Self {
+    if read_value::<u32>(interface_dir.join("type")) == Some(ARPHRD_LOOPBACK) {
+      InterfaceKind::Loopback
+    } else if interface_dir.join("bridge").exists() {
+      InterfaceKind::Bridge
+    } else if interface_dir.join("bonding").exists() {
+      InterfaceKind::Bond
+    } else if interface_dir.join("tun_flags").exists() {
+      InterfaceKind::Tun
+    } else if interface_dir.join("wireless").exists() || interface_dir.join("phy80211").exists() {
+      InterfaceKind::Wireless
+    } else if interface_dir.join("device").exists() {
+      InterfaceKind::Physical
+    } else if interface_name.starts_with("veth") || Self::has_peer(interface_dir) {
+      InterfaceKind::Veth
+    } else {
+      InterfaceKind::Virtual
+    }
+  }
+
+  // A veth end that was renamed (e.g. `eth0` inside a container) still points at its peer through `iflink`.
+  fn has_peer(interface_dir: &Path) -> bool {
+    match (read_value::<u32>(interface_dir.join("ifindex")), read_value::<u32>(interface_dir.join("iflink"))) {
+      (Some(index), Some(link)) => index != link,
+      _ => false,
+    }
+  }
+}
+
 impl From<(&String, &sysinfo::NetworkData)> for NetworkData {
   fn from((interface_name, net_data): (&String, &sysinfo::NetworkData)) -> Self {
     NetworkData {
@@ -35,20 +166,167 @@ impl From<(&String, &sysinfo::NetworkData)> for NetworkData {
       transmitted: net_data.transmitted(),
       total_transmitted: net_data.total_transmitted(),
       total_packets_transmitted: net_data.total_packets_transmitted(),
+
+      addresses: Vec::new(),
+      link: LinkInfo::default(),
     }
   }
 }
 
 struct NetworkDataWrapper<'a> {
   networks: &'a Networks,
+  addresses: HashMap<String, Vec<InterfaceAddress>>,
+  roots: &'a PseudoFsRoots,
 }
 
 impl<'a> From<NetworkDataWrapper<'a>> for NetworkDataCollection {
-  fn from(wrapper: NetworkDataWrapper<'a>) -> Self {
-    wrapper.networks.iter().map(|network: (&String, &sysinfo::NetworkData)| network.into()).collect()
+  fn from(mut wrapper: NetworkDataWrapper<'a>) -> Self {
+    wrapper
+      .networks
+      .iter()
+      .map(|network: (&String, &sysinfo::NetworkData)| {
+        let mut data: NetworkData = network.into();
+        data.addresses = wrapper.addresses.remove(network.0).unwrap_or_default();
+        data.link = LinkInfo::read(network.0, wrapper.roots);
+        data
+      })
+      .collect()
   }
 }
 
-pub fn get_network_info(networks: &Networks) -> Result<NetworkDataCollection> {
-  Ok(NetworkDataWrapper { networks }.into())
+pub fn get_network_info(networks: &Networks, roots: &PseudoFsRoots) -> Result<NetworkDataCollection> {
+  Ok(NetworkDataWrapper { networks, addresses: get_interface_addresses(), roots }.into())
+}
+
+/// Returns the IPv4 and IPv6 addresses of every interface, keyed by interface name, using `getifaddrs(3)`.
+#[cfg(unix)]
+fn get_interface_addresses() -> HashMap<String, Vec<InterfaceAddress>> {
+  use std::ffi::CStr;
+
+  let mut addresses: HashMap<String, Vec<InterfaceAddress>> = HashMap::new();
+  let mut ifaddrs: *mut libc::ifaddrs = std::ptr::null_mut();
+
+  // SAFETY: `getifaddrs` initialises `ifaddrs` with a linked list that stays valid until `freeifaddrs`.
+  if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
+    log::warn!("getifaddrs failed: {}", std::io::Error::last_os_error());
+    return addresses;
+  }
+
+  let mut current = ifaddrs;
+  while !current.is_null() {
+    // SAFETY: `current` is a non-null node of the list returned by `getifaddrs`.
+    let entry = unsafe { &*current };
+    current = entry.ifa_next;
+
+    // SAFETY: the address pointers are either null or point to a sockaddr of the family they report.
+    let (Some(address), netmask) =
+      (unsafe { sockaddr_to_ip(entry.ifa_addr) }, unsafe { sockaddr_to_ip(entry.ifa_netmask) })
+    else {
+      continue;
+    };
+    let prefix_len = match netmask {
+      Some(IpAddr::V4(mask)) => u32::from(mask).count_ones() as u8,
+      Some(IpAddr::V6(mask)) => u128::from(mask).count_ones() as u8,
+      None => 0,
+    };
+
+    // SAFETY: `ifa_name` is a valid NUL terminated string for the lifetime of the list.
+    let name = unsafe { CStr::from_ptr(entry.ifa_name) }.to_string_lossy().into_owned();
+    addresses.entry(name).or_default().push(InterfaceAddress { address, prefix_len });
+  }
+
+  // SAFETY: `ifaddrs` was returned by a successful `getifaddrs` call and is freed exactly once.
+  unsafe { libc::freeifaddrs(ifaddrs) };
+
+  addresses
+}
+
+#[cfg(not(unix))]
+fn get_interface_addresses() -> HashMap<String, Vec<InterfaceAddress>> {
+  HashMap::new()
+}
+
+/// Converts an `AF_INET`/`AF_INET6` socket address into an [`IpAddr`], ignoring other address families.
+///
+/// # Safety
+///
+/// `addr` must be null or point to a valid socket address whose size matches its `sa_family`.
+#[cfg(unix)]
+unsafe fn sockaddr_to_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+  if addr.is_null() {
+    return None;
+  }
+  match (*addr).sa_family as i32 {
+    libc::AF_INET => {
+      let addr = &*(addr as *const libc::sockaddr_in);
+      Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr))))
+    },
+    libc::AF_INET6 => {
+      let addr = &*(addr as *const libc::sockaddr_in6);
+      Some(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)))
+    },
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_link_info_read_from_fixture() {
+    let fixture = FixtureTree::new("network-link");
+    fixture
+      .write("sys/class/net/eth0/operstate", "up\n")
+      .write("sys/class/net/eth0/mtu", "1500\n")
+      .write("sys/class/net/eth0/speed", "1000\n")
+      .write("sys/class/net/eth0/duplex", "full\n")
+      .create_dir("sys/class/net/eth0/device")
+      .write("sys/class/net/wg0/operstate", "unknown\n")
+      .write("sys/class/net/wg0/speed", "-1\n")
+      .write("sys/class/net/wg0/duplex", "unknown\n");
+    let roots = fixture.roots();
+
+    assert_eq!(LinkInfo::read("eth0", &roots), LinkInfo {
+      oper_state: OperState::Up,
+      kind: InterfaceKind::Physical,
+      mtu: Some(1500),
+      speed_mbps: Some(1000),
+      duplex: Some("full".to_string()),
+    });
+    assert_eq!(LinkInfo::read("wg0", &roots), LinkInfo::default());
+    assert_eq!(LinkInfo::read("missing0", &roots), LinkInfo::default());
+  }
+
+  #[test]
+  fn test_interface_kind_detect_from_fixture() {
+    let fixture = FixtureTree::new("network-kind");
+    fixture
+      .write("sys/class/net/lo/type", "772\n")
+      .create_dir("sys/class/net/br0/bridge")
+      .create_dir("sys/class/net/bond0/bonding")
+      .write("sys/class/net/tun0/tun_flags", "0x1002\n")
+      .create_dir("sys/class/net/wlan0/wireless")
+      .create_dir("sys/class/net/wlan0/device")
+      .create_dir("sys/class/net/veth1a2b")
+      .write("sys/class/net/eth0/ifindex", "2\n")
+      .write("sys/class/net/eth0/iflink", "7\n")
+      .write("sys/class/net/dummy0/ifindex", "3\n")
+      .write("sys/class/net/dummy0/iflink", "3\n");
+    let class_net = fixture.roots().sys(CLASS_NET);
+    let detect = |name: &str| InterfaceKind::detect(name, &class_net.join(name));
+
+    assert_eq!(detect("lo"), InterfaceKind::Loopback);
+    assert_eq!(detect("br0"), InterfaceKind::Bridge);
+    assert_eq!(detect("bond0"), InterfaceKind::Bond);
+    assert_eq!(detect("tun0"), InterfaceKind::Tun);
+    assert_eq!(detect("wlan0"), InterfaceKind::Wireless);
+    assert_eq!(detect("veth1a2b"), InterfaceKind::Veth);
+    // A renamed veth end inside a container
+    assert_eq!(detect("eth0"), InterfaceKind::Veth);
+    assert_eq!(detect("dummy0"), InterfaceKind::Virtual);
+  }
 }
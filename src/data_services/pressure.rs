@@ -0,0 +1,242 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::{cgroup2_root, list_dir, PseudoFsRoots},
+};
+
+const PRESSURE: &str = "pressure";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter)]
+pub enum PressureResource {
+  #[strum(serialize = "CPU")]
+  Cpu,
+  #[strum(serialize = "Memory")]
+  Memory,
+  #[strum(serialize = "I/O")]
+  Io,
+}
+
+impl PressureResource {
+  /// Name of the resource's file in `/proc/pressure`, cgroups prefix it with their own name, e.g. `io.pressure`.
+  pub(crate) fn file_name(&self) -> &'static str {
+    match self {
+      PressureResource::Cpu => "cpu",
+      PressureResource::Memory => "memory",
+      PressureResource::Io => "io",
+    }
+  }
+}
+
+/// One line of a pressure file: the share of time in percent tasks were stalled, averaged over 10, 60 and 300
+/// seconds, and the total stall time in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PressureLine {
+  pub avg10: f64,
+  pub avg60: f64,
+  pub avg300: f64,
+  pub total_us: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PressureStat {
+  pub avg10: f64,
+  pub avg60: f64,
+  pub avg300: f64,
+  // Share of the last collection interval tasks were stalled, from the change in total stall time
+  pub stall_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourcePressure {
+  pub resource: PressureResource,
+  // Some tasks were stalled
+  pub some: PressureStat,
+  // All non-idle tasks were stalled at once. The system-wide CPU line reports zeroes on kernels that have it.
+  pub full: Option<PressureStat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupPressure {
+  // Path relative to the cgroup v2 root, e.g. `system.slice`
+  pub path: String,
+  pub resources: Vec<ResourcePressure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PressureData {
+  // Empty when the kernel was built without PSI or booted with `psi=0`
+  pub system: Vec<ResourcePressure>,
+  // Top level cgroups, only available with cgroup v2
+  pub cgroups: Vec<CgroupPressure>,
+}
+
+/// Total stall times in microseconds keyed by `<scope>/<resource>/<some|full>`, the scope being empty for the
+/// system-wide files.
+pub type PressureCounters = HashMap<String, u64>;
+
+/// Parses a pressure file into its `some` and `full` lines, e.g.
+/// `some avg10=0.28 avg60=0.55 avg300=0.27 total=9729874`.
+pub fn parse_pressure(contents: &str) -> (Option<PressureLine>, Option<PressureLine>) {
+  let mut some = None;
+  let mut full = None;
+  for line in contents.lines() {
+    let mut fields = line.split_whitespace();
+    let target = match fields.next() {
+      Some("some") => &mut some,
+      Some("full") => &mut full,
+      _ => continue,
+    };
+    let mut parsed = PressureLine::default();
+    for (key, value) in fields.filter_map(|field| field.split_once('=')) {
+      match key {
+        "avg10" => parsed.avg10 = value.parse().unwrap_or_default(),
+        "avg60" => parsed.avg60 = value.parse().unwrap_or_default(),
+        "avg300" => parsed.avg300 = value.parse().unwrap_or_default(),
+        "total" => parsed.total_us = value.parse().unwrap_or_default(),
+        _ => {},
+      }
+    }
+    *target = Some(parsed);
+  }
+  (some, full)
+}
+
+/// Reads the pressure files of one scope, `file_path` mapping a resource to its file.
+fn read_scope(
+  scope: &str,
+  file_path: impl Fn(PressureResource) -> PathBuf,
+  current: &mut PressureCounters,
+) -> Vec<(PressureResource, PressureLine, Option<PressureLine>)> {
+  PressureResource::iter()
+    .filter_map(|resource| {
+      let (some, full) = parse_pressure(&fs::read_to_string(file_path(resource)).ok()?);
+      let some = some?;
+      current.insert(format!("{}/{}/some", scope, resource.file_name()), some.total_us);
+      if let Some(full) = full {
+        current.insert(format!("{}/{}/full", scope, resource.file_name()), full.total_us);
+      }
+      Some((resource, some, full))
+    })
+    .collect()
+}
+
+fn to_resource_pressure(
+  scope: &str,
+  (resource, some, full): (PressureResource, PressureLine, Option<PressureLine>),
+  current: &PressureCounters,
+  previous: Option<&(PressureCounters, f64)>,
+) -> ResourcePressure {
+  let stat = |line: PressureLine, kind: &str| {
+    let key = format!("{}/{}/{}", scope, resource.file_name(), kind);
+    let stall_percent = previous.and_then(|(previous, elapsed_secs)| {
+      // Microseconds stalled per second, divided by 10^4 to get a percentage
+      Some(rate(*previous.get(&key)?, *current.get(&key)?, *elapsed_secs) / 10_000.0)
+    });
+    PressureStat { avg10: line.avg10, avg60: line.avg60, avg300: line.avg300, stall_percent }
+  };
+  ResourcePressure { resource, some: stat(some, "some"), full: full.map(|full| stat(full, "full")) }
+}
+
+pub fn get_pressure_info(
+  history: &mut CounterHistory<PressureCounters>,
+  roots: &PseudoFsRoots,
+) -> Result<PressureData> {
+  let mut current = PressureCounters::new();
+  let system = read_scope("", |resource| roots.proc(PRESSURE).join(resource.file_name()), &mut current);
+
+  let cgroup_root = cgroup2_root(roots);
+  let cgroups: Vec<(String, Vec<_>)> = cgroup_root
+    .iter()
+    .flat_map(|root| {
+      list_dir(root)
+        .into_iter()
+        .filter(|name| root.join(name).is_dir())
+        .map(|name| {
+          let path = root.join(&name);
+          let resources =
+            read_scope(&name, |resource| path.join(format!("{}.pressure", resource.file_name())), &mut current);
+          (name, resources)
+        })
+        .filter(|(_, resources)| !resources.is_empty())
+        .collect::<Vec<_>>()
+    })
+    .collect();
+
+  let previous = history.record(&current);
+  let previous = previous.as_ref();
+  Ok(PressureData {
+    system: system.into_iter().map(|lines| to_resource_pressure("", lines, &current, previous)).collect(),
+    cgroups: cgroups
+      .into_iter()
+      .map(|(path, resources)| {
+        CgroupPressure {
+          resources: resources
+            .into_iter()
+            .map(|lines| to_resource_pressure(&path, lines, &current, previous))
+            .collect(),
+          path,
+        }
+      })
+      .collect(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_pressure() {
+    let (some, full) = parse_pressure(
+      "some avg10=0.28 avg60=0.55 avg300=0.27 total=9729874
+full avg10=0.20 avg60=0.31 avg300=0.14 total=7458359
+",
+    );
+    assert_eq!(some, Some(PressureLine { avg10: 0.28, avg60: 0.55, avg300: 0.27, total_us: 9729874 }));
+    assert_eq!(full.map(|full| full.total_us), Some(7458359));
+
+    // Kernels before 5.13 have no full line for CPU
+    let (some, full) = parse_pressure("some avg10=7.32 avg60=7.48 avg300=4.10 total=124680210\n");
+    assert_eq!(some.map(|some| some.avg10), Some(7.32));
+    assert_eq!(full, None);
+  }
+
+  #[test]
+  fn test_get_pressure_info_from_fixture() {
+    let fixture = FixtureTree::new("pressure");
+    fixture
+      .write("proc/pressure/cpu", "some avg10=7.32 avg60=7.48 avg300=4.10 total=124680210\n")
+      .write(
+        "proc/pressure/memory",
+        "some avg10=0.28 avg60=0.55 avg300=0.27 total=9729874\nfull avg10=0.20 avg60=0.31 avg300=0.14 total=7458359\n",
+      )
+      .write("sys/fs/cgroup/cgroup.controllers", "cpu io memory pids\n")
+      .write(
+        "sys/fs/cgroup/system.slice/io.pressure",
+        "some avg10=1.50 avg60=0.80 avg300=0.20 total=5000\nfull avg10=1.00 avg60=0.50 avg300=0.10 total=4000\n",
+      )
+      .create_dir("sys/fs/cgroup/init.scope");
+    let mut history = CounterHistory::default();
+    let pressure = get_pressure_info(&mut history, &fixture.roots()).unwrap();
+
+    assert_eq!(
+      pressure.system.iter().map(|resource| (resource.resource, resource.full.is_some())).collect::<Vec<_>>(),
+      vec![(PressureResource::Cpu, false), (PressureResource::Memory, true)]
+    );
+    assert_eq!(pressure.system[0].some.stall_percent, None);
+    assert_eq!(pressure.cgroups.len(), 1);
+    assert_eq!(pressure.cgroups[0].path, "system.slice");
+    assert_eq!(pressure.cgroups[0].resources[0].resource, PressureResource::Io);
+
+    let pressure = get_pressure_info(&mut history, &fixture.roots()).unwrap();
+    assert_eq!(pressure.system[0].some.stall_percent, Some(0.0));
+  }
+}
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs};
 
 use color_eyre::eyre::Result;
 use serde::{Deserialize, Serialize};
 use sysinfo::{Pid, Process, System};
 
+use super::{
+  cgroups::{attribute_cgroup, parse_proc_cgroup, Attribution},
+  pseudo_fs::PseudoFsRoots,
+};
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProcessData {
   pub pid: u32,
@@ -11,6 +16,8 @@ pub struct ProcessData {
   pub name: String,
   pub status: String,
   pub cpu_usage: f32,
+  // systemd unit or container derived from the cgroup, `None` for kernel threads and processes in the root cgroup
+  pub attribution: Option<Attribution>,
 }
 
 pub type ProcessDataCollection = Vec<ProcessData>;
@@ -30,12 +37,23 @@ impl IntoProcessDataCollection for &HashMap<Pid, Process> {
           parent: process.parent().map(Pid::as_u32),
           status: process.status().to_string(),
           cpu_usage: process.cpu_usage(),
+          attribution: None,
         }
       })
       .collect()
   }
 }
 
-pub fn get_process_info(system: &System) -> Result<ProcessDataCollection> {
-  Ok(system.processes().into_process_data_collection())
+/// Reads what the process with `pid` belongs to from `/proc/<pid>/cgroup`.
+fn read_attribution(pid: u32, roots: &PseudoFsRoots) -> Option<Attribution> {
+  let contents = fs::read_to_string(roots.proc(pid.to_string()).join("cgroup")).ok()?;
+  attribute_cgroup(parse_proc_cgroup(&contents)?)
+}
+
+pub fn get_process_info(system: &System, roots: &PseudoFsRoots) -> Result<ProcessDataCollection> {
+  let mut processes = system.processes().into_process_data_collection();
+  for process in &mut processes {
+    process.attribution = read_attribution(process.pid, roots);
+  }
+  Ok(processes)
 }
@@ -0,0 +1,145 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  str::FromStr,
+};
+
+/// Block devices and their partitions, relative to the sysfs root.
+pub(crate) const CLASS_BLOCK: &str = "class/block";
+
+/// Network interfaces, relative to the sysfs root.
+pub(crate) const CLASS_NET: &str = "class/net";
+
+/// Devices grouped by the bus they sit on, e.g. `pci/devices` and `usb/devices`, relative to the sysfs root.
+pub(crate) const BUS: &str = "bus";
+
+/// Per CPU directories (`cpuN`) with cpufreq and cpuidle attributes, relative to the sysfs root.
+pub(crate) const DEVICES_CPU: &str = "devices/system/cpu";
+
+/// `/proc/diskstats` and the sysfs `size` attribute always count in 512 byte sectors, regardless of the device's
+/// sector size.
+pub(crate) const SECTOR_SIZE: u64 = 512;
+
+const CGROUP: &str = "fs/cgroup";
+// Hybrid hierarchies mount cgroup v2 next to the v1 controllers
+const CGROUP_UNIFIED: &str = "fs/cgroup/unified";
+
+/// Where procfs and sysfs are mounted. Collectors resolve every path through these roots, so they can run against
+/// fixture trees in tests or a copy of `/proc` and `/sys` captured on another machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PseudoFsRoots {
+  pub proc_root: PathBuf,
+  pub sys_root: PathBuf,
+}
+
+impl Default for PseudoFsRoots {
+  fn default() -> Self {
+    Self::new("/proc", "/sys")
+  }
+}
+
+impl PseudoFsRoots {
+  pub fn new(proc_root: impl Into<PathBuf>, sys_root: impl Into<PathBuf>) -> Self {
+    Self { proc_root: proc_root.into(), sys_root: sys_root.into() }
+  }
+
+  /// Whether the roots are the running machine's procfs and sysfs rather than a capture.
+  pub fn is_live(&self) -> bool {
+    *self == Self::default()
+  }
+
+  /// Resolves a path relative to the procfs root, e.g. `net/snmp`.
+  pub fn proc(&self, path: impl AsRef<Path>) -> PathBuf {
+    self.proc_root.join(path)
+  }
+
+  /// Resolves a path relative to the sysfs root, e.g. `class/net`.
+  pub fn sys(&self, path: impl AsRef<Path>) -> PathBuf {
+    self.sys_root.join(path)
+  }
+}
+
+/// Reads a file from a pseudo filesystem (`/proc`, `/sys`) and returns its contents with surrounding whitespace
+/// removed. Returns `None` if the file does not exist or cannot be read.
+pub fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+  fs::read_to_string(path).ok().map(|contents| contents.trim().to_owned())
+}
+
+/// Reads a single value from a pseudo filesystem file, returning `None` if it is missing or does not parse as `T`.
+pub fn read_value<T: FromStr>(path: impl AsRef<Path>) -> Option<T> {
+  read_trimmed(path)?.parse().ok()
+}
+
+/// Lists the entry names of a directory sorted by name, or an empty list if the directory cannot be read.
+pub fn list_dir(path: impl AsRef<Path>) -> Vec<String> {
+  let Ok(entries) = fs::read_dir(path) else {
+    return Vec::new();
+  };
+  let mut names: Vec<String> =
+    entries.filter_map(|entry| entry.ok()).map(|entry| entry.file_name().to_string_lossy().into_owned()).collect();
+  names.sort();
+  names
+}
+
+/// Mount point of the cgroup v2 hierarchy below the sysfs root, either the unified mount or the v2 part of a hybrid
+/// setup.
+pub(crate) fn cgroup2_root(roots: &PseudoFsRoots) -> Option<PathBuf> {
+  [CGROUP, CGROUP_UNIFIED].iter().map(|path| roots.sys(path)).find(|path| path.join("cgroup.controllers").exists())
+}
+
+/// A procfs and sysfs tree written to a temporary directory for tests, removed again when dropped. Paths are relative
+/// to the tree, e.g. `proc/net/snmp`.
+#[cfg(test)]
+pub(crate) struct FixtureTree {
+  root: PathBuf,
+}
+
+#[cfg(test)]
+impl FixtureTree {
+  pub(crate) fn new(name: &str) -> Self {
+    let root = std::env::temp_dir().join(format!("{}-{}-{}", env!("CARGO_PKG_NAME"), name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    Self { root }
+  }
+
+  /// Writes `contents` to `path`, creating its parent directories.
+  pub(crate) fn write(&self, path: &str, contents: &str) -> &Self {
+    let path = self.root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+    self
+  }
+
+  pub(crate) fn create_dir(&self, path: &str) -> &Self {
+    fs::create_dir_all(self.root.join(path)).unwrap();
+    self
+  }
+
+  pub(crate) fn roots(&self) -> PseudoFsRoots {
+    PseudoFsRoots::new(self.root.join("proc"), self.root.join("sys"))
+  }
+}
+
+#[cfg(test)]
+impl Drop for FixtureTree {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.root);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_roots() {
+    let roots = PseudoFsRoots::new("/tmp/capture/proc", "/tmp/capture/sys");
+    assert_eq!(roots.proc("net/snmp"), PathBuf::from("/tmp/capture/proc/net/snmp"));
+    assert_eq!(roots.sys("class/net"), PathBuf::from("/tmp/capture/sys/class/net"));
+    assert_eq!(PseudoFsRoots::default().proc("1/comm"), PathBuf::from("/proc/1/comm"));
+    assert!(PseudoFsRoots::new("/proc/", "/sys").is_live());
+    assert!(!roots.is_live());
+  }
+}
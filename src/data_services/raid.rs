@@ -0,0 +1,291 @@
+use std::{fs, io::ErrorKind};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  block_devices::BlockDeviceKind,
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots, CLASS_BLOCK, SECTOR_SIZE},
+};
+
+const MDSTAT: &str = "mdstat";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MdMember {
+  pub device: String,
+  // Role of the device in the array, the number in brackets after its name
+  pub role: u32,
+  pub faulty: bool,
+  pub spare: bool,
+}
+
+/// A resync, recovery, check or reshape running on an array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MdSync {
+  // `resync`, `recovery`, `check`, `repair` or `reshape`
+  pub action: String,
+  // `None` while the operation is delayed or pending
+  pub percent: Option<f64>,
+  pub finish_minutes: Option<f64>,
+  pub speed_kib_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MdArray {
+  pub name: String,
+  pub active: bool,
+  pub read_only: bool,
+  // RAID personality, e.g. `raid1`, unknown for inactive arrays
+  pub level: Option<String>,
+  pub members: Vec<MdMember>,
+  pub size_kib: u64,
+  // Device slots of the array and how many of them hold a working device, `[2/1]` in mdstat
+  pub total_devices: Option<u32>,
+  pub working_devices: Option<u32>,
+  // Per slot state, e.g. `U_` when the second device is missing
+  pub slot_status: Option<String>,
+  pub sync: Option<MdSync>,
+}
+
+impl MdArray {
+  /// An array is degraded when it runs with fewer devices than it has slots or a member has failed.
+  pub fn is_degraded(&self) -> bool {
+    let missing = matches!((self.total_devices, self.working_devices), (Some(total), Some(working)) if working < total);
+    missing || self.members.iter().any(|member| member.faulty)
+  }
+}
+
+/// A device-mapper device along with the name it is known by in `/dev/mapper`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceMapperData {
+  pub device: String,
+  pub name: String,
+  pub kind: BlockDeviceKind,
+  pub size_bytes: u64,
+  pub suspended: bool,
+  // Devices the mapping is built on, e.g. the partition holding a LUKS container
+  pub slaves: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct RaidData {
+  pub md_arrays: Vec<MdArray>,
+  pub device_mappers: Vec<DeviceMapperData>,
+}
+
+impl RaidData {
+  pub fn degraded_arrays(&self) -> impl Iterator<Item = &MdArray> {
+    self.md_arrays.iter().filter(|array| array.is_degraded())
+  }
+}
+
+/// Parses a member of an array such as `sdb1[1]`, `sdc1[2](F)` or `sdd1[3](S)`.
+fn parse_md_member(raw: &str) -> Option<MdMember> {
+  let (device, rest) = raw.split_once('[')?;
+  let (role, flags) = rest.split_once(']')?;
+  Some(MdMember {
+    device: device.to_string(),
+    role: role.parse().ok()?,
+    faulty: flags.contains("(F)"),
+    spare: flags.contains("(S)"),
+  })
+}
+
+/// Parses the array line `md0 : active raid1 sdb1[1] sda1[0]`.
+fn parse_md_header(line: &str) -> Option<MdArray> {
+  let (name, rest) = line.split_once(" : ")?;
+  let mut words = rest.split_whitespace().peekable();
+  let active = words.next()? == "active";
+  let read_only = words.next_if(|word| word.starts_with('(')).is_some_and(|word| word.contains("read-only"));
+  let level = if active { words.next_if(|word| !word.contains('[')).map(str::to_string) } else { None };
+
+  Some(MdArray {
+    name: name.trim().to_string(),
+    active,
+    read_only,
+    level,
+    members: words.filter_map(parse_md_member).collect(),
+    size_kib: 0,
+    total_devices: None,
+    working_devices: None,
+    slot_status: None,
+    sync: None,
+  })
+}
+
+/// Parses the status line `1953382464 blocks super 1.2 [2/1] [U_]`.
+fn parse_md_status(array: &mut MdArray, line: &str) {
+  let words: Vec<&str> = line.split_whitespace().collect();
+  array.size_kib = words.first().and_then(|blocks| blocks.parse().ok()).unwrap_or_default();
+  for word in words.iter().filter_map(|word| word.strip_prefix('[')?.strip_suffix(']')) {
+    if let Some((total, working)) = word.split_once('/') {
+      array.total_devices = total.parse().ok();
+      array.working_devices = working.parse().ok();
+    } else if word.chars().all(|c| c == 'U' || c == '_') {
+      array.slot_status = Some(word.to_string());
+    }
+  }
+}
+
+/// Parses the progress line `[=>....]  recovery =  8.5% (166123520/1953382400) finish=152.4min speed=195407K/sec`
+/// and the `resync=DELAYED` / `resync=PENDING` lines of arrays waiting for their turn.
+fn parse_md_sync(line: &str) -> Option<MdSync> {
+  let line = line.trim();
+  // Skip the progress bar in front of running operations
+  let line = match line.strip_prefix('[') {
+    Some(rest) => rest.split_once(']')?.1,
+    None => line,
+  };
+  let (action, rest) = line.split_once('=')?;
+  let action = action.trim();
+  if !matches!(action, "resync" | "recovery" | "check" | "repair" | "reshape") {
+    return None;
+  }
+  let field = |key: &str| rest.split_whitespace().find_map(|word| word.strip_prefix(key));
+
+  Some(MdSync {
+    action: action.to_string(),
+    percent: rest.split_whitespace().next().and_then(|percent| percent.strip_suffix('%')?.parse().ok()),
+    finish_minutes: field("finish=").and_then(|finish| finish.strip_suffix("min")?.parse().ok()),
+    speed_kib_per_sec: field("speed=").and_then(|speed| speed.strip_suffix("K/sec")?.parse().ok()),
+  })
+}
+
+/// Parses `/proc/mdstat`. Each array starts with an `mdN : ...` line followed by indented detail lines.
+pub fn parse_mdstat(contents: &str) -> Vec<MdArray> {
+  let mut arrays: Vec<MdArray> = Vec::new();
+  for line in contents.lines() {
+    if line.starts_with("md") {
+      arrays.extend(parse_md_header(line));
+      continue;
+    }
+    let Some(array) = arrays.last_mut() else {
+      continue;
+    };
+    if !line.starts_with(char::is_whitespace) {
+      continue;
+    }
+    if line.contains(" blocks") {
+      parse_md_status(array, line);
+    } else if let Some(sync) = parse_md_sync(line) {
+      array.sync = Some(sync);
+    }
+  }
+  arrays
+}
+
+fn read_device_mappers(roots: &PseudoFsRoots) -> Vec<DeviceMapperData> {
+  list_dir(roots.sys(CLASS_BLOCK))
+    .into_iter()
+    .filter(|device| device.starts_with("dm-"))
+    .map(|device| {
+      let path = roots.sys(CLASS_BLOCK).join(&device);
+      DeviceMapperData {
+        name: read_trimmed(path.join("dm/name")).unwrap_or_default(),
+        kind: BlockDeviceKind::from_dm_uuid(&read_trimmed(path.join("dm/uuid")).unwrap_or_default()),
+        size_bytes: read_value::<u64>(path.join("size")).unwrap_or_default() * SECTOR_SIZE,
+        suspended: read_value::<u8>(path.join("dm/suspended")) == Some(1),
+        slaves: list_dir(path.join("slaves")),
+        device,
+      }
+    })
+    .collect()
+}
+
+pub fn get_raid_info(roots: &PseudoFsRoots) -> Result<RaidData> {
+  // mdstat only exists once the md driver is loaded, so a missing file means there are no arrays
+  let md_arrays = match fs::read_to_string(roots.proc(MDSTAT)) {
+    Ok(contents) => parse_mdstat(&contents),
+    Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+    Err(e) => return Err(e.into()),
+  };
+  Ok(RaidData { md_arrays, device_mappers: read_device_mappers(roots) })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  const MDSTAT: &str = "Personalities : [raid1] [raid6] [raid5] [raid4]
+md0 : active raid1 sdb1[1] sda1[0]
+      1953382464 blocks super 1.2 [2/2] [UU]
+      bitmap: 0/15 pages [0KB], 65536KB chunk
+
+md1 : active raid5 sdd1[3] sdc1[1] sdb2[0](F)
+      3906764800 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [U_U]
+      [=>...................]  recovery =  8.5% (166123520/1953382400) finish=152.4min speed=195407K/sec
+
+md2 : active (auto-read-only) raid1 sde1[0] sdf1[1]
+      976630488 blocks super 1.2 [2/2] [UU]
+        resync=PENDING
+
+md3 : inactive sdg1[0](S)
+      976630488 blocks super 1.2
+
+unused devices: <none>
+";
+
+  #[test]
+  fn test_parse_mdstat() {
+    let arrays = parse_mdstat(MDSTAT);
+
+    assert_eq!(arrays.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["md0", "md1", "md2", "md3"]);
+
+    let healthy = &arrays[0];
+    assert_eq!(healthy.level.as_deref(), Some("raid1"));
+    assert_eq!(healthy.size_kib, 1953382464);
+    assert_eq!(healthy.slot_status.as_deref(), Some("UU"));
+    assert!(!healthy.is_degraded());
+
+    let degraded = &arrays[1];
+    assert!(degraded.is_degraded());
+    assert_eq!((degraded.total_devices, degraded.working_devices), (Some(3), Some(2)));
+    assert_eq!(degraded.members[2], MdMember { device: "sdb2".into(), role: 0, faulty: true, spare: false });
+    assert_eq!(
+      degraded.sync,
+      Some(MdSync {
+        action: "recovery".into(),
+        percent: Some(8.5),
+        finish_minutes: Some(152.4),
+        speed_kib_per_sec: Some(195407),
+      })
+    );
+
+    let pending = &arrays[2];
+    assert!(pending.read_only);
+    assert_eq!(pending.level.as_deref(), Some("raid1"));
+    assert_eq!(pending.sync.as_ref().map(|s| (s.action.as_str(), s.percent)), Some(("resync", None)));
+
+    let inactive = &arrays[3];
+    assert!(!inactive.active);
+    assert_eq!(inactive.level, None);
+    assert!(inactive.members[0].spare);
+  }
+
+  #[test]
+  fn test_get_raid_info_from_fixture() {
+    let fixture = FixtureTree::new("raid");
+    fixture
+      .write("proc/mdstat", MDSTAT)
+      .write("sys/class/block/dm-0/dm/name", "vg0-root\n")
+      .write("sys/class/block/dm-0/dm/uuid", "LVM-Xk2jZ3k9\n")
+      .write("sys/class/block/dm-0/dm/suspended", "0\n")
+      .write("sys/class/block/dm-0/size", "2048\n")
+      .create_dir("sys/class/block/dm-0/slaves/md0")
+      .write("sys/class/block/sda/size", "4096\n");
+    let raid = get_raid_info(&fixture.roots()).unwrap();
+
+    assert_eq!(raid.md_arrays.len(), 4);
+    assert_eq!(raid.device_mappers, vec![DeviceMapperData {
+      device: "dm-0".into(),
+      name: "vg0-root".into(),
+      kind: BlockDeviceKind::from_dm_uuid("LVM-Xk2jZ3k9"),
+      size_bytes: 1024 * 1024,
+      suspended: false,
+      slaves: vec!["md0".into()],
+    }]);
+  }
+}
@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::{
+  counters::CounterHistory,
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots},
+};
+
+const CLASS_POWERCAP: &str = "class/powercap";
+// Zones are named `intel-rapl:<package>` with `intel-rapl:<package>:<subzone>` below them. AMD CPUs use the same
+// names. The `intel-rapl-mmio` zones report the same package energy through another interface and are skipped.
+const RAPL_ZONE_PREFIX: &str = "intel-rapl:";
+
+/// Reading of a zone's energy counter. The counter wraps around at `max_energy_range_uj`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnergySample {
+  pub energy_uj: u64,
+  pub max_energy_range_uj: u64,
+}
+
+/// Energy counters keyed by zone id.
+pub type RaplCounters = BTreeMap<String, EnergySample>;
+
+/// Why the energy counters cannot be used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum RaplUnavailable {
+  #[strum(serialize = "No RAPL powercap zones found, the intel_rapl driver is not loaded or the CPU lacks RAPL")]
+  NoDriver,
+  // Since Linux 5.10 `energy_uj` is only readable by root, as the counters leak information about the running code
+  #[strum(serialize = "Permission denied reading the energy counters, run as root or make energy_uj readable")]
+  PermissionDenied,
+  #[strum(serialize = "The energy counters could not be read: {0}")]
+  Unreadable(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaplZoneData {
+  // e.g. `intel-rapl:0:1`
+  pub id: String,
+  // `package-0`, `core`, `uncore`, `dram` or `psys`
+  pub name: String,
+  // Id of the package zone for subzones
+  pub parent: Option<String>,
+  // Average power since the previous collection, `None` on the first one
+  pub power_w: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct RaplData {
+  pub zones: Vec<RaplZoneData>,
+  pub unavailable: Option<RaplUnavailable>,
+}
+
+impl RaplData {
+  /// Combined power of all packages, the figure process power shares are estimated from.
+  pub fn package_power_w(&self) -> Option<f64> {
+    let packages: Vec<f64> =
+      self.zones.iter().filter(|zone| zone.name.starts_with("package")).filter_map(|zone| zone.power_w).collect();
+    (!packages.is_empty()).then(|| packages.iter().sum())
+  }
+}
+
+/// Energy used between two readings of a counter, accounting for the counter wrapping around.
+pub fn energy_delta_uj(previous: EnergySample, current: EnergySample) -> u64 {
+  if current.energy_uj >= previous.energy_uj {
+    current.energy_uj - previous.energy_uj
+  } else {
+    current.max_energy_range_uj.saturating_sub(previous.energy_uj) + current.energy_uj
+  }
+}
+
+/// Parent zone of a subzone id, e.g. `intel-rapl:0` for `intel-rapl:0:2`.
+fn parent_zone(id: &str) -> Option<String> {
+  let (parent, _) = id.strip_prefix(RAPL_ZONE_PREFIX)?.rsplit_once(':')?;
+  Some(format!("{}{}", RAPL_ZONE_PREFIX, parent))
+}
+
+fn read_energy(zone: &Path) -> Result<EnergySample, RaplUnavailable> {
+  let energy = fs::read_to_string(zone.join("energy_uj")).map_err(|error| {
+    match error.kind() {
+      ErrorKind::PermissionDenied => RaplUnavailable::PermissionDenied,
+      _ => RaplUnavailable::Unreadable(error.to_string()),
+    }
+  })?;
+  Ok(EnergySample {
+    energy_uj: energy.trim().parse().map_err(|_| RaplUnavailable::Unreadable(format!("invalid value {:?}", energy)))?,
+    max_energy_range_uj: read_value(zone.join("max_energy_range_uj")).unwrap_or(u64::MAX),
+  })
+}
+
+pub fn get_rapl_info(history: &mut CounterHistory<RaplCounters>, roots: &PseudoFsRoots) -> Result<RaplData> {
+  let powercap = roots.sys(CLASS_POWERCAP);
+  let ids: Vec<String> = list_dir(&powercap).into_iter().filter(|name| name.starts_with(RAPL_ZONE_PREFIX)).collect();
+  if ids.is_empty() {
+    return Ok(RaplData { zones: Vec::new(), unavailable: Some(RaplUnavailable::NoDriver) });
+  }
+
+  let mut current = RaplCounters::new();
+  for id in &ids {
+    let zone = powercap.join(id);
+    // Zones disabled by the firmware keep a stale counter
+    if read_value::<u8>(zone.join("enabled")) == Some(0) {
+      continue;
+    }
+    match read_energy(&zone) {
+      Ok(sample) => {
+        current.insert(id.clone(), sample);
+      },
+      Err(unavailable) => return Ok(RaplData { zones: Vec::new(), unavailable: Some(unavailable) }),
+    }
+  }
+  let previous = history.record(&current);
+
+  let zones = current
+    .iter()
+    .map(|(id, sample)| {
+      let power_w = previous.as_ref().and_then(|(previous, elapsed_secs)| {
+        let previous = previous.get(id)?;
+        (*elapsed_secs > 0.0).then(|| energy_delta_uj(*previous, *sample) as f64 / 1_000_000.0 / elapsed_secs)
+      });
+      RaplZoneData {
+        id: id.clone(),
+        name: read_trimmed(powercap.join(id).join("name")).unwrap_or_else(|| id.clone()),
+        parent: parent_zone(id),
+        power_w,
+      }
+    })
+    .collect();
+
+  Ok(RaplData { zones, unavailable: None })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_energy_delta_wraps_around() {
+    let sample = |energy_uj| EnergySample { energy_uj, max_energy_range_uj: 262_143_328_850 };
+
+    assert_eq!(energy_delta_uj(sample(1_000_000), sample(6_000_000)), 5_000_000);
+    assert_eq!(energy_delta_uj(sample(262_142_328_850), sample(4_000_000)), 5_000_000);
+  }
+
+  #[test]
+  fn test_parent_zone() {
+    assert_eq!(parent_zone("intel-rapl:0:2"), Some("intel-rapl:0".to_string()));
+    assert_eq!(parent_zone("intel-rapl:1"), None);
+  }
+
+  #[test]
+  fn test_package_power() {
+    let zone = |id: &str, name: &str, power_w| {
+      RaplZoneData { id: id.into(), name: name.into(), parent: parent_zone(id), power_w: Some(power_w) }
+    };
+    let data = RaplData {
+      zones: vec![
+        zone("intel-rapl:0", "package-0", 12.5),
+        zone("intel-rapl:0:0", "core", 8.0),
+        zone("intel-rapl:1", "package-1", 10.0),
+      ],
+      unavailable: None,
+    };
+
+    assert_eq!(data.package_power_w(), Some(22.5));
+    assert_eq!(RaplData::default().package_power_w(), None);
+  }
+}
@@ -0,0 +1,230 @@
+use std::{collections::HashMap, fs, path::Path, sync::OnceLock, time::Duration};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots, BUS};
+
+// Locations of the ID databases shipped by the hwdata and pciutils/usbutils packages
+const PCI_IDS: [&str; 2] = ["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids"];
+const USB_IDS: [&str; 2] = ["/usr/share/hwdata/usb.ids", "/usr/share/misc/usb.ids"];
+
+static PCI_DATABASE: OnceLock<IdDatabase> = OnceLock::new();
+static USB_DATABASE: OnceLock<IdDatabase> = OnceLock::new();
+
+/// Vendor and product names from a `pci.ids` or `usb.ids` file, keyed by their lowercase hex IDs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IdDatabase {
+  vendors: HashMap<String, (String, HashMap<String, String>)>,
+}
+
+impl IdDatabase {
+  pub fn lookup(&self, vendor: &str, product: &str) -> Option<String> {
+    let (vendor_name, products) = self.vendors.get(&vendor.to_ascii_lowercase())?;
+    Some(match products.get(&product.to_ascii_lowercase()) {
+      Some(product_name) => format!("{} {}", vendor_name, product_name),
+      None => vendor_name.clone(),
+    })
+  }
+}
+
+fn is_hex_id(id: &str) -> bool {
+  id.len() == 4 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parses the vendor section of an ID database. Vendors are unindented, their products indented by one tab and
+/// subsystems by two. The vendor list ends at the first section of other data, e.g. `C 00  Unclassified device`.
+pub fn parse_ids(contents: &str) -> IdDatabase {
+  let mut database = IdDatabase::default();
+  let mut vendor: Option<String> = None;
+  for line in contents.lines().filter(|line| !line.starts_with('#') && !line.trim().is_empty()) {
+    if line.starts_with("\t\t") {
+      continue;
+    }
+    if let Some(product) = line.strip_prefix('\t') {
+      let (Some(vendor), Some((id, name))) = (&vendor, product.split_once("  ")) else { continue };
+      if let Some((_, products)) = database.vendors.get_mut(vendor) {
+        products.insert(id.to_ascii_lowercase(), name.trim().to_string());
+      }
+      continue;
+    }
+    match line.split_once("  ") {
+      Some((id, name)) if is_hex_id(id) => {
+        database.vendors.insert(id.to_ascii_lowercase(), (name.trim().to_string(), HashMap::new()));
+        vendor = Some(id.to_ascii_lowercase());
+      },
+      _ => break,
+    }
+  }
+  database
+}
+
+fn id_database(bus: &str) -> &'static IdDatabase {
+  let (cell, paths) = match bus {
+    "usb" => (&USB_DATABASE, USB_IDS),
+    _ => (&PCI_DATABASE, PCI_IDS),
+  };
+  cell.get_or_init(|| {
+    let contents = paths.iter().find_map(|path| fs::read_to_string(path).ok());
+    contents.map(|contents| parse_ids(&contents)).unwrap_or_default()
+  })
+}
+
+/// Human readable name of a PCI or USB device. USB devices report their own product strings, PCI devices are looked
+/// up in `pci.ids` and fall back to their `[vendor:device]` IDs.
+pub(crate) fn device_name(bus: &str, device: &Path) -> String {
+  let (vendor, product) = match bus {
+    "usb" => {
+      match (read_trimmed(device.join("manufacturer")), read_trimmed(device.join("product"))) {
+        (Some(manufacturer), Some(product)) => return format!("{} {}", manufacturer, product),
+        (None, Some(product)) => return product,
+        _ => (read_trimmed(device.join("idVendor")), read_trimmed(device.join("idProduct"))),
+      }
+    },
+    _ => (read_trimmed(device.join("vendor")), read_trimmed(device.join("device"))),
+  };
+  let vendor = vendor.unwrap_or_default().trim_start_matches("0x").to_string();
+  let product = product.unwrap_or_default().trim_start_matches("0x").to_string();
+  id_database(bus).lookup(&vendor, &product).unwrap_or_else(|| format!("[{}:{}]", vendor, product))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum RuntimeStatus {
+  #[strum(serialize = "active")]
+  Active,
+  #[strum(serialize = "suspended")]
+  Suspended,
+  #[strum(serialize = "suspending")]
+  Suspending,
+  #[strum(serialize = "resuming")]
+  Resuming,
+  #[strum(serialize = "error")]
+  Error,
+  // The driver does not implement runtime power management
+  #[strum(serialize = "unsupported")]
+  Unsupported,
+}
+
+impl From<&str> for RuntimeStatus {
+  fn from(status: &str) -> Self {
+    match status {
+      "active" => RuntimeStatus::Active,
+      "suspended" => RuntimeStatus::Suspended,
+      "suspending" => RuntimeStatus::Suspending,
+      "resuming" => RuntimeStatus::Resuming,
+      "error" => RuntimeStatus::Error,
+      _ => RuntimeStatus::Unsupported,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmDevice {
+  // `pci` or `usb`
+  pub bus: String,
+  // Bus address, e.g. `0000:00:14.0` or `1-2`
+  pub id: String,
+  pub name: String,
+  pub driver: Option<String>,
+  pub runtime_status: RuntimeStatus,
+  // `auto` lets the device suspend when idle, `on` keeps it powered
+  pub control: Option<String>,
+  // Idle time before the device suspends. Negative values disable autosuspend, most PCI drivers do not support it.
+  pub autosuspend_delay_ms: Option<i64>,
+  pub active_time: Duration,
+  pub suspended_time: Duration,
+}
+
+pub type PmDeviceCollection = Vec<PmDevice>;
+
+impl PmDevice {
+  /// Share of the time since boot the device has spent active, for devices with runtime PM.
+  pub fn active_percent(&self) -> Option<f64> {
+    let total = self.active_time + self.suspended_time;
+    (!total.is_zero()).then(|| self.active_time.as_secs_f64() / total.as_secs_f64() * 100.0)
+  }
+}
+
+fn read_device(bus: &str, device: &Path) -> Option<PmDevice> {
+  let power = device.join("power");
+  let runtime_status = read_trimmed(power.join("runtime_status"))?;
+  let millis = |name: &str| Duration::from_millis(read_value(power.join(name)).unwrap_or_default());
+  Some(PmDevice {
+    bus: bus.to_string(),
+    id: device.file_name()?.to_string_lossy().into_owned(),
+    name: device_name(bus, device),
+    driver: fs::read_link(device.join("driver"))
+      .ok()
+      .and_then(|driver| Some(driver.file_name()?.to_string_lossy().into_owned())),
+    runtime_status: runtime_status.as_str().into(),
+    control: read_trimmed(power.join("control")),
+    autosuspend_delay_ms: read_value(power.join("autosuspend_delay_ms")),
+    active_time: millis("runtime_active_time"),
+    suspended_time: millis("runtime_suspended_time"),
+  })
+}
+
+pub fn get_runtime_pm_info(roots: &PseudoFsRoots) -> Result<PmDeviceCollection> {
+  Ok(
+    ["pci", "usb"]
+      .iter()
+      .flat_map(|bus| {
+        let devices = roots.sys(BUS).join(bus).join("devices");
+        list_dir(&devices)
+          .into_iter()
+          // USB interfaces (`1-1:1.0`) are powered with their device
+          .filter(|name| *bus == "pci" || !name.contains(':'))
+          .filter_map(|name| read_device(bus, &devices.join(name)))
+          .collect::<Vec<_>>()
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_ids() {
+    let ids = "# List of PCI ID's
+8086  Intel Corporation
+\t0d57  Device 0d57
+\t9dc8  Cannon Point-LP High Definition Audio Controller
+\t\t17aa 2279  ThinkPad T490
+1af4  Red Hat, Inc.
+
+C 00  Unclassified device
+\t00  Non-VGA unclassified device
+";
+    let database = parse_ids(ids);
+
+    assert_eq!(
+      database.lookup("8086", "9DC8"),
+      Some("Intel Corporation Cannon Point-LP High Definition Audio Controller".to_string())
+    );
+    assert_eq!(database.lookup("1af4", "1042"), Some("Red Hat, Inc.".to_string()));
+    assert_eq!(database.lookup("ffff", "0000"), None);
+  }
+
+  #[test]
+  fn test_active_percent() {
+    let device = PmDevice {
+      bus: "pci".into(),
+      id: "0000:00:14.0".into(),
+      name: "USB controller".into(),
+      driver: Some("xhci_hcd".into()),
+      runtime_status: "suspended".into(),
+      control: Some("auto".into()),
+      autosuspend_delay_ms: None,
+      active_time: Duration::from_secs(30),
+      suspended_time: Duration::from_secs(90),
+    };
+
+    assert_eq!(device.active_percent(), Some(25.0));
+    assert_eq!(device.runtime_status, RuntimeStatus::Suspended);
+  }
+}
@@ -0,0 +1,134 @@
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::hwmon::HwmonChip;
+
+/// Non temperature sensor channels of a hwmon chip along with the factor turning their raw sysfs values into the unit
+/// they are displayed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum SensorKind {
+  Fan,
+  Voltage,
+  Power,
+  Current,
+}
+
+impl SensorKind {
+  fn attribute_prefix(&self) -> &'static str {
+    match self {
+      SensorKind::Fan => "fan",
+      SensorKind::Voltage => "in",
+      SensorKind::Power => "power",
+      SensorKind::Current => "curr",
+    }
+  }
+
+  /// Divisor from the sysfs unit: fans report RPM, voltages millivolts, power microwatts and currents milliamperes.
+  fn scale(&self) -> f64 {
+    match self {
+      SensorKind::Fan => 1.0,
+      SensorKind::Voltage => 1_000.0,
+      SensorKind::Power => 1_000_000.0,
+      SensorKind::Current => 1_000.0,
+    }
+  }
+
+  pub fn unit(&self) -> &'static str {
+    match self {
+      SensorKind::Fan => "RPM",
+      SensorKind::Voltage => "V",
+      SensorKind::Power => "W",
+      SensorKind::Current => "A",
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorReading {
+  // Identifies the reading across collections, e.g. `hwmon2/fan1`
+  pub id: String,
+  pub kind: SensorKind,
+  pub label: String,
+  // In the unit of the kind (RPM, V, W, A)
+  pub value: f64,
+  pub min: Option<f64>,
+  pub max: Option<f64>,
+}
+
+/// The fan, voltage, power and current readings of a single hwmon chip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorChipData {
+  pub id: String,
+  pub name: String,
+  pub readings: Vec<SensorReading>,
+}
+
+pub type SensorDataCollection = Vec<SensorChipData>;
+
+pub fn chip_sensors(chip: &HwmonChip) -> SensorChipData {
+  let readings = [SensorKind::Fan, SensorKind::Voltage, SensorKind::Power, SensorKind::Current]
+    .iter()
+    .flat_map(|kind| {
+      chip.channels(kind.attribute_prefix()).into_iter().map(move |channel| {
+        let value = |item: &str| channel.values.get(item).map(|value| *value as f64 / kind.scale());
+        SensorReading {
+          id: format!("{}/{}{}", chip.id, kind.attribute_prefix(), channel.index),
+          kind: *kind,
+          label: channel.label.clone(),
+          value: value("input").unwrap_or_default(),
+          min: value("min"),
+          max: value("max").or_else(|| value("cap")),
+        }
+      })
+    })
+    .collect();
+
+  SensorChipData { id: chip.id.clone(), name: chip.name.clone(), readings }
+}
+
+/// Takes the chips read for the temperatures, so the hwmon attributes are only read once per collection.
+pub fn get_sensor_info(chips: &[HwmonChip]) -> Result<SensorDataCollection> {
+  Ok(chips.iter().map(chip_sensors).filter(|chip| !chip.readings.is_empty()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_chip_sensors() {
+    let chip = HwmonChip {
+      id: "hwmon4".into(),
+      name: "nct6798".into(),
+      attributes: BTreeMap::from([
+        ("fan2_input".into(), "1184".into()),
+        ("fan2_label".into(), "CPU fan".into()),
+        ("fan2_min".into(), "300".into()),
+        ("in0_input".into(), "1032".into()),
+        ("power1_input".into(), "12500000".into()),
+        ("curr1_input".into(), "1250".into()),
+        ("temp1_input".into(), "40000".into()),
+      ]),
+    };
+
+    let sensors = chip_sensors(&chip);
+
+    assert_eq!(sensors.readings.len(), 4);
+    assert_eq!(sensors.readings[0], SensorReading {
+      id: "hwmon4/fan2".into(),
+      kind: SensorKind::Fan,
+      label: "CPU fan".into(),
+      value: 1184.0,
+      min: Some(300.0),
+      max: None,
+    });
+    assert_eq!((sensors.readings[1].label.as_str(), sensors.readings[1].value), ("in0", 1.032));
+    assert_eq!((sensors.readings[2].kind, sensors.readings[2].value), (SensorKind::Power, 12.5));
+    assert_eq!((sensors.readings[3].kind, sensors.readings[3].value), (SensorKind::Current, 1.25));
+  }
+}
@@ -0,0 +1,307 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  fs,
+  net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use sysinfo::{Pid, System};
+
+use super::pseudo_fs::{list_dir, PseudoFsRoots};
+
+// `__SO_ACCEPTCON` from linux/net.h, set in the Flags column of /proc/net/unix for listening sockets
+const UNIX_ACCEPTCON_FLAG: u32 = 0x0001_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Display)]
+pub enum SocketProtocol {
+  #[strum(serialize = "tcp")]
+  Tcp,
+  #[strum(serialize = "tcp6")]
+  Tcp6,
+  #[strum(serialize = "udp")]
+  Udp,
+  #[strum(serialize = "udp6")]
+  Udp6,
+  #[strum(serialize = "unix")]
+  Unix,
+}
+
+impl SocketProtocol {
+  fn proc_file(&self) -> &'static str {
+    match self {
+      SocketProtocol::Tcp => "net/tcp",
+      SocketProtocol::Tcp6 => "net/tcp6",
+      SocketProtocol::Udp => "net/udp",
+      SocketProtocol::Udp6 => "net/udp6",
+      SocketProtocol::Unix => "net/unix",
+    }
+  }
+
+  pub fn is_tcp(&self) -> bool {
+    matches!(self, SocketProtocol::Tcp | SocketProtocol::Tcp6)
+  }
+}
+
+/// Socket state using the same short names as `ss`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Display)]
+pub enum SocketState {
+  #[strum(serialize = "ESTAB")]
+  Established,
+  #[strum(serialize = "SYN-SENT")]
+  SynSent,
+  #[strum(serialize = "SYN-RECV")]
+  SynRecv,
+  #[strum(serialize = "FIN-WAIT-1")]
+  FinWait1,
+  #[strum(serialize = "FIN-WAIT-2")]
+  FinWait2,
+  #[strum(serialize = "TIME-WAIT")]
+  TimeWait,
+  #[strum(serialize = "CLOSE")]
+  Close,
+  #[strum(serialize = "CLOSE-WAIT")]
+  CloseWait,
+  #[strum(serialize = "LAST-ACK")]
+  LastAck,
+  #[strum(serialize = "LISTEN")]
+  Listen,
+  #[strum(serialize = "CLOSING")]
+  Closing,
+  #[strum(serialize = "NEW-SYN-RECV")]
+  NewSynRecv,
+  #[strum(serialize = "UNCONN")]
+  Unconnected,
+  #[strum(serialize = "UNKNOWN")]
+  Unknown,
+}
+
+impl SocketState {
+  /// Maps the hex `st` column of `/proc/net/{tcp,udp}*` (values from include/net/tcp_states.h).
+  fn from_inet(protocol: SocketProtocol, state: u8) -> Self {
+    match state {
+      0x01 => SocketState::Established,
+      0x02 => SocketState::SynSent,
+      0x03 => SocketState::SynRecv,
+      0x04 => SocketState::FinWait1,
+      0x05 => SocketState::FinWait2,
+      0x06 => SocketState::TimeWait,
+      // UDP sockets that are not connected report TCP_CLOSE
+      0x07 if !protocol.is_tcp() => SocketState::Unconnected,
+      0x07 => SocketState::Close,
+      0x08 => SocketState::CloseWait,
+      0x09 => SocketState::LastAck,
+      0x0A => SocketState::Listen,
+      0x0B => SocketState::Closing,
+      0x0C => SocketState::NewSynRecv,
+      _ => SocketState::Unknown,
+    }
+  }
+
+  /// Maps the `Flags` and `St` columns of `/proc/net/unix` (`socket_state` from include/uapi/linux/net.h).
+  fn from_unix(flags: u32, state: u8) -> Self {
+    if flags & UNIX_ACCEPTCON_FLAG != 0 {
+      return SocketState::Listen;
+    }
+    match state {
+      0x01 => SocketState::Unconnected,
+      0x02 => SocketState::SynSent,
+      0x03 => SocketState::Established,
+      0x04 => SocketState::Closing,
+      _ => SocketState::Unknown,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SocketData {
+  pub protocol: SocketProtocol,
+  pub state: SocketState,
+  // `ip:port` for inet sockets, the bound path (if any) for unix sockets
+  pub local_address: String,
+  pub remote_address: String,
+  pub inode: u64,
+  pub pid: Option<u32>,
+  pub process_name: Option<String>,
+}
+
+pub type SocketDataCollection = Vec<SocketData>;
+
+/// Counts the TCP (v4 and v6) sockets in each state.
+pub fn tcp_state_counts(sockets: &[SocketData]) -> BTreeMap<SocketState, usize> {
+  let mut counts = BTreeMap::new();
+  for socket in sockets.iter().filter(|s| s.protocol.is_tcp()) {
+    *counts.entry(socket.state).or_insert(0) += 1;
+  }
+  counts
+}
+
+/// Parses an inet socket table (`/proc/net/tcp`, `/proc/net/udp6`, ...). Malformed lines are skipped.
+pub fn parse_inet_sockets(protocol: SocketProtocol, contents: &str) -> SocketDataCollection {
+  contents
+    .lines()
+    .skip(1)
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      if fields.len() < 10 {
+        return None;
+      }
+      Some(SocketData {
+        protocol,
+        state: SocketState::from_inet(protocol, u8::from_str_radix(fields[3], 16).ok()?),
+        local_address: parse_inet_address(fields[1])?.to_string(),
+        remote_address: parse_inet_address(fields[2])?.to_string(),
+        inode: fields[9].parse().ok()?,
+        pid: None,
+        process_name: None,
+      })
+    })
+    .collect()
+}
+
+/// Parses `/proc/net/unix`. Malformed lines are skipped. Paths may contain spaces, everything after the inode is the
+/// path.
+pub fn parse_unix_sockets(contents: &str) -> SocketDataCollection {
+  contents
+    .lines()
+    .skip(1)
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      if fields.len() < 7 {
+        return None;
+      }
+      let flags = u32::from_str_radix(fields[3], 16).ok()?;
+      Some(SocketData {
+        protocol: SocketProtocol::Unix,
+        state: SocketState::from_unix(flags, u8::from_str_radix(fields[5], 16).ok()?),
+        local_address: fields[7..].join(" "),
+        remote_address: String::new(),
+        inode: fields[6].parse().ok()?,
+        pid: None,
+        process_name: None,
+      })
+    })
+    .collect()
+}
+
+/// Decodes a `HEXIP:HEXPORT` address. The kernel prints the network order IP as native endian 32 bit words, while
+/// the port is already converted to host order.
+fn parse_inet_address(raw: &str) -> Option<SocketAddr> {
+  let (ip, port) = raw.split_once(':')?;
+  let port = u16::from_str_radix(port, 16).ok()?;
+  match ip.len() {
+    8 => {
+      let ip = u32::from_str_radix(ip, 16).ok()?;
+      Some(SocketAddr::new(Ipv4Addr::from(ip.to_ne_bytes()).into(), port))
+    },
+    32 => {
+      let mut octets = [0u8; 16];
+      for (i, chunk) in octets.chunks_mut(4).enumerate() {
+        let word = u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).ok()?;
+        chunk.copy_from_slice(&word.to_ne_bytes());
+      }
+      Some(SocketAddr::new(Ipv6Addr::from(octets).into(), port))
+    },
+    _ => None,
+  }
+}
+
+/// Builds a map of socket inode to owning PID by reading the `socket:[inode]` links in `/proc/<pid>/fd`.
+/// Processes whose descriptors cannot be read (usually due to permissions) are skipped.
+fn socket_inode_owners(roots: &PseudoFsRoots) -> HashMap<u64, u32> {
+  let mut owners = HashMap::new();
+  for pid in list_dir(&roots.proc_root).iter().filter_map(|entry| entry.parse::<u32>().ok()) {
+    let Ok(fds) = fs::read_dir(roots.proc(pid.to_string()).join("fd")) else {
+      continue;
+    };
+    for fd in fds.filter_map(|fd| fd.ok()) {
+      let Ok(target) = fs::read_link(fd.path()) else {
+        continue;
+      };
+      let target = target.to_string_lossy();
+      if let Some(inode) = target.strip_prefix("socket:[").and_then(|s| s.strip_suffix(']')) {
+        if let Ok(inode) = inode.parse() {
+          owners.entry(inode).or_insert(pid);
+        }
+      }
+    }
+  }
+  owners
+}
+
+pub fn get_socket_info(system: &System, roots: &PseudoFsRoots) -> Result<SocketDataCollection> {
+  let mut sockets = Vec::new();
+  for protocol in [SocketProtocol::Tcp, SocketProtocol::Tcp6, SocketProtocol::Udp, SocketProtocol::Udp6] {
+    // IPv6 may be disabled, in which case the *6 files do not exist
+    if let Ok(contents) = fs::read_to_string(roots.proc(protocol.proc_file())) {
+      sockets.extend(parse_inet_sockets(protocol, &contents));
+    }
+  }
+  if let Ok(contents) = fs::read_to_string(roots.proc(SocketProtocol::Unix.proc_file())) {
+    sockets.extend(parse_unix_sockets(&contents));
+  }
+
+  let owners = socket_inode_owners(roots);
+  for socket in sockets.iter_mut() {
+    socket.pid = owners.get(&socket.inode).copied();
+    socket.process_name = socket.pid.and_then(|pid| system.process(Pid::from_u32(pid))).map(|p| p.name().to_string());
+  }
+
+  Ok(sockets)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_tcp_sockets() {
+    let contents = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A2B4 2207D9AC:01BB 01 00000000:00000000 02:000A7E1B 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1";
+    let sockets = parse_inet_sockets(SocketProtocol::Tcp, contents);
+
+    assert_eq!(sockets.len(), 2);
+    assert_eq!(sockets[0].local_address, "127.0.0.1:631");
+    assert_eq!(sockets[0].state, SocketState::Listen);
+    assert_eq!(sockets[0].inode, 23456);
+    assert_eq!(sockets[1].local_address, "10.0.2.15:41652");
+    assert_eq!(sockets[1].remote_address, "172.217.7.34:443");
+    assert_eq!(sockets[1].state, SocketState::Established);
+  }
+
+  #[test]
+  fn test_parse_tcp6_address() {
+    let address = parse_inet_address("00000000000000000000000001000000:0016").unwrap();
+    assert_eq!(address.to_string(), "[::1]:22");
+  }
+
+  #[test]
+  fn test_parse_unconnected_udp() {
+    let contents = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  512: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 20417 2 0000000000000000 0";
+    let sockets = parse_inet_sockets(SocketProtocol::Udp, contents);
+    assert_eq!(sockets[0].local_address, "127.0.0.53:53");
+    assert_eq!(sockets[0].state, SocketState::Unconnected);
+  }
+
+  #[test]
+  fn test_parse_unix_sockets() {
+    let contents = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 21987 /run/systemd/private
+0000000000000000: 00000003 00000000 00000000 0001 03 30123
+0000000000000000: 00000002 00000000 00010000 0001 01 30456 /tmp/my app/control.sock";
+    let sockets = parse_unix_sockets(contents);
+
+    assert_eq!(sockets.len(), 3);
+    assert_eq!(sockets[0].state, SocketState::Listen);
+    assert_eq!(sockets[0].local_address, "/run/systemd/private");
+    assert_eq!(sockets[1].state, SocketState::Established);
+    assert_eq!(sockets[1].inode, 30123);
+    assert_eq!(sockets[1].local_address, "");
+    assert_eq!(sockets[2].local_address, "/tmp/my app/control.sock");
+  }
+}
@@ -0,0 +1,207 @@
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use sysinfo::Components;
+
+use super::{
+  hwmon::HwmonChip,
+  pseudo_fs::{list_dir, read_trimmed, read_value, PseudoFsRoots},
+};
+
+const CLASS_THERMAL: &str = "class/thermal";
+
+// Temperatures in sysfs are reported in millidegrees Celsius
+const MILLIDEGREES: f64 = 1000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum TemperatureSource {
+  #[strum(serialize = "hwmon")]
+  Hwmon,
+  #[strum(serialize = "thermal")]
+  ThermalZone,
+  #[strum(serialize = "sysinfo")]
+  Sysinfo,
+}
+
+/// A trip point of a thermal zone, at which the kernel starts cooling (`active`, `passive`) or shuts down
+/// (`hot`, `critical`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TripPoint {
+  pub kind: String,
+  pub celsius: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureData {
+  // Identifies the sensor across collections, e.g. `hwmon2/temp1` or `thermal_zone0`
+  pub id: String,
+  pub source: TemperatureSource,
+  // hwmon chip name or thermal zone type, e.g. `coretemp`, `x86_pkg_temp`
+  pub chip: String,
+  pub label: String,
+  pub celsius: f64,
+  pub high: Option<f64>,
+  pub critical: Option<f64>,
+  pub trip_points: Vec<TripPoint>,
+}
+
+pub type TemperatureDataCollection = Vec<TemperatureData>;
+
+impl TemperatureData {
+  /// Temperature at which the sensor is considered too hot: the critical point, else the high point.
+  pub fn limit(&self) -> Option<f64> {
+    self.critical.or(self.high).filter(|limit| *limit > 0.0)
+  }
+}
+
+/// Temperature channels of a hwmon chip, e.g. the per core sensors of `coretemp`.
+pub fn hwmon_temperatures(chip: &HwmonChip) -> TemperatureDataCollection {
+  chip
+    .channels("temp")
+    .into_iter()
+    .map(|channel| {
+      let value = |item: &str| channel.values.get(item).map(|value| *value as f64 / MILLIDEGREES);
+      TemperatureData {
+        id: format!("{}/temp{}", chip.id, channel.index),
+        source: TemperatureSource::Hwmon,
+        chip: chip.name.clone(),
+        label: channel.label.clone(),
+        celsius: value("input").unwrap_or_default(),
+        high: value("max"),
+        critical: value("crit").or_else(|| value("emergency")),
+        trip_points: Vec::new(),
+      }
+    })
+    .collect()
+}
+
+/// Builds a thermal zone from its `type`, `temp` and `trip_point_N_{type,temp}` attributes.
+pub fn thermal_zone_temperature(
+  zone: &str,
+  zone_type: &str,
+  millidegrees: i64,
+  trip_points: &[(String, i64)],
+) -> TemperatureData {
+  let trip_points: Vec<TripPoint> = trip_points
+    .iter()
+    // Unused trip points are reported with absurd values on some firmware
+    .filter(|(_, temp)| *temp > 0)
+    .map(|(kind, temp)| TripPoint { kind: kind.clone(), celsius: *temp as f64 / MILLIDEGREES })
+    .collect();
+  let lowest_of = |kinds: &[&str]| {
+    trip_points
+      .iter()
+      .filter(|trip| kinds.contains(&trip.kind.as_str()))
+      .map(|trip| trip.celsius)
+      .min_by(|a, b| a.total_cmp(b))
+  };
+
+  TemperatureData {
+    id: zone.to_string(),
+    source: TemperatureSource::ThermalZone,
+    chip: zone_type.to_string(),
+    label: zone.to_string(),
+    celsius: millidegrees as f64 / MILLIDEGREES,
+    high: lowest_of(&["passive", "hot"]),
+    critical: lowest_of(&["critical"]),
+    trip_points,
+  }
+}
+
+fn read_thermal_zones(roots: &PseudoFsRoots) -> TemperatureDataCollection {
+  list_dir(roots.sys(CLASS_THERMAL))
+    .iter()
+    .filter(|zone| zone.starts_with("thermal_zone"))
+    .filter_map(|zone| {
+      let path = roots.sys(CLASS_THERMAL).join(zone);
+      // Reading fails with ENODATA for zones whose sensor is powered down
+      let millidegrees = read_value::<i64>(path.join("temp"))?;
+      let trip_points: Vec<(String, i64)> = (0..)
+        .map_while(|i| {
+          let kind = read_trimmed(path.join(format!("trip_point_{}_type", i)))?;
+          Some((kind, read_value::<i64>(path.join(format!("trip_point_{}_temp", i))).unwrap_or_default()))
+        })
+        .collect();
+      let zone_type = read_trimmed(path.join("type")).unwrap_or_default();
+      Some(thermal_zone_temperature(zone, &zone_type, millidegrees, &trip_points))
+    })
+    .collect()
+}
+
+fn sysinfo_temperatures(components: &Components) -> TemperatureDataCollection {
+  components
+    .iter()
+    .map(|component| {
+      TemperatureData {
+        id: component.label().to_string(),
+        source: TemperatureSource::Sysinfo,
+        chip: String::new(),
+        label: component.label().to_string(),
+        celsius: component.temperature() as f64,
+        high: Some(component.max() as f64).filter(|max| *max > 0.0),
+        critical: component.critical().map(|critical| critical as f64),
+        trip_points: Vec::new(),
+      }
+    })
+    .collect()
+}
+
+pub fn get_temperature_info(
+  components: &Components,
+  chips: &[HwmonChip],
+  roots: &PseudoFsRoots,
+) -> Result<TemperatureDataCollection> {
+  let mut temperatures: TemperatureDataCollection = chips.iter().flat_map(hwmon_temperatures).collect();
+  // On Linux sysinfo reads the same hwmon attributes, so its components are only used where hwmon is unavailable
+  if temperatures.is_empty() {
+    temperatures.extend(sysinfo_temperatures(components));
+  }
+  temperatures.extend(read_thermal_zones(roots));
+  Ok(temperatures)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_hwmon_temperatures() {
+    let chip = HwmonChip {
+      id: "hwmon1".into(),
+      name: "nvme".into(),
+      attributes: BTreeMap::from([
+        ("temp1_input".into(), "38850".into()),
+        ("temp1_label".into(), "Composite".into()),
+        ("temp1_max".into(), "81850".into()),
+        ("temp1_crit".into(), "84850".into()),
+      ]),
+    };
+
+    assert_eq!(hwmon_temperatures(&chip), vec![TemperatureData {
+      id: "hwmon1/temp1".into(),
+      source: TemperatureSource::Hwmon,
+      chip: "nvme".into(),
+      label: "Composite".into(),
+      celsius: 38.85,
+      high: Some(81.85),
+      critical: Some(84.85),
+      trip_points: Vec::new(),
+    }]);
+  }
+
+  #[test]
+  fn test_thermal_zone_trip_points() {
+    let trips = vec![("passive".to_string(), 95000), ("critical".to_string(), 105000), ("active".to_string(), 0)];
+    let zone = thermal_zone_temperature("thermal_zone1", "x86_pkg_temp", 52000, &trips);
+
+    assert_eq!(zone.celsius, 52.0);
+    assert_eq!(zone.high, Some(95.0));
+    assert_eq!(zone.critical, Some(105.0));
+    assert_eq!(zone.limit(), Some(105.0));
+    assert_eq!(zone.trip_points.len(), 2);
+  }
+}
@@ -0,0 +1,253 @@
+use std::{fs, io::ErrorKind, path::PathBuf, process::Command};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::{
+  pseudo_fs::{list_dir, read_trimmed, PseudoFsRoots, BUS, CLASS_NET},
+  runtime_pm::device_name,
+};
+
+const CLASS_SCSI_HOST: &str = "class/scsi_host";
+const HDA_POWER_SAVE: &str = "module/snd_hda_intel/parameters/power_save";
+const HDA_POWER_SAVE_CONTROLLER: &str = "module/snd_hda_intel/parameters/power_save_controller";
+const NMI_WATCHDOG: &str = "sys/kernel/nmi_watchdog";
+const DIRTY_WRITEBACK_CENTISECS: &str = "sys/vm/dirty_writeback_centisecs";
+
+// Writing back dirty pages every 15 seconds instead of the default 5 lets the disk stay idle for longer
+const SUGGESTED_WRITEBACK_CENTISECS: u64 = 1500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum TunableCategory {
+  #[strum(serialize = "Runtime PM")]
+  RuntimePm,
+  #[strum(serialize = "SATA link")]
+  SataLinkPower,
+  #[strum(serialize = "Audio")]
+  AudioPowerSave,
+  #[strum(serialize = "Watchdog")]
+  NmiWatchdog,
+  #[strum(serialize = "VM")]
+  DirtyWriteback,
+  #[strum(serialize = "Wi-Fi")]
+  WifiPowerSave,
+}
+
+/// Where a tunable's value is read from and written to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TunableTarget {
+  File(PathBuf),
+  // Wi-Fi power save is set through nl80211, which is driven with `iw` as there is no sysfs file for it
+  WifiInterface(String),
+}
+
+impl std::fmt::Display for TunableTarget {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TunableTarget::File(path) => write!(f, "{}", path.display()),
+      TunableTarget::WifiInterface(interface) => write!(f, "iw dev {} power_save", interface),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tunable {
+  pub category: TunableCategory,
+  pub description: String,
+  pub target: TunableTarget,
+  pub current: String,
+  pub suggested: String,
+  pub good: bool,
+}
+
+pub type TunableCollection = Vec<Tunable>;
+
+impl Tunable {
+  fn new(category: TunableCategory, description: String, target: TunableTarget, current: String) -> Self {
+    let (good, suggested) = assess(category, &current);
+    Tunable { category, description, target, current, suggested, good }
+  }
+}
+
+/// Whether a value saves power and the value suggested instead.
+pub fn assess(category: TunableCategory, current: &str) -> (bool, String) {
+  match category {
+    TunableCategory::RuntimePm => (current == "auto", "auto".to_string()),
+    // `min_power` saves slightly more but is known to lose data on some drives
+    TunableCategory::SataLinkPower => {
+      (matches!(current, "med_power_with_dipm" | "min_power"), "med_power_with_dipm".to_string())
+    },
+    // power_save is a timeout in seconds, power_save_controller a Y/N flag
+    TunableCategory::AudioPowerSave => {
+      match current {
+        "Y" | "N" => (current == "Y", "Y".to_string()),
+        _ => (current.parse::<u64>().is_ok_and(|timeout| timeout > 0), "1".to_string()),
+      }
+    },
+    TunableCategory::NmiWatchdog => (current == "0", "0".to_string()),
+    TunableCategory::DirtyWriteback => {
+      (
+        current.parse::<u64>().is_ok_and(|centisecs| centisecs >= SUGGESTED_WRITEBACK_CENTISECS),
+        SUGGESTED_WRITEBACK_CENTISECS.to_string(),
+      )
+    },
+    TunableCategory::WifiPowerSave => (current == "on", "on".to_string()),
+  }
+}
+
+/// Parses the output of `iw dev <interface> get power_save`, e.g. `Power save: on`.
+pub fn parse_iw_power_save(output: &str) -> Option<String> {
+  Some(output.trim().strip_prefix("Power save:")?.trim().to_string())
+}
+
+fn file_tunable(category: TunableCategory, description: String, path: PathBuf) -> Option<Tunable> {
+  let current = read_trimmed(&path)?;
+  Some(Tunable::new(category, description, TunableTarget::File(path), current))
+}
+
+fn runtime_pm_tunables(roots: &PseudoFsRoots) -> Vec<Tunable> {
+  ["pci", "usb"]
+    .iter()
+    .flat_map(|bus| {
+      let devices = roots.sys(BUS).join(bus).join("devices");
+      list_dir(&devices)
+        .into_iter()
+        // USB interfaces (`1-1:1.0`) share the power settings of their device
+        .filter(|name| *bus == "pci" || !name.contains(':'))
+        .filter_map(move |name| {
+          let device = devices.join(&name);
+          file_tunable(
+            TunableCategory::RuntimePm,
+            format!("Runtime PM for {} {} {}", bus.to_uppercase(), name, device_name(bus, &device)),
+            device.join("power/control"),
+          )
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect()
+}
+
+fn sata_tunables(roots: &PseudoFsRoots) -> Vec<Tunable> {
+  let scsi_host = roots.sys(CLASS_SCSI_HOST);
+  list_dir(&scsi_host)
+    .into_iter()
+    .filter_map(|host| {
+      file_tunable(
+        TunableCategory::SataLinkPower,
+        format!("SATA link power management for {}", host),
+        scsi_host.join(&host).join("link_power_management_policy"),
+      )
+    })
+    .collect()
+}
+
+/// Queries the power save state of every wireless interface with `iw`, which runs once per interface. `iw` always
+/// talks to the running kernel, so this describes the live machine whatever the roots are and is only queried on
+/// demand rather than on every collection.
+pub fn get_wifi_tunables() -> TunableCollection {
+  let class_net = PseudoFsRoots::default().sys(CLASS_NET);
+  list_dir(&class_net)
+    .into_iter()
+    .filter(|interface| class_net.join(interface).join("wireless").exists())
+    .filter_map(|interface| {
+      let output = Command::new("iw").args(["dev", &interface, "get", "power_save"]).output().ok()?;
+      let current = parse_iw_power_save(&String::from_utf8_lossy(&output.stdout))?;
+      Some(Tunable::new(
+        TunableCategory::WifiPowerSave,
+        format!("Wi-Fi power save for {}", interface),
+        TunableTarget::WifiInterface(interface),
+        current,
+      ))
+    })
+    .collect()
+}
+
+/// Writes `value` to a tunable. Permission errors are reported with a hint as they are by far the most common. Writes
+/// are refused when reading a capture, they would change a copy or, for Wi-Fi, the live machine.
+pub fn set_tunable(target: &TunableTarget, value: &str, roots: &PseudoFsRoots) -> Result<()> {
+  if !roots.is_live() {
+    return Err(eyre!("Tunables of a capture cannot be changed"));
+  }
+  match target {
+    TunableTarget::File(path) => {
+      fs::write(path, value).map_err(|error| {
+        match error.kind() {
+          ErrorKind::PermissionDenied => eyre!("Permission denied writing {}, run as root", path.display()),
+          _ => eyre!("Failed to write {}: {}", path.display(), error),
+        }
+      })
+    },
+    TunableTarget::WifiInterface(interface) => {
+      let output = Command::new("iw")
+        .args(["dev", interface, "set", "power_save", value])
+        .output()
+        .map_err(|error| eyre!("Failed to run iw: {}", error))?;
+      if output.status.success() {
+        Ok(())
+      } else {
+        Err(eyre!("iw failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+      }
+    },
+  }
+}
+
+pub fn get_tunable_info(roots: &PseudoFsRoots) -> Result<TunableCollection> {
+  let mut tunables = runtime_pm_tunables(roots);
+  tunables.extend(sata_tunables(roots));
+  tunables.extend(file_tunable(
+    TunableCategory::AudioPowerSave,
+    "Audio codec power save timeout".to_string(),
+    roots.sys(HDA_POWER_SAVE),
+  ));
+  tunables.extend(file_tunable(
+    TunableCategory::AudioPowerSave,
+    "Audio controller power save".to_string(),
+    roots.sys(HDA_POWER_SAVE_CONTROLLER),
+  ));
+  tunables.extend(file_tunable(TunableCategory::NmiWatchdog, "NMI watchdog".to_string(), roots.proc(NMI_WATCHDOG)));
+  tunables.extend(file_tunable(
+    TunableCategory::DirtyWriteback,
+    "VM dirty writeback timeout (centisecs)".to_string(),
+    roots.proc(DIRTY_WRITEBACK_CENTISECS),
+  ));
+  Ok(tunables)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_assess() {
+    assert_eq!(assess(TunableCategory::RuntimePm, "on"), (false, "auto".to_string()));
+    assert_eq!(assess(TunableCategory::SataLinkPower, "max_performance"), (false, "med_power_with_dipm".to_string()));
+    assert!(!assess(TunableCategory::AudioPowerSave, "0").0);
+    assert!(assess(TunableCategory::AudioPowerSave, "10").0);
+    assert_eq!(assess(TunableCategory::AudioPowerSave, "N"), (false, "Y".to_string()));
+    assert!(!assess(TunableCategory::NmiWatchdog, "1").0);
+    assert_eq!(assess(TunableCategory::DirtyWriteback, "500"), (false, "1500".to_string()));
+    assert!(assess(TunableCategory::DirtyWriteback, "6000").0);
+  }
+
+  #[test]
+  fn test_set_tunable_refuses_capture() {
+    let fixture = FixtureTree::new("tunables");
+    fixture.write("proc/sys/kernel/nmi_watchdog", "1\n");
+    let roots = fixture.roots();
+    let tunables = get_tunable_info(&roots).unwrap();
+
+    assert_eq!(tunables.len(), 1);
+    assert!(set_tunable(&tunables[0].target, "0", &roots).is_err());
+    assert_eq!(read_trimmed(roots.proc(NMI_WATCHDOG)).as_deref(), Some("1"));
+  }
+
+  #[test]
+  fn test_parse_iw_power_save() {
+    assert_eq!(parse_iw_power_save("Power save: on\n"), Some("on".to_string()));
+    assert_eq!(parse_iw_power_save("command failed: No such device (-19)"), None);
+  }
+}
@@ -0,0 +1,137 @@
+use std::{collections::HashMap, fs};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::PseudoFsRoots,
+};
+
+const VMSTAT: &str = "vmstat";
+
+// Not a vmstat field, derived from `pgfault` which counts major faults as well
+const MINOR_FAULTS: &str = "pgminfault";
+const OOM_KILL: &str = "oom_kill";
+
+/// Counters tracked from `/proc/vmstat` as (name, label) pairs.
+const TRACKED_COUNTERS: [(&str, &str); 8] = [
+  ("pgpgin", "Paged in KiB"),
+  ("pgpgout", "Paged out KiB"),
+  ("pswpin", "Swapped in pages"),
+  ("pswpout", "Swapped out pages"),
+  ("pgmajfault", "Major faults"),
+  (MINOR_FAULTS, "Minor faults"),
+  ("compact_stall", "Compaction stalls"),
+  (OOM_KILL, "OOM kills"),
+];
+
+/// Raw cumulative counters keyed by their `/proc/vmstat` name.
+pub type VmStatCounters = HashMap<String, u64>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VmStatCounter {
+  pub key: String,
+  pub label: String,
+  pub total: u64,
+  // Events per second since the previous collection, 0 on the first collection
+  pub rate: f64,
+}
+
+impl VmStatCounter {
+  /// Whether the counter shows the system running short of memory rather than normal paging activity. Any swapping
+  /// or OOM kill means memory is actively being reclaimed from something that still needs it.
+  pub fn is_thrashing(&self) -> bool {
+    self.rate > 0.0 && matches!(self.key.as_str(), "pswpin" | "pswpout" | OOM_KILL)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct VmStatData {
+  pub counters: Vec<VmStatCounter>,
+  // Processes killed by the OOM killer since the previous collection
+  pub oom_kills: u64,
+}
+
+/// Parses the `name value` lines of `/proc/vmstat`.
+pub fn parse_vmstat(contents: &str) -> VmStatCounters {
+  let mut counters: VmStatCounters = contents
+    .lines()
+    .filter_map(|line| {
+      let (name, value) = line.split_once(' ')?;
+      Some((name.to_string(), value.trim().parse().ok()?))
+    })
+    .collect();
+  if let (Some(faults), Some(major)) = (counters.get("pgfault"), counters.get("pgmajfault")) {
+    counters.insert(MINOR_FAULTS.to_string(), faults.saturating_sub(*major));
+  }
+  counters
+}
+
+pub fn get_vmstat_info(history: &mut CounterHistory<VmStatCounters>, roots: &PseudoFsRoots) -> Result<VmStatData> {
+  let current = parse_vmstat(&fs::read_to_string(roots.proc(VMSTAT))?);
+  let previous = history.record(&current);
+
+  let counters = TRACKED_COUNTERS
+    .iter()
+    // oom_kill was added in 4.13, compact_stall needs CONFIG_COMPACTION
+    .filter_map(|(key, label)| {
+      let total = *current.get(*key)?;
+      let rate = match &previous {
+        Some((previous, elapsed)) => previous.get(*key).map_or(0.0, |p| rate(*p, total, *elapsed)),
+        None => 0.0,
+      };
+      Some(VmStatCounter { key: key.to_string(), label: label.to_string(), total, rate })
+    })
+    .collect();
+  let oom_kills = match (&previous, current.get(OOM_KILL)) {
+    (Some((previous, _)), Some(total)) => total.saturating_sub(previous.get(OOM_KILL).copied().unwrap_or(*total)),
+    _ => 0,
+  };
+
+  Ok(VmStatData { counters, oom_kills })
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::pseudo_fs::FixtureTree;
+
+  #[test]
+  fn test_parse_vmstat() {
+    let counters = parse_vmstat(
+      "nr_free_pages 463749
+pgpgin 1261038
+pswpout 0
+pgfault 28927602
+pgmajfault 1555
+oom_kill 2
+",
+    );
+
+    assert_eq!(counters.get("pgpgin"), Some(&1261038));
+    assert_eq!(counters.get(MINOR_FAULTS), Some(&28926047));
+    assert_eq!(counters.get(OOM_KILL), Some(&2));
+  }
+
+  #[test]
+  fn test_oom_kills_since_previous_collection() {
+    let fixture = FixtureTree::new("vmstat");
+    let mut history = CounterHistory::default();
+    fixture.write("proc/vmstat", "pswpin 10\noom_kill 2\n");
+    let first = get_vmstat_info(&mut history, &fixture.roots()).unwrap();
+    fixture.write("proc/vmstat", "pswpin 10\noom_kill 5\n");
+    let second = get_vmstat_info(&mut history, &fixture.roots()).unwrap();
+
+    // Kills before the monitor started are not reported
+    assert_eq!(first.oom_kills, 0);
+    assert_eq!(second.oom_kills, 3);
+    assert_eq!(second.counters.iter().map(|counter| counter.key.as_str()).collect::<Vec<_>>(), vec![
+      "pswpin", "oom_kill"
+    ]);
+    assert!(second.counters[1].is_thrashing());
+    assert!(!second.counters[0].is_thrashing());
+  }
+}
@@ -0,0 +1,152 @@
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  counters::{rate, CounterHistory},
+  pseudo_fs::{list_dir, read_trimmed, PseudoFsRoots},
+};
+
+/// Scheduler counters of a process, summed over its threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SchedCounters {
+  // Times a thread blocked, each one is followed by a wakeup
+  pub voluntary_switches: u64,
+  // Times a thread was preempted
+  pub involuntary_switches: u64,
+  // Time spent running on a CPU in nanoseconds
+  pub run_time_ns: u64,
+}
+
+impl std::ops::AddAssign for SchedCounters {
+  fn add_assign(&mut self, other: Self) {
+    self.voluntary_switches += other.voluntary_switches;
+    self.involuntary_switches += other.involuntary_switches;
+    self.run_time_ns += other.run_time_ns;
+  }
+}
+
+/// Scheduler counters keyed by PID.
+pub type WakeupCounters = HashMap<u32, SchedCounters>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessWakeups {
+  pub pid: u32,
+  pub name: String,
+  pub wakeups_per_sec: f64,
+  pub preemptions_per_sec: f64,
+  // Share of one CPU used during the interval, above 100% for processes running on several CPUs
+  pub cpu_percent: f64,
+  // Total CPU time used since the process started
+  pub cpu_time: Duration,
+}
+
+pub type ProcessWakeupsCollection = Vec<ProcessWakeups>;
+
+/// Parses the context switch counts out of a `/proc/<pid>/task/<tid>/status` file.
+pub fn parse_context_switches(status: &str) -> (u64, u64) {
+  let count = |key: &str| {
+    status.lines().find_map(|line| line.strip_prefix(key)?.strip_prefix(':')?.trim().parse().ok()).unwrap_or_default()
+  };
+  (count("voluntary_ctxt_switches"), count("nonvoluntary_ctxt_switches"))
+}
+
+/// Parses the run time in nanoseconds, the first of the three fields of a `schedstat` file.
+pub fn parse_schedstat_run_time(schedstat: &str) -> Option<u64> {
+  schedstat.split_whitespace().next()?.parse().ok()
+}
+
+/// Power attributed to a process when `package_power_w` is split in proportion to the CPU used by each process.
+pub fn estimated_power_w(cpu_percent: f64, total_cpu_percent: f64, package_power_w: f64) -> f64 {
+  if total_cpu_percent <= 0.0 {
+    return 0.0;
+  }
+  package_power_w * cpu_percent / total_cpu_percent
+}
+
+fn read_thread_counters(task: &Path) -> Option<SchedCounters> {
+  let (voluntary_switches, involuntary_switches) =
+    parse_context_switches(&fs::read_to_string(task.join("status")).ok()?);
+  Some(SchedCounters {
+    voluntary_switches,
+    involuntary_switches,
+    run_time_ns: fs::read_to_string(task.join("schedstat")).ok().as_deref().and_then(parse_schedstat_run_time)?,
+  })
+}
+
+/// Counters of a process summed over its threads. The counters in `/proc/<pid>/status` and `/proc/<pid>/schedstat`
+/// only cover the main thread.
+fn read_process_counters(process: &Path) -> Option<SchedCounters> {
+  let tasks = process.join("task");
+  let mut total = SchedCounters::default();
+  let mut found = false;
+  for tid in list_dir(&tasks) {
+    if let Some(counters) = read_thread_counters(&tasks.join(tid)) {
+      total += counters;
+      found = true;
+    }
+  }
+  found.then_some(total)
+}
+
+pub fn get_wakeup_info(
+  history: &mut CounterHistory<WakeupCounters>,
+  roots: &PseudoFsRoots,
+) -> Result<ProcessWakeupsCollection> {
+  let pids: Vec<u32> = list_dir(&roots.proc_root).iter().filter_map(|name| name.parse().ok()).collect();
+  let current: WakeupCounters =
+    pids.iter().filter_map(|pid| Some((*pid, read_process_counters(&roots.proc(pid.to_string()))?))).collect();
+  let Some((previous, elapsed_secs)) = history.record(&current) else {
+    return Ok(Vec::new());
+  };
+
+  Ok(
+    current
+      .iter()
+      .map(|(pid, counters)| {
+        // Processes started during the interval count from zero
+        let previous = previous.get(pid).copied().unwrap_or_default();
+        ProcessWakeups {
+          pid: *pid,
+          name: read_trimmed(roots.proc(pid.to_string()).join("comm")).unwrap_or_default(),
+          wakeups_per_sec: rate(previous.voluntary_switches, counters.voluntary_switches, elapsed_secs),
+          preemptions_per_sec: rate(previous.involuntary_switches, counters.involuntary_switches, elapsed_secs),
+          cpu_percent: rate(previous.run_time_ns, counters.run_time_ns, elapsed_secs) / 1e7,
+          cpu_time: Duration::from_nanos(counters.run_time_ns),
+        }
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_context_switches() {
+    let status = "Name:\tfirefox
+State:\tS (sleeping)
+Threads:\t87
+voluntary_ctxt_switches:\t15294
+nonvoluntary_ctxt_switches:\t312
+";
+    assert_eq!(parse_context_switches(status), (15294, 312));
+    assert_eq!(parse_context_switches("Name:\tkthreadd\n"), (0, 0));
+  }
+
+  #[test]
+  fn test_parse_schedstat_run_time() {
+    assert_eq!(parse_schedstat_run_time("2456189432 81234567 1893\n"), Some(2_456_189_432));
+    assert_eq!(parse_schedstat_run_time(""), None);
+  }
+
+  #[test]
+  fn test_estimated_power() {
+    assert_eq!(estimated_power_w(25.0, 100.0, 12.0), 3.0);
+    assert_eq!(estimated_power_w(0.0, 0.0, 12.0), 0.0);
+  }
+}
@@ -17,7 +17,7 @@ use color_eyre::eyre::Result;
 use logging::initialize_logging;
 use sysinfo::System;
 
-use crate::{app::App, utils::initialize_panic_handler};
+use crate::{app::App, data_services::pseudo_fs::PseudoFsRoots, utils::initialize_panic_handler};
 
 async fn tokio_main() -> Result<()> {
   initialize_logging()?;
@@ -25,7 +25,8 @@ async fn tokio_main() -> Result<()> {
   initialize_panic_handler()?;
   let args = Cli::parse();
 
-  let mut app = App::new(args.tick_rate, args.frame_rate)?;
+  let roots = PseudoFsRoots::new(args.proc_root, args.sys_root);
+  let mut app = App::new(args.tick_rate, args.frame_rate, args.read_only, roots)?;
   app.run().await?;
 
   Ok(())
@@ -6,6 +6,7 @@ use serde::{
 };
 use strum::Display;
 
+use super::mode::Mode;
 use crate::data_services::data_collector::DataCollected;
 
 // #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display)]
@@ -21,4 +22,25 @@ pub enum Action {
   Error(String),
   Help,
   DataUpdate(Box<DataCollected>),
+  SwitchMode(Mode),
+  NextMode,
+  PreviousMode,
+  ScrollUp,
+  ScrollDown,
+  Select,
+  // Select the process with the given PID in the process table
+  SelectProcess(u32),
+  // Sort the current view by its next column
+  CycleSort,
+  ReverseSort,
+  // Switch the current view to its alternate presentation
+  ToggleView,
+  // Collapse or expand the selected node of a tree view
+  ToggleCollapse,
+  // Only show the processes of the cgroup with the given path in the process table
+  FilterCgroup(String),
+  // Show all processes in the process table again
+  ClearFilter,
+  // Group the process table by systemd unit or container
+  ToggleGrouping,
 }
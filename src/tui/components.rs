@@ -6,13 +6,33 @@ use tokio::sync::mpsc::UnboundedSender;
 use super::ui::{Event, Frame};
 use crate::{configuration::app_configuration::Config, tui::action::Action};
 
+pub mod battery;
+pub mod block_devices;
+pub mod cgroups;
+pub mod connections;
 pub mod cpu;
+pub mod device_power;
+pub mod disk_io;
 pub mod disks;
 pub mod fps;
+pub mod frequency;
+pub mod header;
 pub mod home;
+pub mod idle_stats;
+pub mod interrupts;
+pub mod limits;
 pub mod memory;
+pub mod net_stats;
 pub mod network;
+pub mod overview;
+pub mod pressure;
 pub mod process_table;
+pub mod raid;
+pub mod rapl;
+pub mod sensors;
+pub mod temperatures;
+pub mod tunables;
+pub mod vmstat;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 /// Implementors of this trait can be registered with the main application loop and will be able to receive events,
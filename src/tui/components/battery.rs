@@ -0,0 +1,275 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::battery::{BatteryData, BatteryDataCollection, BatteryStatus},
+  tui::{action::Action, layout::PowerLayout, mode::Mode, ui::Frame, widgets::usage::threshold_style},
+  utils::format_duration,
+};
+
+// Weight of the newest reading in the smoothed charge/discharge rate. Reported power jumps with every burst of load,
+// so a low weight keeps the time estimate from jumping around.
+const SMOOTHING: f64 = 0.1;
+
+/// Smoothed charge or discharge rate of a battery. Reset whenever the battery switches between charging and
+/// discharging.
+#[derive(Debug, Clone, PartialEq)]
+struct RateEstimate {
+  status: BatteryStatus,
+  smoothed_w: Option<f64>,
+  // Energy at the previous collection, for batteries that do not report their power draw
+  last_energy: Option<(Instant, f64)>,
+}
+
+impl RateEstimate {
+  fn new(status: BatteryStatus) -> Self {
+    Self { status, smoothed_w: None, last_energy: None }
+  }
+
+  fn update(&mut self, battery: &BatteryData, now: Instant) {
+    if battery.status != self.status {
+      *self = RateEstimate::new(battery.status);
+    }
+    let measured = battery.power_w.filter(|power| *power > 0.0).or_else(|| {
+      let (then, energy_then) = self.last_energy?;
+      let hours = now.duration_since(then).as_secs_f64() / 3600.0;
+      let energy_now = battery.energy_now_wh?;
+      // Energy is only updated by the firmware every few seconds, identical readings say nothing about the rate
+      (hours > 0.0 && energy_now != energy_then).then(|| (energy_now - energy_then).abs() / hours)
+    });
+    if let Some(energy_now) = battery.energy_now_wh {
+      if self.last_energy.is_none_or(|(_, energy)| energy != energy_now) {
+        self.last_energy = Some((now, energy_now));
+      }
+    }
+    if let Some(measured) = measured {
+      self.smoothed_w = Some(match self.smoothed_w {
+        Some(smoothed) => smoothed + SMOOTHING * (measured - smoothed),
+        None => measured,
+      });
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatteryComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: BatteryDataCollection,
+  // Charge percentage per battery, oldest first
+  history: HashMap<String, VecDeque<u64>>,
+  estimates: HashMap<String, RateEstimate>,
+  mode: Mode,
+}
+
+impl Default for BatteryComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl BatteryComponent {
+  // At the default collection interval this covers the last ten minutes
+  pub const WINDOW_SIZE: usize = 600;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      history: HashMap::new(),
+      estimates: HashMap::new(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: BatteryDataCollection) {
+    let now = Instant::now();
+    for battery in &new_data {
+      let history =
+        self.history.entry(battery.name.clone()).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back(battery.capacity_percent.unwrap_or_default().round() as u64);
+      self
+        .estimates
+        .entry(battery.name.clone())
+        .or_insert_with(|| RateEstimate::new(battery.status))
+        .update(battery, now);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn summary(&self, battery: &BatteryData) -> Vec<Line<'static>> {
+    let smoothed = self.estimates.get(&battery.name).and_then(|estimate| estimate.smoothed_w);
+    let estimate = match (battery.status, smoothed.and_then(|power| battery.time_remaining(power))) {
+      (BatteryStatus::Discharging, Some(remaining)) => format!("{} until empty", format_duration(remaining)),
+      (BatteryStatus::Charging, Some(remaining)) => format!("{} until full", format_duration(remaining)),
+      (BatteryStatus::Discharging | BatteryStatus::Charging, None) => "estimating...".to_string(),
+      _ => String::new(),
+    };
+    let wh = |energy: Option<f64>| energy.map_or_else(|| "-".to_string(), |energy| format!("{:.1} Wh", energy));
+
+    let mut lines = vec![
+      Line::from(vec![
+        Span::from(battery.status.to_string()).bold(),
+        Span::from(format!(
+          "  {}  ",
+          battery.power_w.map_or_else(|| "-".to_string(), |power| format!("{:.2} W", power))
+        )),
+        Span::from(estimate).cyan(),
+      ]),
+      Line::from(format!(
+        "Energy {} / {} (design {})",
+        wh(battery.energy_now_wh),
+        wh(battery.energy_full_wh),
+        wh(battery.energy_full_design_wh)
+      )),
+    ];
+    let mut wear = Vec::new();
+    if let Some(health) = battery.health_percent() {
+      wear.push(Span::from("Health "));
+      wear.push(Span::styled(format!("{:.0}%", health), threshold_style(100.0 - health, 20.0, 40.0)));
+    }
+    if let Some(cycles) = battery.cycle_count {
+      wear.push(Span::from(format!("  Cycles {}", cycles)));
+    }
+    if let Some(health) = &battery.health {
+      wear.push(Span::from(format!("  ({})", health)));
+    }
+    lines.push(Line::from(wear));
+    lines
+  }
+
+  fn draw_battery(&mut self, frame: &mut Frame<'_>, area: Rect, battery: &BatteryData) {
+    let title = match (&battery.manufacturer, &battery.model) {
+      (Some(manufacturer), Some(model)) => format!("{} ({} {})", battery.name, manufacturer, model),
+      (None, Some(model)) => format!("{} ({})", battery.name, model),
+      _ => battery.name.clone(),
+    };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1), Constraint::Length(3), Constraint::Min(0)])
+      .split(inner);
+
+    let capacity = battery.capacity_percent.unwrap_or_default();
+    // Low charge is the bad end here, so the usage thresholds are applied to the charge used
+    let style = threshold_style(100.0 - capacity, 70.0, 85.0);
+    frame.render_widget(
+      Gauge::default().ratio((capacity / 100.0).clamp(0.0, 1.0)).label(format!("{:.0}%", capacity)).gauge_style(style),
+      rows[0],
+    );
+    frame.render_widget(Paragraph::new(self.summary(battery)), rows[1]);
+    if let Some(history) = self.history.get_mut(&battery.name) {
+      // Show the most recent readings that fit the width
+      let data = history.make_contiguous();
+      let start = data.len().saturating_sub(rows[2].width as usize);
+      frame.render_widget(
+        Sparkline::default().block(Block::new().title("Charge history")).data(&data[start..]).max(100).style(style),
+        rows[2],
+      );
+    }
+  }
+}
+
+impl Component for BatteryComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.batteries {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Power {
+      return Ok(());
+    }
+
+    let battery_area = PowerLayout::new(area).battery;
+    if self.collected_data.is_empty() {
+      frame.render_widget(
+        Paragraph::new("No battery found").dark_gray().block(Block::bordered().title("Battery")),
+        battery_area,
+      );
+      return Ok(());
+    }
+
+    let areas = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Ratio(1, self.collected_data.len() as u32); self.collected_data.len()])
+      .split(battery_area);
+    for (battery, battery_area) in self.collected_data.clone().iter().zip(areas.iter()) {
+      self.draw_battery(frame, *battery_area, battery);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn battery(status: BatteryStatus, power_w: Option<f64>, energy_now_wh: f64) -> BatteryData {
+    BatteryData {
+      name: "BAT0".into(),
+      model: None,
+      manufacturer: None,
+      status,
+      capacity_percent: None,
+      energy_now_wh: Some(energy_now_wh),
+      energy_full_wh: Some(50.0),
+      energy_full_design_wh: None,
+      power_w,
+      cycle_count: None,
+      health: None,
+    }
+  }
+
+  #[test]
+  fn test_rate_is_smoothed_and_reset_on_status_change() {
+    let now = Instant::now();
+    let mut estimate = RateEstimate::new(BatteryStatus::Discharging);
+    estimate.update(&battery(BatteryStatus::Discharging, Some(10.0), 30.0), now);
+    estimate.update(&battery(BatteryStatus::Discharging, Some(20.0), 30.0), now);
+    assert_eq!(estimate.smoothed_w, Some(11.0));
+
+    estimate.update(&battery(BatteryStatus::Charging, Some(30.0), 30.0), now);
+    assert_eq!(estimate.smoothed_w, Some(30.0));
+  }
+
+  #[test]
+  fn test_rate_from_energy_without_power() {
+    let start = Instant::now();
+    let mut estimate = RateEstimate::new(BatteryStatus::Discharging);
+    estimate.update(&battery(BatteryStatus::Discharging, None, 30.0), start);
+    assert_eq!(estimate.smoothed_w, None);
+
+    estimate.update(&battery(BatteryStatus::Discharging, None, 29.0), start + Duration::from_secs(360));
+    assert_eq!(estimate.smoothed_w.map(|power| power.round()), Some(10.0));
+  }
+}
@@ -0,0 +1,208 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::{
+    block_devices::{BlockDevice, BlockDeviceCollection},
+    disks::DiskDataCollection,
+  },
+  tui::{
+    action::Action,
+    layout::BlockDevicesLayout,
+    mode::Mode,
+    ui::Frame,
+    widgets::{table_selection::TableSelection, usage::usage_style},
+  },
+  utils::format_bytes,
+};
+
+/// A block device flattened into a table row, with the tree guides (`├─`, `└─`) leading up to it.
+#[derive(Debug, Clone, PartialEq)]
+struct TreeRow {
+  prefix: String,
+  device: BlockDevice,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockDeviceTree {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: BlockDeviceCollection,
+  // File systems from the disk table, used to show the usage of mounted devices
+  disks: DiskDataCollection,
+  rows: Vec<TreeRow>,
+  mode: Mode,
+  selection: TableSelection,
+}
+
+impl Default for BlockDeviceTree {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Appends `devices` and their children in depth first order. `guides` holds the lines drawn for the ancestors.
+fn flatten_tree(devices: &[BlockDevice], guides: &str, rows: &mut Vec<TreeRow>) {
+  for (i, device) in devices.iter().enumerate() {
+    let is_last = i + 1 == devices.len();
+    let branch = if is_last { "└─" } else { "├─" };
+    let mut own_device = device.clone();
+    own_device.children = Vec::new();
+    rows.push(TreeRow { prefix: format!("{}{}", guides, branch), device: own_device });
+    flatten_tree(&device.children, &format!("{}{}", guides, if is_last { "  " } else { "│ " }), rows);
+  }
+}
+
+impl BlockDeviceTree {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      disks: [].to_vec(),
+      rows: Vec::new(),
+      mode: Mode::Home,
+      selection: TableSelection::default(),
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: BlockDeviceCollection) {
+    self.collected_data = new_data;
+    self.rows.clear();
+    for device in &self.collected_data {
+      // Top level devices are drawn without guides, like lsblk does
+      self.rows.push(TreeRow { prefix: String::new(), device: BlockDevice { children: Vec::new(), ..device.clone() } });
+      flatten_tree(&device.children, "", &mut self.rows);
+    }
+    self.selection.clamp(self.rows.len());
+  }
+
+  fn headers() -> Vec<&'static str> {
+    vec!["Name", "Type", "Size", "Rota", "RO", "RM", "Model", "Mount Points", "Use%"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Min(28),
+      Constraint::Length(5),
+      Constraint::Length(10),
+      Constraint::Length(4),
+      Constraint::Length(3),
+      Constraint::Length(3),
+      Constraint::Length(24),
+      Constraint::Min(20),
+      Constraint::Length(5),
+    ]
+  }
+
+  fn row(&self, tree_row: &TreeRow) -> Row<'static> {
+    let device = &tree_row.device;
+    let name = match &device.label {
+      Some(label) => format!("{}{} ({})", tree_row.prefix, device.name, label),
+      None => format!("{}{}", tree_row.prefix, device.name),
+    };
+    let flag = |value: bool| if value { "1" } else { "0" };
+    let usage = self
+      .disks
+      .iter()
+      .find(|disk| device.mount_points.contains(&disk.mount_path))
+      .map(|disk| disk.used_percentage())
+      .map_or_else(
+        || Cell::from(""),
+        |percentage| Cell::from(format!("{:>3.0}%", percentage)).style(usage_style(percentage)),
+      );
+
+    Row::new(vec![
+      Cell::from(name),
+      Cell::from(device.kind.to_string()),
+      Cell::from(format_bytes(device.size_bytes)),
+      Cell::from(device.rotational.map_or("-", flag)),
+      Cell::from(flag(device.read_only)),
+      Cell::from(flag(device.removable)),
+      Cell::from(device.model.clone().unwrap_or_default()),
+      Cell::from(device.mount_points.join(", ")),
+      usage,
+    ])
+  }
+}
+
+impl Component for BlockDeviceTree {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        if let Some(disks) = data.disk {
+          self.disks = disks;
+        }
+        match data.block_devices {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::BlockDevices => self.selection.next(self.rows.len()),
+      Action::ScrollUp if self.mode == Mode::BlockDevices => self.selection.previous(self.rows.len()),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::BlockDevices {
+      return Ok(());
+    }
+
+    let rows: Vec<Row> = self.rows.iter().map(|row| self.row(row)).collect();
+    let header = Row::new(Self::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
+    let table = Table::new(rows, Self::column_widths())
+      .block(Block::bordered().title("Block Devices"))
+      .column_spacing(2)
+      .style(Style::default().white())
+      .header(header)
+      .highlight_style(Style::default().reversed())
+      .highlight_symbol(">>");
+
+    frame.render_stateful_widget(table, BlockDevicesLayout::new(area).tree, self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::block_devices::BlockDeviceKind;
+
+  fn device(name: &str, children: Vec<BlockDevice>) -> BlockDevice {
+    BlockDevice {
+      name: name.to_string(),
+      kind: BlockDeviceKind::Disk,
+      size_bytes: 0,
+      model: None,
+      rotational: None,
+      removable: false,
+      read_only: false,
+      label: None,
+      mount_points: Vec::new(),
+      children,
+    }
+  }
+
+  #[test]
+  fn test_tree_guides() {
+    let mut tree = BlockDeviceTree::new();
+    tree.update_data_stats(vec![device("sda", vec![
+      device("sda1", vec![]),
+      device("sda2", vec![device("dm-0", vec![device("dm-1", vec![])])]),
+    ])]);
+
+    let prefixes: Vec<String> = tree.rows.iter().map(|row| format!("{}{}", row.prefix, row.device.name)).collect();
+    assert_eq!(prefixes, vec!["sda", "├─sda1", "└─sda2", "  └─dm-0", "    └─dm-1"]);
+  }
+}
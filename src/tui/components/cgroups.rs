@@ -0,0 +1,332 @@
+use std::{collections::HashSet, time::Instant};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{pressure::pressure_style, Component};
+use crate::{
+  data_services::{
+    cgroups::{CgroupData, CgroupNode},
+    pressure::PressureResource,
+  },
+  tui::{
+    action::Action,
+    mode::Mode,
+    ui::Frame,
+    widgets::{table_selection::TableSelection, usage::usage_style},
+  },
+  utils::format_bytes,
+};
+
+fn optional_cell(value: Option<String>) -> Cell<'static> {
+  match value {
+    Some(value) => Cell::from(value),
+    None => Cell::from("-").dark_gray(),
+  }
+}
+
+fn pressure_cell(node: &CgroupNode, resource: PressureResource) -> Cell<'static> {
+  let avg10 = node.pressure.iter().find(|(other, _)| *other == resource).map(|(_, avg10)| *avg10);
+  match avg10 {
+    Some(avg10) => Cell::from(format!("{:>6.2}", avg10)).style(pressure_style(avg10)),
+    None => Cell::from(format!("{:>6}", "-")).dark_gray(),
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct CgroupTree {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  command_tx: Option<UnboundedSender<Action>>,
+  collected_data: CgroupData,
+  // Paths of the collapsed cgroups, their descendants are hidden
+  collapsed: HashSet<String>,
+  // Indices into the collected nodes of the rows currently shown
+  visible: Vec<usize>,
+  selection: TableSelection,
+  // Follows the highlighted cgroup while scopes come and go between refreshes
+  selected_path: Option<String>,
+  mode: Mode,
+}
+
+impl Default for CgroupTree {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CgroupTree {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      command_tx: None,
+      collected_data: CgroupData::default(),
+      collapsed: HashSet::new(),
+      visible: Vec::new(),
+      selection: TableSelection::default(),
+      selected_path: None,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: CgroupData) {
+    self.collected_data = new_data;
+    self.update_visible();
+  }
+
+  /// Recomputes the shown rows, skipping everything below a collapsed cgroup.
+  fn update_visible(&mut self) {
+    self.visible.clear();
+    let mut hidden_below: Option<usize> = None;
+    for (index, node) in self.collected_data.nodes.iter().enumerate() {
+      match hidden_below {
+        Some(depth) if node.depth > depth => continue,
+        _ => hidden_below = None,
+      }
+      self.visible.push(index);
+      if node.has_children && self.collapsed.contains(&node.path) {
+        hidden_below = Some(node.depth);
+      }
+    }
+    self.sync_selection();
+  }
+
+  fn visible_node(&self, row: usize) -> Option<&CgroupNode> {
+    self.visible.get(row).and_then(|i| self.collected_data.nodes.get(*i))
+  }
+
+  /// Keeps the selection on the same cgroup after the tree changed, or clamps it if that cgroup is gone or hidden.
+  fn sync_selection(&mut self) {
+    let row = self.selected_path.as_ref().and_then(|path| {
+      (0..self.visible.len()).find(|row| self.visible_node(*row).is_some_and(|node| node.path == *path))
+    });
+    match row {
+      Some(row) => self.selection.select(Some(row)),
+      None => {
+        self.selection.clamp(self.visible.len());
+        self.selected_path =
+          self.selection.selected().and_then(|row| self.visible_node(row)).map(|node| node.path.clone());
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if down {
+      self.selection.next(self.visible.len());
+    } else {
+      self.selection.previous(self.visible.len());
+    }
+    self.selected_path = self.selection.selected().and_then(|row| self.visible_node(row)).map(|node| node.path.clone());
+  }
+
+  fn selected_node(&self) -> Option<&CgroupNode> {
+    let path = self.selected_path.as_ref()?;
+    self.visible.iter().filter_map(|i| self.collected_data.nodes.get(*i)).find(|node| node.path == *path)
+  }
+
+  fn toggle_collapse(&mut self) {
+    let Some(node) = self.selected_node().filter(|node| node.has_children) else {
+      return;
+    };
+    let path = node.path.clone();
+    if !self.collapsed.remove(&path) {
+      self.collapsed.insert(path);
+    }
+    self.update_visible();
+  }
+
+  /// Filters the process table to the selected cgroup and jumps to it.
+  fn filter_processes(&mut self) -> Result<()> {
+    let Some(node) = self.selected_node() else {
+      return Ok(());
+    };
+    if let Some(tx) = &self.command_tx {
+      tx.send(Action::FilterCgroup(node.path.clone()))?;
+      tx.send(Action::SwitchMode(Mode::Home))?;
+    }
+    Ok(())
+  }
+
+  fn row(&self, node: &CgroupNode) -> Row<'static> {
+    let marker = match (node.has_children, self.collapsed.contains(&node.path)) {
+      (false, _) => "  ",
+      (true, true) => "▸ ",
+      (true, false) => "▾ ",
+    };
+    let name = format!("{}{}{}", "  ".repeat(node.depth), marker, node.name);
+    let memory = node.memory_current.map(|current| {
+      match node.memory_max {
+        Some(max) => format!("{} / {}", format_bytes(current), format_bytes(max)),
+        None => format_bytes(current),
+      }
+    });
+    let memory_style = match (node.memory_current, node.memory_max) {
+      (Some(current), Some(max)) if max > 0 => usage_style(current as f64 / max as f64 * 100.0),
+      _ => Style::default(),
+    };
+    let io_rate = |rate: Option<f64>| rate.map(|rate| format!("{}/s", format_bytes(rate as u64)));
+
+    Row::new(vec![
+      Cell::from(name),
+      optional_cell(node.pids_current.map(|pids| format!("{:>5}", pids))),
+      optional_cell(node.cpu_percent.map(|percent| format!("{:>6.1}", percent))),
+      optional_cell(memory).style(memory_style),
+      optional_cell(io_rate(node.read_rate)),
+      optional_cell(io_rate(node.write_rate)),
+      pressure_cell(node, PressureResource::Cpu),
+      pressure_cell(node, PressureResource::Memory),
+      pressure_cell(node, PressureResource::Io),
+    ])
+  }
+
+  fn headers() -> Vec<String> {
+    vec![
+      "Cgroup".to_string(),
+      format!("{:>5}", "Tasks"),
+      format!("{:>6}", "CPU%"),
+      "Memory / Max".to_string(),
+      "Read".to_string(),
+      "Write".to_string(),
+      // Pressure stall averages over 10 seconds
+      format!("{:>6}", "cpu10"),
+      format!("{:>6}", "mem10"),
+      format!("{:>6}", "io10"),
+    ]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Min(40),
+      Constraint::Length(5),
+      Constraint::Length(6),
+      Constraint::Length(23),
+      Constraint::Length(12),
+      Constraint::Length(12),
+      Constraint::Length(6),
+      Constraint::Length(6),
+      Constraint::Length(6),
+    ]
+  }
+}
+
+impl Component for CgroupTree {
+  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+    self.command_tx = Some(tx);
+    Ok(())
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.cgroups {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Cgroups => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Cgroups => self.scroll(false),
+      Action::ToggleCollapse if self.mode == Mode::Cgroups => self.toggle_collapse(),
+      Action::Select if self.mode == Mode::Cgroups => self.filter_processes()?,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Cgroups {
+      return Ok(());
+    }
+
+    let nodes = &self.collected_data.nodes;
+    let title = if nodes.is_empty() {
+      "Control Groups - no cgroup v2 hierarchy found".to_string()
+    } else {
+      let plural = if nodes.len() == 1 { "" } else { "s" };
+      format!("Control Groups - {} cgroup{} (Space collapses, Enter filters processes)", nodes.len(), plural)
+    };
+    let rows = self.visible.iter().filter_map(|index| nodes.get(*index)).map(|node| self.row(node)).collect_vec();
+    let table = Table::new(rows, Self::column_widths())
+      .block(Block::bordered().title(title))
+      .column_spacing(1)
+      .header(Row::new(Self::headers()).style(Style::default().bold().underlined()))
+      .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, area, self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn node(path: &str, depth: usize, has_children: bool) -> CgroupNode {
+    CgroupNode {
+      path: path.to_string(),
+      name: path.rsplit('/').next().unwrap().to_string(),
+      depth,
+      has_children,
+      cpu_percent: None,
+      memory_current: None,
+      memory_max: None,
+      read_rate: None,
+      write_rate: None,
+      pids_current: None,
+      pressure: Vec::new(),
+      pids: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_collapse_hides_descendants() {
+    let mut tree = CgroupTree::new();
+    tree.update_data_stats(CgroupData {
+      nodes: vec![
+        node("/", 0, true),
+        node("system.slice", 1, true),
+        node("system.slice/nginx.service", 2, false),
+        node("user.slice", 1, false),
+      ],
+    });
+    assert_eq!(tree.visible, vec![0, 1, 2, 3]);
+
+    tree.scroll(true);
+    tree.scroll(true);
+    tree.toggle_collapse();
+    assert_eq!(tree.visible, vec![0, 1, 3]);
+    tree.toggle_collapse();
+    assert_eq!(tree.visible, vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn test_selection_follows_cgroup() {
+    let mut tree = CgroupTree::new();
+    tree.update_data_stats(CgroupData {
+      nodes: vec![node("/", 0, true), node("system.slice", 1, false), node("user.slice", 1, false)],
+    });
+    tree.scroll(true);
+    tree.scroll(true);
+    tree.scroll(true);
+    assert_eq!(tree.selected_node().map(|node| node.path.as_str()), Some("user.slice"));
+
+    // A transient scope shows up above the selected cgroup
+    tree.update_data_stats(CgroupData {
+      nodes: vec![
+        node("/", 0, true),
+        node("init.scope", 1, false),
+        node("system.slice", 1, false),
+        node("user.slice", 1, false),
+      ],
+    });
+    assert_eq!(tree.selection.selected(), Some(3));
+    assert_eq!(tree.selected_node().map(|node| node.path.as_str()), Some("user.slice"));
+  }
+}
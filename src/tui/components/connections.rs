@@ -0,0 +1,239 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Component;
+use crate::{
+  data_services::sockets::{tcp_state_counts, SocketData, SocketDataCollection, SocketProtocol, SocketState},
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
+};
+
+#[derive(Debug, Clone)]
+pub struct ConnectionTable {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  command_tx: Option<UnboundedSender<Action>>,
+  collected_data: SocketDataCollection,
+  mode: Mode,
+  selection: TableSelection,
+  // Follows the highlighted socket while the list is re-sorted on every refresh
+  selected_socket: Option<SocketKey>,
+}
+
+/// Identifies a socket across refreshes. The inode alone is not enough, sockets in `TIME_WAIT` all have inode 0.
+type SocketKey = (SocketProtocol, String, String, u64);
+
+fn socket_key(socket: &SocketData) -> SocketKey {
+  (socket.protocol, socket.local_address.clone(), socket.remote_address.clone(), socket.inode)
+}
+
+impl From<&SocketData> for Row<'static> {
+  fn from(val: &SocketData) -> Self {
+    let state_style = match val.state {
+      SocketState::Listen => Style::default().green(),
+      SocketState::Established => Style::default().cyan(),
+      SocketState::TimeWait | SocketState::CloseWait | SocketState::FinWait1 | SocketState::FinWait2 => {
+        Style::default().yellow()
+      },
+      _ => Style::default(),
+    };
+
+    Row::new(vec![
+      Cell::from(val.protocol.to_string()),
+      Cell::from(val.state.to_string()).style(state_style),
+      Cell::from(val.local_address.clone()),
+      Cell::from(val.remote_address.clone()),
+      Cell::from(val.pid.map_or_else(|| "-".to_string(), |pid| pid.to_string())),
+      Cell::from(val.process_name.clone().unwrap_or_else(|| "-".to_string())),
+    ])
+  }
+}
+
+impl SocketData {
+  fn headers() -> Vec<&'static str> {
+    vec!["Proto", "State", "Local Address", "Peer Address", "PID", "Process"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(5),
+      Constraint::Length(12),
+      Constraint::Min(24),
+      Constraint::Min(24),
+      Constraint::Length(8),
+      Constraint::Length(20),
+    ]
+  }
+}
+
+impl Default for ConnectionTable {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ConnectionTable {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      command_tx: None,
+      collected_data: [].to_vec(),
+      mode: Mode::Home,
+      selection: TableSelection::default(),
+      selected_socket: None,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: SocketDataCollection) {
+    self.collected_data = new_data;
+    self.collected_data.sort_by(|a, b| {
+      (a.protocol, a.state, &a.local_address, &a.remote_address).cmp(&(
+        b.protocol,
+        b.state,
+        &b.local_address,
+        &b.remote_address,
+      ))
+    });
+    self.sync_selection();
+  }
+
+  /// Keeps the selection on the same socket after the list changed, or clamps it if that socket is gone.
+  fn sync_selection(&mut self) {
+    let index = self
+      .selected_socket
+      .as_ref()
+      .and_then(|key| self.collected_data.iter().position(|socket| socket_key(socket) == *key));
+    match index {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.collected_data.len());
+        self.selected_socket = self.selection.selected().map(|i| socket_key(&self.collected_data[i]));
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if down {
+      self.selection.next(self.collected_data.len());
+    } else {
+      self.selection.previous(self.collected_data.len());
+    }
+    self.selected_socket = self.selection.selected().map(|i| socket_key(&self.collected_data[i]));
+  }
+
+  /// Jumps to the process owning the selected socket in the process table.
+  fn select_owning_process(&mut self) -> Result<()> {
+    let Some(pid) = self.selection.selected().and_then(|i| self.collected_data.get(i)).and_then(|s| s.pid) else {
+      return Ok(());
+    };
+    if let Some(tx) = &self.command_tx {
+      tx.send(Action::SelectProcess(pid))?;
+      tx.send(Action::SwitchMode(Mode::Home))?;
+    }
+    Ok(())
+  }
+
+  fn tcp_summary(&self) -> Line<'static> {
+    let counts = tcp_state_counts(&self.collected_data);
+    let spans = counts
+      .iter()
+      .flat_map(|(state, count)| vec![Span::from(format!("{} ", state)).bold(), Span::from(format!("{}   ", count))])
+      .collect_vec();
+    Line::from(spans)
+  }
+}
+
+impl Component for ConnectionTable {
+  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+    self.command_tx = Some(tx);
+    Ok(())
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.sockets {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Connections => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Connections => self.scroll(false),
+      Action::Select if self.mode == Mode::Connections => self.select_owning_process()?,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Connections {
+      return Ok(());
+    }
+
+    let rects = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+      .split(area);
+
+    let summary = Paragraph::new(self.tcp_summary()).block(Block::bordered().title("TCP States"));
+    frame.render_widget(summary, rects[0]);
+
+    let rows: Vec<Row> = self.collected_data.iter().map(Into::into).collect();
+    let header = Row::new(SocketData::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
+    let table = Table::new(rows, SocketData::column_widths())
+      .block(Block::bordered().title(format!("Connections ({}) - <Enter> to show process", self.collected_data.len())))
+      .column_spacing(2)
+      .style(Style::default().white())
+      .header(header)
+      .highlight_style(Style::default().reversed())
+      .highlight_symbol(">>");
+
+    frame.render_stateful_widget(table, rects[1], self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn socket(local_address: &str, inode: u64, pid: u32) -> SocketData {
+    SocketData {
+      protocol: SocketProtocol::Tcp,
+      state: SocketState::Listen,
+      local_address: local_address.to_string(),
+      remote_address: "0.0.0.0:0".to_string(),
+      inode,
+      pid: Some(pid),
+      process_name: None,
+    }
+  }
+
+  #[test]
+  fn test_selection_follows_socket() {
+    let mut table = ConnectionTable::new();
+    table.update_data_stats(vec![socket("0.0.0.0:22", 100, 1), socket("0.0.0.0:80", 200, 2)]);
+    table.scroll(true);
+    table.scroll(true);
+    assert_eq!(table.selection.selected(), Some(1));
+
+    // A new socket sorts in front of the selected one
+    table.update_data_stats(vec![
+      socket("0.0.0.0:22", 100, 1),
+      socket("0.0.0.0:80", 200, 2),
+      socket("0.0.0.0:5", 300, 3),
+    ]);
+    assert_eq!(table.selection.selected(), Some(2));
+    assert_eq!(table.collected_data[2].pid, Some(2));
+  }
+}
@@ -17,7 +17,7 @@ use sysinfo::System;
 use super::Component;
 use crate::{
   data_services::cpu::{get_cpu_info, CpuData, CpuDataCollection},
-  tui::action::Action,
+  tui::{action::Action, layout::HomeLayout, mode::Mode},
 };
 
 const MAX_DATA_POINTS: usize = 50;
@@ -46,6 +46,7 @@ pub struct Cpu {
   collected_data: CpuDataCollection,
   cpu_stats: CpuStats,
   graph_type: CpuGraphType,
+  mode: Mode,
 }
 
 impl Default for Cpu {
@@ -62,6 +63,7 @@ impl Cpu {
       collected_data: [].to_vec(),
       cpu_stats: CpuStats { max_usage: 0.0, cpu_groups: HashMap::new(), min_x: 0.0, max_x: 0.0, points: 0 },
       graph_type: CpuGraphType::BarChart,
+      mode: Mode::Home,
     }
   }
 
@@ -166,34 +168,27 @@ impl Cpu {
 
 impl Component for Cpu {
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    if let Action::DataUpdate(data) = action {
-      match data.cpu {
-        Some(d) => self.update_data_stats(d),
-        None => {
-          log::debug!("Received Action with no data.")
-        },
-      }
+    match action {
+      Action::DataUpdate(data) => {
+        match data.cpu {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
     }
     Ok(None)
   }
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
-    let rects = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints(vec![
-        Constraint::Percentage(50), // Top row spans whole width
-        Constraint::Percentage(50), // Bottom row split 50/50
-      ])
-      .split(area);
-    let top_row = rects[0];
-    let top_row_rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(vec![
-        Constraint::Percentage(50), // Top row spans whole width
-        Constraint::Percentage(50), // Bottom row split 50/50
-      ])
-      .split(top_row);
-    let cpu_rect = top_row_rects[0];
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let cpu_rect = HomeLayout::new(area).cpu;
     // TODO: CPU Ordering on both graphs
     // TODO: Handle Data Cleaning
     // TODO: Each of these charts could be moved into its own "Widget" module as an abstraction over ratatui so it can be easy to implement new charts
@@ -0,0 +1,218 @@
+use std::{cmp::Reverse, time::Instant};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::runtime_pm::{PmDevice, PmDeviceCollection, RuntimeStatus},
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
+  utils::format_duration,
+};
+
+impl From<&PmDevice> for Row<'static> {
+  fn from(val: &PmDevice) -> Self {
+    let status_style = match val.runtime_status {
+      RuntimeStatus::Active => Style::default().yellow(),
+      RuntimeStatus::Suspended => Style::default().green(),
+      RuntimeStatus::Error => Style::default().red().bold(),
+      _ => Style::default().dark_gray(),
+    };
+    let control = val.control.clone().unwrap_or_else(|| "-".to_string());
+    // A device that is kept powered can block the package from reaching its deepest idle states
+    let control_style = if control == "on" { Style::default().red() } else { Style::default() };
+    let autosuspend = match val.autosuspend_delay_ms {
+      Some(delay) if delay < 0 => "never".to_string(),
+      Some(delay) => format!("{} ms", delay),
+      None => "-".to_string(),
+    };
+
+    Row::new(vec![
+      Cell::from(val.bus.to_uppercase()),
+      Cell::from(val.id.clone()),
+      Cell::from(val.name.clone()),
+      Cell::from(val.driver.clone().unwrap_or_else(|| "-".to_string())),
+      Cell::from(val.runtime_status.to_string()).style(status_style),
+      Cell::from(control).style(control_style),
+      Cell::from(autosuspend),
+      Cell::from(format_duration(val.active_time)),
+      Cell::from(format_duration(val.suspended_time)),
+      Cell::from(val.active_percent().map_or_else(|| "-".to_string(), |percent| format!("{:>5.1}%", percent))),
+    ])
+  }
+}
+
+impl PmDevice {
+  fn headers() -> Vec<&'static str> {
+    vec!["Bus", "Device", "Name", "Driver", "Status", "Control", "Autosuspend", "Active", "Suspended", "Active%"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(3),
+      Constraint::Length(12),
+      Constraint::Min(30),
+      Constraint::Length(14),
+      Constraint::Length(11),
+      Constraint::Length(7),
+      Constraint::Length(11),
+      Constraint::Length(8),
+      Constraint::Length(9),
+      Constraint::Length(7),
+    ]
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevicePowerComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  // Active devices first, the ones active for the largest share of the time at the top
+  collected_data: PmDeviceCollection,
+  selection: TableSelection,
+  // Bus and address of the selected device, rows are re-sorted as devices suspend and resume
+  selected_device: Option<(String, String)>,
+  mode: Mode,
+}
+
+impl Default for DevicePowerComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DevicePowerComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      selection: TableSelection::default(),
+      selected_device: None,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: PmDeviceCollection) {
+    self.collected_data = new_data;
+    self.collected_data.sort_by_key(|device| {
+      (
+        device.runtime_status != RuntimeStatus::Active,
+        Reverse((device.active_percent().unwrap_or_default() * 10.0) as u64),
+        device.bus.clone(),
+        device.id.clone(),
+      )
+    });
+    self.sync_selection();
+  }
+
+  fn device_key(device: &PmDevice) -> (String, String) {
+    (device.bus.clone(), device.id.clone())
+  }
+
+  /// Points the table selection at the row of the selected device, keeping the current row if it was removed.
+  fn sync_selection(&mut self) {
+    let index = self
+      .selected_device
+      .as_ref()
+      .and_then(|(bus, id)| self.collected_data.iter().position(|device| device.bus == *bus && device.id == *id));
+    match index {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.collected_data.len());
+        self.selected_device = self.selection.selected().map(|i| Self::device_key(&self.collected_data[i]));
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if down {
+      self.selection.next(self.collected_data.len());
+    } else {
+      self.selection.previous(self.collected_data.len());
+    }
+    self.selected_device = self.selection.selected().map(|i| Self::device_key(&self.collected_data[i]));
+  }
+}
+
+impl Component for DevicePowerComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.runtime_pm {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::DevicePower => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::DevicePower => self.scroll(false),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::DevicePower {
+      return Ok(());
+    }
+
+    let active = self.collected_data.iter().filter(|device| device.runtime_status == RuntimeStatus::Active).count();
+    let title = format!("Device runtime PM - {} active, {} devices", active, self.collected_data.len());
+    let rows = self.collected_data.iter().map(Row::from).collect_vec();
+    let table = Table::new(rows, PmDevice::column_widths())
+      .block(Block::bordered().title(title))
+      .column_spacing(1)
+      .header(Row::new(PmDevice::headers()).style(Style::default().bold().underlined()))
+      .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, area, self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn device(id: &str, runtime_status: RuntimeStatus) -> PmDevice {
+    PmDevice {
+      bus: "pci".into(),
+      id: id.into(),
+      name: id.into(),
+      driver: None,
+      runtime_status,
+      control: Some("auto".into()),
+      autosuspend_delay_ms: None,
+      active_time: Duration::ZERO,
+      suspended_time: Duration::ZERO,
+    }
+  }
+
+  #[test]
+  fn test_selection_follows_device() {
+    let mut component = DevicePowerComponent::new();
+    component.update_data_stats(vec![
+      device("0000:00:02.0", RuntimeStatus::Active),
+      device("0000:00:14.0", RuntimeStatus::Suspended),
+    ]);
+    component.scroll(true);
+    component.scroll(true);
+    assert_eq!(component.selected_device, Some(("pci".into(), "0000:00:14.0".into())));
+
+    // The selected device resumes and moves above the one that suspended
+    component.update_data_stats(vec![
+      device("0000:00:02.0", RuntimeStatus::Suspended),
+      device("0000:00:14.0", RuntimeStatus::Active),
+    ]);
+    assert_eq!(component.collected_data[0].id, "0000:00:14.0");
+    assert_eq!(component.selection.selected(), Some(0));
+  }
+}
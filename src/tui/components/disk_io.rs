@@ -0,0 +1,132 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::disk_io::{DiskIoData, DiskIoDataCollection},
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame},
+  utils::format_bytes,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskIoComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: DiskIoDataCollection,
+  // Combined read + write throughput history in bytes per second, keyed by device
+  throughput: HashMap<String, VecDeque<u64>>,
+  mode: Mode,
+}
+
+impl Default for DiskIoComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DiskIoComponent {
+  pub const WINDOW_SIZE: usize = 60;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      throughput: HashMap::new(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: DiskIoDataCollection) {
+    // Forget devices that disappeared (e.g. a removed USB stick)
+    self.throughput.retain(|device, _| new_data.iter().any(|d| &d.device == device));
+
+    for device in new_data.iter() {
+      let history =
+        self.throughput.entry(device.device.clone()).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back((device.read_bytes_per_sec + device.write_bytes_per_sec) as u64);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn summary_line(device: &DiskIoData) -> Line<'static> {
+    let utilization_style = match device.utilization_percent {
+      u if u >= 80.0 => Style::default().red().bold(),
+      u if u >= 50.0 => Style::default().yellow(),
+      _ => Style::default().green(),
+    };
+    // Only the first mount point fits, the disk table lists all of them
+    let mount = match device.mount_points.as_slice() {
+      [] => String::new(),
+      [mount] => mount.clone(),
+      [mount, rest @ ..] => format!("{} +{}", mount, rest.len()),
+    };
+
+    Line::from(vec![
+      Span::from(format!("{:<8} ", device.device)).bold(),
+      Span::from(format!("{:<12.12} ", mount)).dim(),
+      Span::styled(format!("{:>4.0}% ", device.utilization_percent), utilization_style),
+      Span::from(format!(
+        "R {:>9}/s W {:>9}/s {:>5.0} IOPS await {:>4.1}ms q {:>3.1} ",
+        format_bytes(device.read_bytes_per_sec as u64),
+        format_bytes(device.write_bytes_per_sec as u64),
+        device.read_iops + device.write_iops,
+        device.await_ms,
+        device.queue_depth,
+      )),
+    ])
+  }
+}
+
+impl Component for DiskIoComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.disk_io {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let disk_io_area = HomeLayout::new(area).disk_io;
+    let block = Block::bordered().title("Disk I/O");
+    let inner = block.inner(disk_io_area);
+    frame.render_widget(block, disk_io_area);
+
+    // Each device takes a summary line followed by its throughput sparkline
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); self.collected_data.len() * 2])
+      .split(inner);
+
+    for (device, rects) in self.collected_data.iter().zip(rows.chunks(2)) {
+      frame.render_widget(Paragraph::new(Self::summary_line(device)), rects[0]);
+      if let Some(history) = self.throughput.get_mut(&device.device) {
+        frame
+          .render_widget(Sparkline::default().data(history.make_contiguous()).style(Style::default().cyan()), rects[1]);
+      }
+    }
+
+    Ok(())
+  }
+}
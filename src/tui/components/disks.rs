@@ -1,55 +1,156 @@
-use std::time::Instant;
+use std::{cmp::Ordering, time::Instant};
 
 use color_eyre::{
   eyre::{Ok, Result},
   owo_colors::OwoColorize,
 };
 use ratatui::{prelude::*, widgets::*};
+use strum::{Display, EnumIter, IntoEnumIterator};
 
 use super::Component;
 use crate::{
+  configuration::app_configuration::{Config, DiskConfig},
   data_services::disks::{DiskData, DiskDataCollection},
-  tui::{action::Action, ui::Frame},
+  tui::{
+    action::Action,
+    layout::HomeLayout,
+    mode::Mode,
+    ui::Frame,
+    widgets::{
+      donut::Donut,
+      table_selection::TableSelection,
+      usage::{usage_bar, usage_style},
+    },
+  },
+  utils::format_bytes,
 };
 
+const USAGE_BAR_WIDTH: usize = 10;
+// Minimum size of a single donut in the donut view
+const DONUT_WIDTH: u16 = 30;
+const DONUT_HEIGHT: u16 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter)]
+pub enum DiskSortColumn {
+  #[default]
+  Mount,
+  Name,
+  Size,
+  Used,
+  #[strum(serialize = "Use%")]
+  UsedPercentage,
+  #[strum(serialize = "Inode%")]
+  InodePercentage,
+}
+
+impl DiskSortColumn {
+  fn compare(&self, a: &DiskData, b: &DiskData) -> Ordering {
+    match self {
+      DiskSortColumn::Mount => a.mount_path.cmp(&b.mount_path),
+      DiskSortColumn::Name => a.name.cmp(&b.name),
+      DiskSortColumn::Size => a.total_space.cmp(&b.total_space),
+      DiskSortColumn::Used => a.used_space().cmp(&b.used_space()),
+      DiskSortColumn::UsedPercentage => a.used_percentage().total_cmp(&b.used_percentage()),
+      DiskSortColumn::InodePercentage => {
+        a.inode_used_percentage().unwrap_or_default().total_cmp(&b.inode_used_percentage().unwrap_or_default())
+      },
+    }
+  }
+
+  fn next(self) -> Self {
+    DiskSortColumn::iter().cycle().skip_while(|column| *column != self).nth(1).unwrap_or_default()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiskView {
+  #[default]
+  Table,
+  Donut,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DiskTable {
   app_start_time: Instant,
   render_start_time: Instant,
   collected_data: DiskDataCollection,
-}
-
-impl From<DiskData> for Row<'static> {
-  fn from(val: DiskData) -> Self {
-    Row::new(vec![
-      val.name,
-      val.kind,
-      val.file_system,
-      val.total_space.to_string(),
-      val.available_space.to_string(),
-      val.is_removable.to_string(),
-      val.mount_path,
-    ])
-  }
+  // Disks left after applying the configured filters, in display order
+  visible_disks: DiskDataCollection,
+  filters: DiskConfig,
+  sort_column: DiskSortColumn,
+  sort_descending: bool,
+  view: DiskView,
+  selection: TableSelection,
+  mode: Mode,
 }
 
 impl DiskData {
   // TODO: Better way to create headers from struct
   fn headers() -> Vec<&'static str> {
-    vec!["Name", "Kind", "File System", "Total (bytes)", "Available (bytes)", "IsRemovable", "Mount"]
+    vec!["Name", "Mount", "Size", "Use%"]
   }
 
   fn column_widths() -> Vec<Constraint> {
     vec![
       Constraint::Length(12),
+      Constraint::Min(12),
+      Constraint::Length(10),
+      Constraint::Length(USAGE_BAR_WIDTH as u16 + 5),
+    ]
+  }
+
+  fn detailed_headers() -> Vec<&'static str> {
+    vec!["Name", "Kind", "File System", "Mount", "Size", "Used", "Available", "Use%", "Inode%", "Removable"]
+  }
+
+  fn detailed_column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(16),
       Constraint::Length(6),
-      Constraint::Length(12),
-      Constraint::Length(12),
-      Constraint::Length(12),
+      Constraint::Length(11),
+      Constraint::Min(16),
+      Constraint::Length(10),
+      Constraint::Length(10),
+      Constraint::Length(10),
+      Constraint::Length(USAGE_BAR_WIDTH as u16 + 5),
       Constraint::Length(6),
-      Constraint::Length(12),
+      Constraint::Length(9),
     ]
   }
+
+  fn usage_cell(&self) -> Cell<'static> {
+    let percentage = self.used_percentage();
+    Cell::from(format!("{} {:>3.0}%", usage_bar(percentage, USAGE_BAR_WIDTH), percentage))
+      .style(usage_style(percentage))
+  }
+
+  fn row(&self) -> Row<'static> {
+    Row::new(vec![
+      Cell::from(self.name.clone()),
+      Cell::from(self.mount_path.clone()),
+      Cell::from(format_bytes(self.total_space)),
+      self.usage_cell(),
+    ])
+  }
+
+  fn detailed_row(&self) -> Row<'static> {
+    let inodes = match self.inode_used_percentage() {
+      Some(percentage) => Cell::from(format!("{:>3.0}%", percentage)).style(usage_style(percentage)),
+      None => Cell::from("-"),
+    };
+    Row::new(vec![
+      Cell::from(self.name.clone()),
+      Cell::from(self.kind.clone()),
+      Cell::from(self.file_system.clone()),
+      Cell::from(self.mount_path.clone()),
+      Cell::from(format_bytes(self.total_space)),
+      Cell::from(format_bytes(self.used_space())),
+      Cell::from(format_bytes(self.available_space)),
+      self.usage_cell(),
+      inodes,
+      Cell::from(self.is_removable.to_string()),
+    ])
+  }
 }
 
 impl Default for DiskTable {
@@ -60,58 +161,151 @@ impl Default for DiskTable {
 
 impl DiskTable {
   pub fn new() -> Self {
-    Self { app_start_time: Instant::now(), render_start_time: Instant::now(), collected_data: [].to_vec() }
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      visible_disks: [].to_vec(),
+      filters: DiskConfig::default(),
+      sort_column: DiskSortColumn::default(),
+      sort_descending: false,
+      view: DiskView::default(),
+      selection: TableSelection::default(),
+      mode: Mode::Home,
+    }
   }
 
   fn update_data_stats(&mut self, new_data: DiskDataCollection) {
     self.collected_data = new_data;
+    self.refresh_visible_disks();
   }
-}
 
-impl Component for DiskTable {
-  fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    if let Action::DataUpdate(data) = action {
-      match data.disk {
-        Some(d) => self.update_data_stats(d),
-        None => {
-          log::debug!("Received Action with no data.")
-        },
+  /// Re-applies the filters and sort order to the collected disks.
+  fn refresh_visible_disks(&mut self) {
+    self.visible_disks =
+      self.collected_data.iter().filter(|d| !self.filters.is_hidden(&d.file_system, &d.mount_path)).cloned().collect();
+    self.visible_disks.sort_by(|a, b| {
+      let ordering = self.sort_column.compare(a, b);
+      if self.sort_descending {
+        ordering.reverse()
+      } else {
+        ordering
       }
-    }
-    Ok(None)
+    });
+    self.selection.clamp(self.visible_disks.len());
   }
 
-  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
-    let rects = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints(vec![
-        Constraint::Percentage(100), // Top row spans whole width
-        Constraint::Percentage(50),  // Bottom row split 50/50
-      ])
-      .split(area);
-
-    let bottom_row_rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(vec![
-        Constraint::Percentage(33), // Left half
-        Constraint::Percentage(33), // Right half
-        Constraint::Percentage(33), // Right half
-      ])
-      .split(rects[1]);
-
-    let rows: Vec<Row> = self.collected_data.clone().into_iter().map(Into::into).collect();
-    let col_widths = DiskData::column_widths();
-    let header = Row::new(DiskData::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
+  fn draw_table(&mut self, frame: &mut Frame<'_>, area: Rect, detailed: bool) {
+    let (rows, headers, col_widths): (Vec<Row>, _, _) = if detailed {
+      (
+        self.visible_disks.iter().map(DiskData::detailed_row).collect(),
+        DiskData::detailed_headers(),
+        DiskData::detailed_column_widths(),
+      )
+    } else {
+      (self.visible_disks.iter().map(DiskData::row).collect(), DiskData::headers(), DiskData::column_widths())
+    };
+    let header = Row::new(headers).style(Style::default().bold().underlined()).bottom_margin(1);
+    let title = if detailed {
+      format!("Disks - sorted by {}{}", self.sort_column, if self.sort_descending { " ▼" } else { " ▲" })
+    } else {
+      "Disk".to_string()
+    };
 
     let table = Table::new(rows, col_widths)
-      .block(Block::bordered().title("Disk"))
-      .column_spacing(3)
+      .block(Block::bordered().title(title))
+      .column_spacing(2)
       .style(Style::default().white())
       .header(header)
       .highlight_style(Style::default().reversed())
       .highlight_symbol(">>");
 
-    frame.render_widget(table, bottom_row_rects[1]);
+    if detailed {
+      frame.render_stateful_widget(table, area, self.selection.state_mut());
+    } else {
+      frame.render_widget(table, area);
+    }
+  }
+
+  fn draw_donuts(&self, frame: &mut Frame<'_>, area: Rect) {
+    let block = Block::bordered().title("Disks - usage");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = (inner.width / DONUT_WIDTH).max(1) as usize;
+    let row_count = self.visible_disks.len().div_ceil(columns);
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(DONUT_HEIGHT); row_count])
+      .split(inner);
+
+    for (row, disks) in rows.iter().zip(self.visible_disks.chunks(columns)) {
+      let cells = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(*row);
+      for (cell, disk) in cells.iter().zip(disks) {
+        let percentage = disk.used_percentage();
+        let donut = Donut::new(percentage).filled_color(usage_style(percentage).fg.unwrap_or(Color::Green)).block(
+          Block::new().title(format!(
+            "{} ({} / {})",
+            disk.mount_path,
+            format_bytes(disk.used_space()),
+            format_bytes(disk.total_space)
+          )),
+        );
+        frame.render_widget(donut, *cell);
+      }
+    }
+  }
+}
+
+impl Component for DiskTable {
+  fn register_config_handler(&mut self, config: Config) -> Result<()> {
+    self.filters = config.disks;
+    self.refresh_visible_disks();
+    Ok(())
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.disk {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Disks => self.selection.next(self.visible_disks.len()),
+      Action::ScrollUp if self.mode == Mode::Disks => self.selection.previous(self.visible_disks.len()),
+      Action::CycleSort if self.mode == Mode::Disks => {
+        self.sort_column = self.sort_column.next();
+        self.refresh_visible_disks();
+      },
+      Action::ReverseSort if self.mode == Mode::Disks => {
+        self.sort_descending = !self.sort_descending;
+        self.refresh_visible_disks();
+      },
+      Action::ToggleView if self.mode == Mode::Disks => {
+        self.view = match self.view {
+          DiskView::Table => DiskView::Donut,
+          DiskView::Donut => DiskView::Table,
+        }
+      },
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    match (self.mode, self.view) {
+      (Mode::Home, _) => self.draw_table(frame, HomeLayout::new(area).disks, false),
+      (Mode::Disks, DiskView::Table) => self.draw_table(frame, area, true),
+      (Mode::Disks, DiskView::Donut) => self.draw_donuts(frame, area),
+      _ => {},
+    }
 
     Ok(())
   }
@@ -0,0 +1,224 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::cpu_freq::{sampled_residency, CpuFreqCore, CpuFreqData, FrequencyResidency, FrequencySource},
+  tui::{action::Action, mode::Mode, ui::Frame},
+};
+
+const BAR_WIDTH: u16 = 4;
+const BAR_GAP: u16 = 1;
+// Smallest histogram that still shows labels, values and a few lines of bars
+const MIN_CORE_WIDTH: u16 = 36;
+const MIN_CORE_HEIGHT: u16 = 9;
+
+/// Merges adjacent frequencies so the histogram has at most `max_bars` bars. Each bar is labelled with the lowest
+/// frequency it covers.
+fn bucket_residency(residency: &[FrequencyResidency], max_bars: usize) -> Vec<FrequencyResidency> {
+  if max_bars == 0 || residency.len() <= max_bars {
+    return residency.to_vec();
+  }
+  residency
+    .chunks(residency.len().div_ceil(max_bars))
+    .map(|chunk| {
+      FrequencyResidency {
+        frequency_khz: chunk[0].frequency_khz,
+        percent: chunk.iter().map(|frequency| frequency.percent).sum(),
+      }
+    })
+    .collect()
+}
+
+fn format_ghz(khz: u64) -> String {
+  format!("{:.1}", khz as f64 / 1_000_000.0)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: CpuFreqData,
+  // Sampled frequencies per CPU, oldest first, for cores whose driver keeps no time_in_state
+  samples: HashMap<u32, VecDeque<u64>>,
+  // First row of the core grid shown
+  offset: usize,
+  mode: Mode,
+}
+
+impl Default for FrequencyComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FrequencyComponent {
+  // At the default collection interval the sampled histogram covers the last minute
+  pub const WINDOW_SIZE: usize = 60;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: CpuFreqData::default(),
+      samples: HashMap::new(),
+      offset: 0,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: CpuFreqData) {
+    for core in new_data.cores.iter().filter(|core| core.source == FrequencySource::Sampled) {
+      let Some(current) = core.current_khz else { continue };
+      let samples = self.samples.entry(core.cpu).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if samples.len() == Self::WINDOW_SIZE {
+        samples.pop_front();
+      }
+      samples.push_back(current);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn residency(&self, core: &CpuFreqCore) -> Vec<FrequencyResidency> {
+    match core.source {
+      FrequencySource::TimeInState => core.residency.clone(),
+      FrequencySource::Sampled => {
+        self.samples.get(&core.cpu).map(|samples| sampled_residency(samples.iter().copied())).unwrap_or_default()
+      },
+    }
+  }
+
+  fn draw_core(&self, frame: &mut Frame<'_>, area: Rect, core: &CpuFreqCore) {
+    let mut title = vec![Span::from(format!("cpu{} ", core.cpu)).bold()];
+    if let Some(current) = core.current_khz {
+      title.push(Span::from(format!("{} GHz ", format_ghz(current))).cyan());
+    }
+    if let (Some(min), Some(max)) = (core.min_khz, core.max_khz) {
+      title.push(Span::from(format!("[{}-{}] ", format_ghz(min), format_ghz(max))));
+    }
+    if let Some(governor) = &core.governor {
+      title.push(Span::from(governor.clone()).dark_gray());
+    }
+    let block = Block::bordered().title(Line::from(title));
+    let inner = block.inner(area);
+
+    let residency = self.residency(core);
+    if residency.is_empty() {
+      frame.render_widget(Paragraph::new("Collecting...").dark_gray().block(block), area);
+      return;
+    }
+
+    let max_bars = ((inner.width + BAR_GAP) / (BAR_WIDTH + BAR_GAP)) as usize;
+    let bars = bucket_residency(&residency, max_bars)
+      .iter()
+      .map(|frequency| {
+        Bar::default()
+          .value(frequency.percent.round() as u64)
+          .text_value(format!("{:.0}%", frequency.percent))
+          .label(format_ghz(frequency.frequency_khz).into())
+      })
+      .collect_vec();
+    let chart = BarChart::default()
+      .block(block)
+      .bar_width(BAR_WIDTH)
+      .bar_gap(BAR_GAP)
+      .bar_style(Style::new().cyan())
+      .value_style(Style::new().black().on_cyan())
+      .data(BarGroup::default().bars(&bars))
+      .max(100);
+    frame.render_widget(chart, area);
+  }
+}
+
+impl Component for FrequencyComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.cpu_freq {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Frequency => self.offset = self.offset.saturating_add(1),
+      Action::ScrollUp if self.mode == Mode::Frequency => self.offset = self.offset.saturating_sub(1),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Frequency {
+      return Ok(());
+    }
+
+    let source = self.collected_data.cores.first().map_or("-".to_string(), |core| core.source.to_string());
+    let block = Block::bordered().title(format!(
+      "CPU frequency - driver {}, {}",
+      self.collected_data.driver.as_deref().unwrap_or("-"),
+      source
+    ));
+    if self.collected_data.cores.is_empty() {
+      frame.render_widget(
+        Paragraph::new("No cpufreq support exposed by the kernel (common in virtual machines)")
+          .dark_gray()
+          .block(block),
+        area,
+      );
+      return Ok(());
+    }
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = (inner.width / MIN_CORE_WIDTH).clamp(1, self.collected_data.cores.len() as u16) as usize;
+    let grid_rows = self.collected_data.cores.len().div_ceil(columns);
+    let visible_rows = ((inner.height / MIN_CORE_HEIGHT) as usize).clamp(1, grid_rows);
+    self.offset = self.offset.min(grid_rows - visible_rows);
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Ratio(1, visible_rows as u32); visible_rows])
+      .split(inner);
+    let cores = self.collected_data.cores.clone();
+    for (row_area, chunk) in rows.iter().zip(cores.chunks(columns).skip(self.offset)) {
+      let cells = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(*row_area);
+      for (cell, core) in cells.iter().zip(chunk) {
+        self.draw_core(frame, *cell, core);
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_bucket_residency() {
+    let residency = [800_000, 1_200_000, 1_600_000, 2_000_000, 2_400_000]
+      .iter()
+      .map(|frequency_khz| FrequencyResidency { frequency_khz: *frequency_khz, percent: 20.0 })
+      .collect_vec();
+
+    assert_eq!(bucket_residency(&residency, 5), residency);
+    assert_eq!(bucket_residency(&residency, 2), vec![
+      FrequencyResidency { frequency_khz: 800_000, percent: 60.0 },
+      FrequencyResidency { frequency_khz: 2_000_000, percent: 40.0 },
+    ]);
+  }
+}
@@ -0,0 +1,140 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::{
+    host::{HostData, LoadAverage},
+    pseudo_fs::PseudoFsRoots,
+  },
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame, widgets::usage::alert_style},
+  utils::{format_duration, format_timestamp},
+};
+
+const SEPARATOR: &str = " │ ";
+
+/// Colors a per core load: below 0.7 leaves headroom, above 1.0 means work is queueing for a CPU.
+fn load_style(per_core: f64) -> Style {
+  alert_style(per_core * 100.0, 70.0, 100.0)
+}
+
+fn load_spans(load: LoadAverage, per_core: LoadAverage) -> Vec<Span<'static>> {
+  let mut spans = vec![Span::from("Load ").bold()];
+  for (value, normalized) in [(load.one, per_core.one), (load.five, per_core.five), (load.fifteen, per_core.fifteen)] {
+    spans.push(Span::styled(format!("{:.2} ", value), load_style(normalized)));
+  }
+  spans.push(Span::from(format!("(per core {:.2} {:.2} {:.2})", per_core.one, per_core.five, per_core.fifteen)));
+  spans
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: Option<HostData>,
+  // Where a capture is read from, None when monitoring the running machine
+  capture: Option<String>,
+  mode: Mode,
+}
+
+impl Default for HeaderComponent {
+  fn default() -> Self {
+    Self::new(&PseudoFsRoots::default())
+  }
+}
+
+impl HeaderComponent {
+  pub fn new(roots: &PseudoFsRoots) -> Self {
+    let capture = (!roots.is_live()).then(|| format!("{} and {}", roots.proc_root.display(), roots.sys_root.display()));
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: None,
+      capture,
+      mode: Mode::Home,
+    }
+  }
+
+  /// Borders the header. For a capture it notes that the widgets backed by sysinfo, which only reads the running
+  /// machine, are left empty.
+  fn block(&self, title: Span<'static>) -> Block<'static> {
+    let block = Block::bordered().title(title);
+    match &self.capture {
+      Some(capture) => {
+        let notice = format!(" Capture of {}, CPU usage, processes, disks and networks are only shown live ", capture);
+        block.title(Line::from(notice).yellow().right_aligned())
+      },
+      None => block,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: HostData) {
+    self.collected_data = Some(new_data);
+  }
+
+  fn identity_line(host: &HostData) -> Line<'static> {
+    // Distribution and architecture are unknown for a capture, they are left out rather than shown as unknown
+    let fields = [
+      host.distribution.clone(),
+      Some(format!("Kernel {}", host.kernel.as_deref().unwrap_or("unknown"))),
+      host.architecture.clone(),
+      Some(format!("Up {}", format_duration(host.uptime))),
+    ];
+    let mut spans =
+      Itertools::intersperse(fields.into_iter().flatten().map(Span::from), Span::from(SEPARATOR).dark_gray())
+        .collect::<Vec<_>>();
+    if let Some(boot_time) = host.boot_time {
+      spans.push(Span::from(format!(" (booted {})", format_timestamp(boot_time))).dark_gray());
+    }
+    Line::from(spans)
+  }
+
+  fn activity_line(host: &HostData) -> Line<'static> {
+    let mut spans = load_spans(host.load, host.load.per_core(host.cpus));
+    spans.push(Span::from(format!(" on {} CPU{}", host.cpus, if host.cpus == 1 { "" } else { "s" })));
+    spans.push(Span::from(SEPARATOR).dark_gray());
+    spans.push(Span::from(format!("{} processes, {} threads, {} running", host.processes, host.threads, host.running)));
+    Line::from(spans)
+  }
+}
+
+impl Component for HeaderComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.host {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let header_area = HomeLayout::new(area).header;
+    let Some(host) = &self.collected_data else {
+      frame.render_widget(self.block(Span::from("System")), header_area);
+      return Ok(());
+    };
+    let title = host.hostname.clone().unwrap_or_else(|| "System".to_string());
+    let block = self.block(Span::from(title).bold());
+    frame.render_widget(
+      Paragraph::new(vec![Self::identity_line(host), Self::activity_line(host)]).block(block),
+      header_area,
+    );
+
+    Ok(())
+  }
+}
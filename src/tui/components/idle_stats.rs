@@ -0,0 +1,218 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::cpu_idle::{CpuIdleCore, CpuIdleData},
+  tui::{
+    action::Action,
+    mode::Mode,
+    ui::Frame,
+    widgets::{table_selection::TableSelection, usage::threshold_style},
+  },
+};
+
+const RESIDENCY_WIDTH: u16 = 10;
+
+/// A state averaged over all cores, the equivalent of the package column of powertop's idle stats.
+#[derive(Debug, Clone, PartialEq)]
+struct StateSummary {
+  name: String,
+  description: String,
+  latency_us: u64,
+  disabled: bool,
+  residency_percent: f64,
+  entries_per_sec: f64,
+  average_residency_us: Option<f64>,
+}
+
+fn summarize_states(cores: &[CpuIdleCore]) -> Vec<StateSummary> {
+  let Some(first) = cores.first() else {
+    return Vec::new();
+  };
+  first
+    .states
+    .iter()
+    .enumerate()
+    .map(|(index, state)| {
+      let per_core = cores.iter().filter_map(|core| core.states.get(index)).collect_vec();
+      let entries_per_sec: f64 = per_core.iter().map(|s| s.entries_per_sec).sum();
+      // Weighted by entries so cores that rarely enter the state do not skew the average
+      let weighted_time: f64 = per_core.iter().filter_map(|s| Some(s.average_residency_us? * s.entries_per_sec)).sum();
+      StateSummary {
+        name: state.name.clone(),
+        description: state.description.clone(),
+        latency_us: state.latency_us,
+        disabled: per_core.iter().all(|s| s.disabled),
+        residency_percent: per_core.iter().map(|s| s.residency_percent).sum::<f64>() / per_core.len() as f64,
+        entries_per_sec,
+        average_residency_us: (entries_per_sec > 0.0).then(|| weighted_time / entries_per_sec),
+      }
+    })
+    .collect()
+}
+
+fn format_residency_time(micros: Option<f64>) -> String {
+  match micros {
+    Some(us) if us >= 1_000.0 => format!("{:.1} ms", us / 1_000.0),
+    Some(us) => format!("{:.0} us", us),
+    None => "-".to_string(),
+  }
+}
+
+/// Deep idle states are where the power is saved, so residency is highlighted more the higher it is.
+fn residency_style(percent: f64) -> Style {
+  if percent >= 50.0 {
+    Style::default().green().bold()
+  } else if percent >= 10.0 {
+    Style::default().green()
+  } else if percent > 0.0 {
+    Style::default()
+  } else {
+    Style::default().dark_gray()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleStatsComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: CpuIdleData,
+  summary: Vec<StateSummary>,
+  selection: TableSelection,
+  mode: Mode,
+}
+
+impl Default for IdleStatsComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl IdleStatsComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: CpuIdleData::default(),
+      summary: Vec::new(),
+      selection: TableSelection::default(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: CpuIdleData) {
+    self.summary = summarize_states(&new_data.cores);
+    self.collected_data = new_data;
+    self.selection.clamp(self.collected_data.cores.len());
+  }
+
+  fn title(&self) -> String {
+    format!(
+      "Idle stats - driver {}, governor {}",
+      self.collected_data.driver.as_deref().unwrap_or("-"),
+      self.collected_data.governor.as_deref().unwrap_or("-")
+    )
+  }
+
+  fn draw_summary(&self, frame: &mut Frame<'_>, area: Rect) {
+    let rows = self.summary.iter().map(|state| {
+      let name = if state.disabled { format!("{} (disabled)", state.name) } else { state.name.clone() };
+      Row::new(vec![
+        Cell::from(name),
+        Cell::from(state.description.clone()),
+        Cell::from(format!("{} us", state.latency_us)),
+        Cell::from(format!("{:>5.1}%", state.residency_percent)).style(residency_style(state.residency_percent)),
+        Cell::from(format!("{:>9.1}", state.entries_per_sec)),
+        Cell::from(format_residency_time(state.average_residency_us)),
+      ])
+    });
+    let header = Row::new(vec!["State", "Description", "Exit latency", "Residency", "Entries/s", "Avg time"])
+      .style(Style::default().bold().underlined());
+    let table = Table::new(rows, vec![
+      Constraint::Length(16),
+      Constraint::Min(20),
+      Constraint::Length(12),
+      Constraint::Length(9),
+      Constraint::Length(10),
+      Constraint::Length(9),
+    ])
+    .block(Block::bordered().title(self.title()))
+    .column_spacing(2)
+    .header(header);
+    frame.render_widget(table, area);
+  }
+
+  fn draw_cores(&mut self, frame: &mut Frame<'_>, area: Rect) {
+    let state_names = self.summary.iter().map(|state| state.name.clone()).collect_vec();
+    let mut headers = vec!["CPU".to_string(), "C0 active".to_string()];
+    headers.extend(state_names.iter().cloned());
+
+    let rows = self.collected_data.cores.iter().map(|core| {
+      let mut cells = vec![
+        Cell::from(format!("cpu{}", core.cpu)),
+        Cell::from(format!("{:>5.1}%", core.active_percent)).style(threshold_style(core.active_percent, 50.0, 80.0)),
+      ];
+      cells.extend(core.states.iter().map(|state| {
+        Cell::from(format!("{:>5.1}%", state.residency_percent)).style(residency_style(state.residency_percent))
+      }));
+      Row::new(cells)
+    });
+    let widths = std::iter::once(Constraint::Length(6))
+      .chain(std::iter::repeat_n(Constraint::Length(RESIDENCY_WIDTH), state_names.len() + 1))
+      .collect_vec();
+    let table = Table::new(rows, widths)
+      .block(Block::bordered().title("Residency per core"))
+      .column_spacing(1)
+      .header(Row::new(headers).style(Style::default().bold().underlined()))
+      .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, area, self.selection.state_mut());
+  }
+}
+
+impl Component for IdleStatsComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.cpu_idle {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::IdleStats => self.selection.next(self.collected_data.cores.len()),
+      Action::ScrollUp if self.mode == Mode::IdleStats => self.selection.previous(self.collected_data.cores.len()),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::IdleStats {
+      return Ok(());
+    }
+
+    if self.collected_data.cores.is_empty() {
+      let message = match self.collected_data.driver.as_deref() {
+        None | Some("none") => "The cpuidle driver exposes no idle states (common in virtual machines)",
+        Some(_) => "Collecting idle state residency...",
+      };
+      frame.render_widget(Paragraph::new(message).dark_gray().block(Block::bordered().title(self.title())), area);
+      return Ok(());
+    }
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(self.summary.len() as u16 + 3), Constraint::Min(0)])
+      .split(area);
+    self.draw_summary(frame, rows[0]);
+    self.draw_cores(frame, rows[1]);
+
+    Ok(())
+  }
+}
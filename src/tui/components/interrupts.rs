@@ -0,0 +1,216 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::interrupts::{InterruptData, InterruptKind, InterruptSource},
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
+};
+
+const RATE_WIDTH: u16 = 9;
+// Number of busiest sources highlighted at the top of the table
+const TOP_SOURCES: usize = 5;
+
+/// Colors a per CPU rate relative to the busiest cell of the table, so a single CPU taking all of a device's
+/// interrupts stands out.
+fn heat_style(rate: f64, max_rate: f64) -> Style {
+  if rate <= 0.0 || max_rate <= 0.0 {
+    return Style::default().dark_gray();
+  }
+  let heat = rate / max_rate;
+  if heat >= 0.66 {
+    Style::default().white().on_red()
+  } else if heat >= 0.33 {
+    Style::default().black().on_yellow()
+  } else if heat >= 0.05 {
+    Style::default().yellow()
+  } else {
+    Style::default().green()
+  }
+}
+
+fn format_rate(rate: f64) -> String {
+  if rate >= 10_000.0 {
+    format!("{:>8.0}k", rate / 1000.0)
+  } else {
+    format!("{:>9.0}", rate)
+  }
+}
+
+fn source_row(source: &InterruptSource, max_rate: f64, top: bool) -> Row<'static> {
+  let name_style = if top { Style::default().red().bold() } else { Style::default() };
+  let mut cells = vec![
+    Cell::from(source.kind.to_string()).dark_gray(),
+    Cell::from(source.id.clone()).style(name_style),
+    Cell::from(source.description.clone()),
+    Cell::from(format_rate(source.total_rate)).style(name_style),
+  ];
+  cells
+    .extend(source.per_cpu_rates.iter().map(|rate| Cell::from(format_rate(*rate)).style(heat_style(*rate, max_rate))));
+  Row::new(cells)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterruptsComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: InterruptData,
+  selection: TableSelection,
+  // Sources are re-sorted by rate on every collection, so the selection follows a source rather than a row
+  selected_source: Option<(InterruptKind, String)>,
+  mode: Mode,
+}
+
+impl Default for InterruptsComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl InterruptsComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: InterruptData::default(),
+      selection: TableSelection::default(),
+      selected_source: None,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: InterruptData) {
+    self.collected_data = new_data;
+    self.sync_selection();
+  }
+
+  fn source_key(source: &InterruptSource) -> (InterruptKind, String) {
+    (source.kind, source.id.clone())
+  }
+
+  /// Points the table selection at the row of the selected source, keeping the current row if it disappeared.
+  fn sync_selection(&mut self) {
+    let sources = &self.collected_data.sources;
+    let index = self
+      .selected_source
+      .as_ref()
+      .and_then(|(kind, id)| sources.iter().position(|source| source.kind == *kind && source.id == *id));
+    match index {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(sources.len());
+        self.selected_source = self.selection.selected().map(|i| Self::source_key(&sources[i]));
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    let sources = &self.collected_data.sources;
+    if down {
+      self.selection.next(sources.len());
+    } else {
+      self.selection.previous(sources.len());
+    }
+    self.selected_source = self.selection.selected().map(|i| Self::source_key(&sources[i]));
+  }
+}
+
+impl Component for InterruptsComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.interrupts {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Interrupts => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Interrupts => self.scroll(false),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Interrupts {
+      return Ok(());
+    }
+
+    let data = &self.collected_data;
+    let max_rate = data.sources.iter().flat_map(|source| source.per_cpu_rates.iter().copied()).fold(0.0, f64::max);
+    let total: f64 = data.sources.iter().map(|source| source.total_rate).sum();
+    let cpus = data.cpus.len();
+    let title =
+      format!("Interrupts per second - {:.0} total on {} CPU{}", total, cpus, if cpus == 1 { "" } else { "s" });
+
+    let rows = data
+      .sources
+      .iter()
+      .enumerate()
+      .map(|(index, source)| source_row(source, max_rate, index < TOP_SOURCES && source.total_rate > 0.0))
+      .collect_vec();
+    let mut widths =
+      vec![Constraint::Length(7), Constraint::Length(10), Constraint::Min(20), Constraint::Length(RATE_WIDTH)];
+    widths.extend(std::iter::repeat_n(Constraint::Length(RATE_WIDTH), data.cpus.len()));
+    let mut headers =
+      vec!["Type".to_string(), "Source".to_string(), "Description".to_string(), format!("{:>9}", "Total")];
+    headers.extend(data.cpus.iter().map(|cpu| format!("{:>9}", cpu)));
+
+    let table = Table::new(rows, widths)
+      .block(Block::bordered().title(title))
+      .column_spacing(1)
+      .header(Row::new(headers).style(Style::default().bold().underlined()))
+      .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, area, self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_heat_style() {
+    assert_eq!(heat_style(0.0, 100.0), Style::default().dark_gray());
+    assert_eq!(heat_style(1.0, 100.0), Style::default().green());
+    assert_eq!(heat_style(50.0, 100.0), Style::default().black().on_yellow());
+    assert_eq!(heat_style(100.0, 100.0), Style::default().white().on_red());
+    assert_eq!(format_rate(123456.0), "     123k");
+  }
+
+  fn source(kind: InterruptKind, id: &str, total_rate: f64) -> InterruptSource {
+    InterruptSource { kind, id: id.into(), description: String::new(), per_cpu_rates: vec![total_rate], total_rate }
+  }
+
+  #[test]
+  fn test_selection_follows_source() {
+    let mut component = InterruptsComponent::new();
+    let data = |eth0, net_rx| {
+      InterruptData {
+        cpus: vec!["CPU0".into()],
+        sources: vec![source(InterruptKind::Hardware, "29", eth0), source(InterruptKind::Soft, "NET_RX", net_rx)],
+      }
+    };
+    component.update_data_stats(data(200.0, 100.0));
+    component.scroll(true);
+    component.scroll(true);
+    assert_eq!(component.selected_source, Some((InterruptKind::Soft, "NET_RX".into())));
+
+    // Sources arrive sorted by rate, NET_RX is now the busiest
+    let mut busier = data(200.0, 300.0);
+    busier.sources.reverse();
+    component.update_data_stats(busier);
+    assert_eq!(component.selection.selected(), Some(0));
+    assert_eq!(component.selected_source, Some((InterruptKind::Soft, "NET_RX".into())));
+  }
+}
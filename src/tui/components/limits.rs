@@ -0,0 +1,134 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::limits::{KernelLimit, KernelLimitCollection},
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::usage::usage_style},
+};
+
+const NAME_WIDTH: u16 = 18;
+const SOURCE_WIDTH: u16 = 32;
+const VALUE_WIDTH: u16 = 28;
+
+/// Some limits are effectively unlimited, e.g. `fs.file-max` defaults to `i64::MAX` on recent kernels.
+fn format_max(max: u64) -> String {
+  if max >= i64::MAX as u64 {
+    "unlimited".to_string()
+  } else {
+    max.to_string()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitsComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: KernelLimitCollection,
+  mode: Mode,
+}
+
+impl Default for LimitsComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl LimitsComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: KernelLimitCollection) {
+    self.collected_data = new_data;
+  }
+
+  fn draw_limit(frame: &mut Frame<'_>, area: Rect, limit: &KernelLimit) {
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![
+        Constraint::Length(NAME_WIDTH),
+        Constraint::Length(SOURCE_WIDTH),
+        Constraint::Min(10),
+        Constraint::Length(VALUE_WIDTH),
+      ])
+      .split(area);
+
+    let ratio = limit.usage_ratio();
+    // Turns red at 90%, where the limit is also reported as near
+    let style = usage_style(ratio * 100.0);
+    frame.render_widget(Paragraph::new(limit.name.clone()).bold(), columns[0]);
+    frame.render_widget(Paragraph::new(limit.source.clone()).dark_gray(), columns[1]);
+    frame.render_widget(
+      Gauge::default().gauge_style(style).ratio(ratio).label(format!("{:.1}%", ratio * 100.0)),
+      columns[2],
+    );
+    frame.render_widget(
+      Paragraph::new(format!("{} / {}", limit.used, format_max(limit.max))).style(style).alignment(Alignment::Right),
+      columns[3],
+    );
+  }
+}
+
+impl Component for LimitsComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.limits {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Limits {
+      return Ok(());
+    }
+
+    let near = self.collected_data.iter().filter(|limit| limit.is_near_limit()).count();
+    let title = match near {
+      0 => "Kernel Limits".to_string(),
+      near => format!("Kernel Limits - {} near the limit", near),
+    };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // A blank line between gauges keeps them apart
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(2); self.collected_data.len()])
+      .split(inner);
+    for (limit, row) in self.collected_data.iter().zip(rows.iter()) {
+      Self::draw_limit(frame, Rect { height: 1, ..*row }, limit);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_format_max() {
+    assert_eq!(format_max(9223372036854775807), "unlimited");
+  }
+}
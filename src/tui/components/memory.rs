@@ -11,16 +11,76 @@ use serde::{Deserialize, Serialize};
 use super::Component;
 use crate::{
   configuration::app_configuration::Config,
-  data_services::memory::MemoryData,
-  tui::{action::Action, ui::Frame},
+  data_services::memory::{MemoryData, SwapDevice, ZramDevice, ZswapData},
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame, widgets::usage::alert_style},
+  utils::format_bytes,
 };
 
+/// Colors the share of a swap area in use, a nearly full area pushes the kernel towards the OOM killer.
+fn swap_usage_style(percent: f64) -> Style {
+  alert_style(percent, 50.0, 80.0)
+}
+
+fn ratio_cell(ratio: Option<f64>) -> Cell<'static> {
+  match ratio {
+    Some(ratio) => Cell::from(format!("{:.1}x", ratio)),
+    None => Cell::from("-").dark_gray(),
+  }
+}
+
+impl From<&SwapDevice> for Row<'static> {
+  fn from(val: &SwapDevice) -> Self {
+    let percent = if val.size == 0 { 0.0 } else { val.used as f64 / val.size as f64 * 100.0 };
+    Row::new(vec![
+      Cell::from(val.path.clone()),
+      Cell::from(val.kind.clone()),
+      Cell::from(format!("{} / {}", format_bytes(val.used), format_bytes(val.size))),
+      Cell::from(format!("{:.0}%", percent)).style(swap_usage_style(percent)),
+      Cell::from(format!("prio {}", val.priority)),
+    ])
+  }
+}
+
+impl From<&ZramDevice> for Row<'static> {
+  fn from(val: &ZramDevice) -> Self {
+    Row::new(vec![
+      Cell::from(val.name.clone()),
+      Cell::from(val.algorithm.clone().unwrap_or_else(|| "zram".to_string())),
+      Cell::from(format!("{} → {}", format_bytes(val.original_size), format_bytes(val.compressed_size))),
+      ratio_cell(val.compression_ratio()),
+      Cell::from(format!("mem {}", format_bytes(val.memory_used))),
+    ])
+  }
+}
+
+impl From<&ZswapData> for Row<'static> {
+  fn from(val: &ZswapData) -> Self {
+    if !val.enabled {
+      return Row::new(vec![Cell::from("zswap"), Cell::from("disabled")]).dark_gray();
+    }
+    let stored = match (val.stored_size, val.pool_size) {
+      (Some(stored), Some(pool)) => format!("{} → {}", format_bytes(stored), format_bytes(pool)),
+      _ => "-".to_string(),
+    };
+    Row::new(vec![
+      Cell::from("zswap"),
+      Cell::from(val.compressor.clone().unwrap_or_default()),
+      Cell::from(stored),
+      ratio_cell(val.compression_ratio()),
+      Cell::from(val.max_pool_percent.map(|percent| format!("max {}%", percent)).unwrap_or_default()),
+    ])
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemoryViewModel {
   available_ram: VecDeque<(f64, f64)>,
   available_swap: VecDeque<(f64, f64)>,
   total_ram: u64,
   total_swap: u64,
+  swap_devices: Vec<SwapDevice>,
+  zram_devices: Vec<ZramDevice>,
+  zswap: Option<ZswapData>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +88,7 @@ pub struct MemoryComponent {
   app_start_time: Instant,
   render_start_time: Instant,
   memory_view_model: MemoryViewModel,
+  mode: Mode,
 }
 
 impl Default for MemoryComponent {
@@ -48,7 +109,11 @@ impl MemoryComponent {
         total_swap: 0,
         available_ram: VecDeque::with_capacity(Self::WINDOW_SIZE),
         available_swap: VecDeque::with_capacity(Self::WINDOW_SIZE),
+        swap_devices: Vec::new(),
+        zram_devices: Vec::new(),
+        zswap: None,
       },
+      mode: Mode::Home,
     }
   }
 
@@ -72,33 +137,73 @@ impl MemoryComponent {
       self.memory_view_model.available_swap.iter_mut().for_each(|f| f.0 -= 1.0);
     }
     self.memory_view_model.available_swap.push_back((self.memory_view_model.available_swap.len() as f64, swap_percent));
+
+    self.memory_view_model.swap_devices = new_data.swap_devices;
+    self.memory_view_model.zram_devices = new_data.zram_devices;
+    self.memory_view_model.zswap = new_data.zswap;
+  }
+
+  /// One row per swap area, then the zram devices backing them and zswap in front of them.
+  fn swap_rows(&self) -> Vec<Row<'static>> {
+    let model = &self.memory_view_model;
+    let mut rows: Vec<Row<'static>> = model.swap_devices.iter().map(Row::from).collect();
+    rows.extend(model.zram_devices.iter().map(Row::from));
+    if rows.is_empty() {
+      rows.push(Row::new(vec![Cell::from("No swap areas"), Cell::from("")]).dark_gray());
+    }
+    rows.extend(model.zswap.iter().map(Row::from));
+    rows
+  }
+
+  fn draw_swap_section(&self, frame: &mut Frame<'_>, area: Rect, rows: Vec<Row<'static>>) {
+    let widths = vec![
+      Constraint::Length(16),
+      Constraint::Length(9),
+      Constraint::Length(23),
+      Constraint::Length(5),
+      Constraint::Min(0),
+    ];
+    let table = Table::new(rows, widths)
+      .column_spacing(1)
+      .block(Block::default().borders(Borders::TOP).title("Swap").border_style(Style::default().dark_gray()));
+    frame.render_widget(table, area);
   }
 }
 
 impl Component for MemoryComponent {
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    if let Action::DataUpdate(data) = action {
-      match data.memory {
-        Some(d) => self.update_data_stats(d),
-        None => {
-          log::debug!("Received Action with no data.")
-        },
-      }
+    match action {
+      Action::DataUpdate(data) => {
+        match data.memory {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
     }
     Ok(None)
   }
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
-    let rects = Layout::default()
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let memory_rect = HomeLayout::new(area).memory;
+    let block = Block::bordered().title("Memory");
+    let inner = block.inner(memory_rect);
+    frame.render_widget(block, memory_rect);
+
+    // The swap details take at most half of the widget, below the chart
+    let swap_rows = self.swap_rows();
+    let swap_height = (swap_rows.len() as u16 + 1).min(inner.height / 2);
+    let sections = Layout::default()
       .direction(Direction::Vertical)
-      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
-      .split(area);
-    let top_row = rects[0];
-    let top_row_rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
-      .split(top_row);
-    let memory_rect = top_row_rects[1];
+      .constraints(vec![Constraint::Min(0), Constraint::Length(swap_height)])
+      .split(inner);
 
     let x_axis = Axis::default().style(Style::default().white()).bounds([0.0, 100.0]);
     let y_axis = Axis::default().style(Style::default().white()).bounds([0.0, 100.0]);
@@ -126,13 +231,13 @@ impl Component for MemoryComponent {
       .data(swap_data);
 
     let chart = Chart::new(vec![ram_data_set, swap_data_set])
-      .block(Block::bordered().title("Memory"))
       .x_axis(x_axis)
       .y_axis(y_axis)
       .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
       .legend_position(Some(LegendPosition::TopRight));
 
-    frame.render_widget(chart, memory_rect);
+    frame.render_widget(chart, sections[0]);
+    self.draw_swap_section(frame, sections[1], swap_rows);
 
     Ok(())
   }
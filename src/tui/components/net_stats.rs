@@ -0,0 +1,110 @@
+use std::{collections::VecDeque, time::Instant};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::net_stats::NetStatsDataCollection,
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame},
+};
+
+const LABEL_WIDTH: u16 = 18;
+const RATE_WIDTH: u16 = 9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetStatsComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: NetStatsDataCollection,
+  // Rate history per counter, scaled by 100 so fractional rates still show up in the sparkline
+  history: Vec<VecDeque<u64>>,
+  mode: Mode,
+}
+
+impl Default for NetStatsComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl NetStatsComponent {
+  pub const WINDOW_SIZE: usize = 30;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      history: Vec::new(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: NetStatsDataCollection) {
+    // The set of counters is fixed, it only changes if the kernel stops exposing one of them
+    if self.history.len() != new_data.len() {
+      self.history = vec![VecDeque::with_capacity(Self::WINDOW_SIZE); new_data.len()];
+    }
+    for (history, counter) in self.history.iter_mut().zip(new_data.iter()) {
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back((counter.rate * 100.0).round() as u64);
+    }
+    self.collected_data = new_data;
+  }
+}
+
+impl Component for NetStatsComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.net_stats {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let stats_area = HomeLayout::new(area).net_stats;
+    let block = Block::bordered().title("TCP/IP Stack (per sec)");
+    let inner = block.inner(stats_area);
+    frame.render_widget(block, stats_area);
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); self.collected_data.len()])
+      .split(inner);
+
+    for ((counter, history), row) in self.collected_data.iter().zip(self.history.iter_mut()).zip(rows.iter()) {
+      let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Length(LABEL_WIDTH), Constraint::Length(RATE_WIDTH), Constraint::Min(0)])
+        .split(*row);
+
+      // Any non zero rate for these counters is worth noticing
+      let rate_style = if counter.rate > 0.0 { Style::default().yellow().bold() } else { Style::default().dark_gray() };
+
+      frame.render_widget(Paragraph::new(counter.label.clone()), columns[0]);
+      frame.render_widget(Paragraph::new(format!("{:>7.1}", counter.rate)).style(rate_style), columns[1]);
+      frame.render_widget(
+        Sparkline::default().data(history.make_contiguous()).style(Style::default().yellow()),
+        columns[2],
+      );
+    }
+
+    Ok(())
+  }
+}
@@ -11,8 +11,9 @@ use serde::{Deserialize, Serialize};
 use super::Component;
 use crate::{
   configuration::app_configuration::Config,
-  data_services::network::{NetworkData, NetworkDataCollection},
-  tui::{action::Action, ui::Frame},
+  data_services::network::{NetworkData, NetworkDataCollection, OperState},
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame},
+  utils::format_bytes,
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -21,6 +22,7 @@ pub struct NetworkViewModel {
   transmitted: VecDeque<u64>,
   total_transmitted: u64,
   total_received: u64,
+  interfaces: NetworkDataCollection,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +30,7 @@ pub struct NetworkComponent {
   app_start_time: Instant,
   render_start_time: Instant,
   network_view_model: NetworkViewModel,
+  mode: Mode,
 }
 
 impl Default for NetworkComponent {
@@ -48,7 +51,9 @@ impl NetworkComponent {
         transmitted: VecDeque::with_capacity(NetworkComponent::WIND),
         total_transmitted: 0,
         total_received: 0,
+        interfaces: Vec::new(),
       },
+      mode: Mode::Home,
     }
   }
 
@@ -71,52 +76,99 @@ impl NetworkComponent {
 
     self.network_view_model.total_transmitted = new_data.iter().map(|c| c.total_transmitted).sum();
     self.network_view_model.total_received = new_data.iter().map(|c| c.total_received).sum();
+
+    self.network_view_model.interfaces = new_data;
+    self.network_view_model.interfaces.sort_by(|a, b| a.interface_name.cmp(&b.interface_name));
+  }
+}
+
+impl From<&NetworkData> for Row<'static> {
+  fn from(val: &NetworkData) -> Self {
+    let state_style = match val.link.oper_state {
+      OperState::Up => Style::default().green(),
+      OperState::Down | OperState::LowerLayerDown | OperState::NotPresent => Style::default().red(),
+      _ => Style::default().yellow(),
+    };
+    let link = match (val.link.speed_mbps, &val.link.duplex) {
+      (Some(speed), Some(duplex)) => format!("{}Mb/s {}", speed, duplex),
+      (Some(speed), None) => format!("{}Mb/s", speed),
+      _ => "-".to_string(),
+    };
+    // Prefer an IPv4 address since it is usually the one people look for
+    let address = val
+      .addresses
+      .iter()
+      .find(|a| a.address.is_ipv4())
+      .or(val.addresses.first())
+      .map_or_else(|| "-".to_string(), ToString::to_string);
+
+    Row::new(vec![
+      Cell::from(val.interface_name.clone()),
+      Cell::from(val.link.oper_state.to_string()).style(state_style),
+      Cell::from(val.link.kind.to_string()),
+      Cell::from(link),
+      Cell::from(val.link.mtu.map_or_else(|| "-".to_string(), |mtu| mtu.to_string())),
+      Cell::from(address),
+      Cell::from(format_bytes(val.received)),
+      Cell::from(format_bytes(val.transmitted)),
+    ])
   }
 }
 
 impl NetworkData {
+  fn headers() -> Vec<&'static str> {
+    vec!["Iface", "State", "Kind", "Link", "MTU", "Address", "RX", "TX"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(10),
+      Constraint::Length(6),
+      Constraint::Length(8),
+      Constraint::Length(14),
+      Constraint::Length(5),
+      Constraint::Min(18),
+      Constraint::Length(10),
+      Constraint::Length(10),
+    ]
+  }
 }
 
 impl Component for NetworkComponent {
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    if let Action::DataUpdate(data) = action {
-      match data.networks {
-        Some(d) => self.update_data_stats(d),
-        None => {
-          log::debug!("Received Action with no data.")
-        },
-      }
+    match action {
+      Action::DataUpdate(data) => {
+        match data.networks {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
     }
     Ok(None)
   }
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
-    let rects = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints(vec![
-        Constraint::Percentage(100), // Top row spans whole width
-        Constraint::Percentage(50),  // Bottom row split 50/50
-      ])
-      .split(area);
-
-    let bottom_row_rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(vec![Constraint::Percentage(33), Constraint::Percentage(33), Constraint::Percentage(33)])
-      .split(rects[1]);
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
 
-    let network_area = bottom_row_rects[2];
+    let network_area = HomeLayout::new(area).network;
 
     let outer_block = Block::bordered().title("Network");
     let inner = outer_block.inner(network_area);
     let inner_split = Layout::default()
       .direction(Direction::Vertical)
-      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+      .constraints(vec![Constraint::Percentage(25), Constraint::Percentage(25), Constraint::Percentage(50)])
       .split(inner);
 
     // TODO Value Scaling and Units
     let max_spark_value = 10000;
     let continuous_rx_values = self.network_view_model.received.make_contiguous();
-    let rx_title = format!("Received - {} bytes", self.network_view_model.total_received);
+    let rx_title = format!("Received - {}", format_bytes(self.network_view_model.total_received));
     let rx_spark = Sparkline::default()
       .block(Block::new().title(rx_title))
       .data(continuous_rx_values)
@@ -125,7 +177,7 @@ impl Component for NetworkComponent {
       .style(Style::default().red().black());
 
     let continuous_tx_values = self.network_view_model.transmitted.make_contiguous();
-    let tx_title = format!("Transmitted - {} bytes", self.network_view_model.total_transmitted);
+    let tx_title = format!("Transmitted - {}", format_bytes(self.network_view_model.total_transmitted));
     let tx_spark = Sparkline::default()
       .block(Block::new().title(tx_title))
       .data(continuous_tx_values)
@@ -137,6 +189,15 @@ impl Component for NetworkComponent {
     frame.render_widget(rx_spark, inner_split[0]);
     frame.render_widget(tx_spark.clone(), inner_split[1]);
 
+    let rows: Vec<Row> = self.network_view_model.interfaces.iter().map(Into::into).collect();
+    let header = Row::new(NetworkData::headers()).style(Style::default().bold().underlined());
+    let interfaces_table = Table::new(rows, NetworkData::column_widths())
+      .block(Block::new().title("Interfaces"))
+      .column_spacing(1)
+      .style(Style::default().white())
+      .header(header);
+    frame.render_widget(interfaces_table, inner_split[2]);
+
     Ok(())
   }
 }
@@ -0,0 +1,269 @@
+use std::{cmp::Ordering, time::Instant};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+use strum::{Display, EnumIter, IntoEnumIterator};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::Component;
+use crate::{
+  data_services::{
+    rapl::{RaplData, RaplUnavailable},
+    wakeups::{estimated_power_w, ProcessWakeups, ProcessWakeupsCollection},
+  },
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
+  utils::format_duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter)]
+pub enum WakeupSortColumn {
+  #[default]
+  #[strum(serialize = "Wakeups/s")]
+  Wakeups,
+  // Power estimates are proportional to CPU usage, so this also ranks by estimated power
+  #[strum(serialize = "CPU%")]
+  CpuUsage,
+  #[strum(serialize = "CPU time")]
+  CpuTime,
+}
+
+impl WakeupSortColumn {
+  fn compare(&self, a: &ProcessWakeups, b: &ProcessWakeups) -> Ordering {
+    match self {
+      WakeupSortColumn::Wakeups => a.wakeups_per_sec.total_cmp(&b.wakeups_per_sec),
+      WakeupSortColumn::CpuUsage => a.cpu_percent.total_cmp(&b.cpu_percent),
+      WakeupSortColumn::CpuTime => a.cpu_time.cmp(&b.cpu_time),
+    }
+  }
+
+  fn next(self) -> Self {
+    WakeupSortColumn::iter().cycle().skip_while(|column| *column != self).nth(1).unwrap_or_default()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct OverviewComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  command_tx: Option<UnboundedSender<Action>>,
+  // Processes that woke up or ran during the last interval, in display order
+  collected_data: ProcessWakeupsCollection,
+  total_cpu_percent: f64,
+  package_power_w: Option<f64>,
+  rapl_unavailable: Option<RaplUnavailable>,
+  sort_column: WakeupSortColumn,
+  selection: TableSelection,
+  // Rows are re-ranked on every refresh, so the selection follows a PID rather than a row
+  selected_pid: Option<u32>,
+  mode: Mode,
+}
+
+impl Default for OverviewComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl OverviewComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      command_tx: None,
+      collected_data: [].to_vec(),
+      total_cpu_percent: 0.0,
+      package_power_w: None,
+      rapl_unavailable: None,
+      sort_column: WakeupSortColumn::default(),
+      selection: TableSelection::default(),
+      selected_pid: None,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_rapl(&mut self, rapl: &RaplData) {
+    self.package_power_w = rapl.package_power_w();
+    self.rapl_unavailable = rapl.unavailable.clone();
+  }
+
+  fn update_data_stats(&mut self, new_data: ProcessWakeupsCollection) {
+    self.total_cpu_percent = new_data.iter().map(|process| process.cpu_percent).sum();
+    self.collected_data =
+      new_data.into_iter().filter(|process| process.wakeups_per_sec > 0.0 || process.cpu_percent > 0.0).collect();
+    self.sort();
+  }
+
+  fn sort(&mut self) {
+    // Busiest first, ties broken by PID so rows do not jump around
+    self.collected_data.sort_by(|a, b| self.sort_column.compare(b, a).then(a.pid.cmp(&b.pid)));
+    self.sync_selection();
+  }
+
+  /// Points the table selection at the row of the selected PID, keeping the current row if the process went idle.
+  fn sync_selection(&mut self) {
+    match self.selected_pid.and_then(|pid| self.collected_data.iter().position(|p| p.pid == pid)) {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.collected_data.len());
+        self.selected_pid = self.selection.selected().map(|i| self.collected_data[i].pid);
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if down {
+      self.selection.next(self.collected_data.len());
+    } else {
+      self.selection.previous(self.collected_data.len());
+    }
+    self.selected_pid = self.selection.selected().map(|i| self.collected_data[i].pid);
+  }
+
+  /// Jumps to the selected process in the process table.
+  fn select_process(&mut self) -> Result<()> {
+    let Some(pid) = self.selected_pid else {
+      return Ok(());
+    };
+    if let Some(tx) = &self.command_tx {
+      tx.send(Action::SelectProcess(pid))?;
+      tx.send(Action::SwitchMode(Mode::Home))?;
+    }
+    Ok(())
+  }
+
+  fn summary(&self) -> Line<'static> {
+    let wakeups: f64 = self.collected_data.iter().map(|process| process.wakeups_per_sec).sum();
+    let mut spans = vec![Span::from(format!("{:.1} wakeups/s  {:.1}% CPU  ", wakeups, self.total_cpu_percent))];
+    match (self.package_power_w, &self.rapl_unavailable) {
+      (Some(power), _) => spans.push(Span::from(format!("Package {:.2} W", power)).magenta()),
+      (None, Some(unavailable)) => {
+        spans.push(Span::from(format!("Power estimates unavailable: {}", unavailable)).yellow())
+      },
+      (None, None) => spans.push(Span::from("Measuring package power...").dark_gray()),
+    }
+    Line::from(spans)
+  }
+
+  fn row(&self, process: &ProcessWakeups) -> Row<'static> {
+    let power = self.package_power_w.map_or_else(
+      || "-".to_string(),
+      |package| format!("{:.2} W", estimated_power_w(process.cpu_percent, self.total_cpu_percent, package)),
+    );
+    Row::new(vec![
+      Cell::from(power).magenta(),
+      Cell::from(format!("{:>9.1}", process.wakeups_per_sec)),
+      Cell::from(format!("{:>9.1}", process.preemptions_per_sec)),
+      Cell::from(format!("{:>6.1}", process.cpu_percent)),
+      Cell::from(format_duration(process.cpu_time)),
+      Cell::from(process.pid.to_string()),
+      Cell::from(process.name.clone()),
+    ])
+  }
+}
+
+impl Component for OverviewComponent {
+  fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+    self.command_tx = Some(tx);
+    Ok(())
+  }
+
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        if let Some(rapl) = &data.rapl {
+          self.update_rapl(rapl);
+        }
+        match data.wakeups {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Overview => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Overview => self.scroll(false),
+      Action::CycleSort if self.mode == Mode::Overview => {
+        self.sort_column = self.sort_column.next();
+        self.sort();
+      },
+      Action::Select if self.mode == Mode::Overview => self.select_process()?,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Overview {
+      return Ok(());
+    }
+
+    let block = Block::bordered().title(format!("Overview - sorted by {} ▼", self.sort_column));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(2), Constraint::Min(0)])
+      .split(inner);
+    frame.render_widget(Paragraph::new(self.summary()), rows[0]);
+
+    let table_rows = self.collected_data.iter().map(|process| self.row(process)).collect_vec();
+    let header = Row::new(vec!["Power est.", "Wakeups/s", "Preempt/s", "CPU%", "CPU time", "PID", "Name"])
+      .style(Style::default().bold().underlined());
+    let table = Table::new(table_rows, vec![
+      Constraint::Length(10),
+      Constraint::Length(9),
+      Constraint::Length(9),
+      Constraint::Length(6),
+      Constraint::Length(8),
+      Constraint::Length(8),
+      Constraint::Min(16),
+    ])
+    .column_spacing(2)
+    .header(header)
+    .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, rows[1], self.selection.state_mut());
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn process(pid: u32, wakeups_per_sec: f64) -> ProcessWakeups {
+    ProcessWakeups {
+      pid,
+      name: format!("process-{}", pid),
+      wakeups_per_sec,
+      preemptions_per_sec: 0.0,
+      cpu_percent: 0.0,
+      cpu_time: Duration::ZERO,
+    }
+  }
+
+  #[test]
+  fn test_selection_follows_process() {
+    let mut overview = OverviewComponent::new();
+    overview.update_data_stats(vec![process(1, 50.0), process(2, 10.0)]);
+    overview.scroll(true);
+    overview.scroll(true);
+    assert_eq!(overview.selected_pid, Some(2));
+
+    // PID 2 now wakes up the most and is ranked first
+    overview.update_data_stats(vec![process(1, 50.0), process(2, 80.0)]);
+    assert_eq!(overview.collected_data.iter().map(|p| p.pid).collect_vec(), vec![2, 1]);
+    assert_eq!(overview.selection.selected(), Some(0));
+    assert_eq!(overview.selected_pid, Some(2));
+
+    // Once it goes idle the selection stays on the same row
+    overview.update_data_stats(vec![process(1, 50.0), process(2, 0.0)]);
+    assert_eq!(overview.selected_pid, Some(1));
+  }
+}
@@ -0,0 +1,237 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::pressure::{PressureData, PressureResource, PressureStat, ResourcePressure},
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame, widgets::usage::alert_style},
+};
+
+const LABEL_WIDTH: u16 = 7;
+const VALUE_WIDTH: u16 = 6;
+// Label, some and full averages and the stall share of the last interval
+const TABLE_COLUMNS: u16 = 8;
+
+/// Colors a share of time stalled in percent. A few percent is normal under load, above a quarter of the time the
+/// affected tasks are mostly waiting.
+pub(crate) fn pressure_style(percent: f64) -> Style {
+  if percent > 0.0 {
+    alert_style(percent, 5.0, 25.0)
+  } else {
+    Style::default().dark_gray()
+  }
+}
+
+fn value_cell(percent: Option<f64>) -> Cell<'static> {
+  match percent {
+    Some(percent) => Cell::from(format!("{:>6.2}", percent)).style(pressure_style(percent)),
+    None => Cell::from(format!("{:>6}", "-")).dark_gray(),
+  }
+}
+
+fn stat_cells(stat: Option<&PressureStat>) -> [Cell<'static>; 3] {
+  [
+    value_cell(stat.map(|stat| stat.avg10)),
+    value_cell(stat.map(|stat| stat.avg60)),
+    value_cell(stat.map(|stat| stat.avg300)),
+  ]
+}
+
+impl From<&ResourcePressure> for Row<'static> {
+  fn from(val: &ResourcePressure) -> Self {
+    let mut cells = vec![Cell::from(val.resource.to_string())];
+    cells.extend(stat_cells(Some(&val.some)));
+    cells.extend(stat_cells(val.full.as_ref()));
+    cells.push(value_cell(val.some.stall_percent));
+    Row::new(cells)
+  }
+}
+
+impl ResourcePressure {
+  fn headers() -> Vec<String> {
+    // Right aligned like the values below them
+    ["", "some10", "60", "300", "full10", "60", "300", "stall"].iter().map(|header| format!("{:>6}", header)).collect()
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    let mut widths = vec![Constraint::Length(LABEL_WIDTH)];
+    widths.extend(std::iter::repeat_n(Constraint::Length(VALUE_WIDTH), TABLE_COLUMNS as usize - 1));
+    widths
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PressureComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: PressureData,
+  // Stall share of each interval in hundredths of a percent, so low pressure still shows up in the sparkline
+  history: HashMap<PressureResource, VecDeque<u64>>,
+  // Show the top level cgroups instead of the system-wide pressure
+  show_cgroups: bool,
+  mode: Mode,
+}
+
+impl Default for PressureComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl PressureComponent {
+  pub const WINDOW_SIZE: usize = 60;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: PressureData::default(),
+      history: HashMap::new(),
+      show_cgroups: false,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: PressureData) {
+    for resource in &new_data.system {
+      let Some(stall_percent) = resource.some.stall_percent else {
+        continue;
+      };
+      let history = self.history.entry(resource.resource).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back((stall_percent * 100.0).round() as u64);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn draw_system(&mut self, frame: &mut Frame<'_>, area: Rect) {
+    if self.collected_data.system.is_empty() {
+      frame
+        .render_widget(Paragraph::new("PSI is not available, the kernel needs CONFIG_PSI and psi=1").dark_gray(), area);
+      return;
+    }
+    let table_width = LABEL_WIDTH + (TABLE_COLUMNS - 1) * (VALUE_WIDTH + 1);
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Length(table_width), Constraint::Length(1), Constraint::Min(0)])
+      .split(area);
+
+    let rows = self.collected_data.system.iter().map(Row::from).collect_vec();
+    let table = Table::new(rows, ResourcePressure::column_widths())
+      .column_spacing(1)
+      .header(Row::new(ResourcePressure::headers()).style(Style::default().bold()));
+    frame.render_widget(table, columns[0]);
+
+    // One sparkline per table row, below the header line
+    let sparkline_rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); self.collected_data.system.len() + 1])
+      .split(columns[2]);
+    for (resource, row) in self.collected_data.system.iter().zip(sparkline_rows.iter().skip(1)) {
+      let Some(history) = self.history.get_mut(&resource.resource) else {
+        continue;
+      };
+      let style = pressure_style(resource.some.avg10);
+      frame.render_widget(Sparkline::default().data(history.make_contiguous()).style(style), *row);
+    }
+  }
+
+  fn draw_cgroups(&self, frame: &mut Frame<'_>, area: Rect) {
+    if self.collected_data.cgroups.is_empty() {
+      frame.render_widget(Paragraph::new("No top level cgroups with pressure files found").dark_gray(), area);
+      return;
+    }
+    let rows = self
+      .collected_data
+      .cgroups
+      .iter()
+      .map(|cgroup| {
+        let mut cells = vec![Cell::from(cgroup.path.clone())];
+        cells.extend([PressureResource::Cpu, PressureResource::Memory, PressureResource::Io].map(|resource| {
+          value_cell(
+            cgroup.resources.iter().find(|pressure| pressure.resource == resource).map(|pressure| pressure.some.avg10),
+          )
+        }));
+        Row::new(cells)
+      })
+      .collect_vec();
+    let widths = vec![
+      Constraint::Min(12),
+      Constraint::Length(VALUE_WIDTH),
+      Constraint::Length(VALUE_WIDTH),
+      Constraint::Length(VALUE_WIDTH),
+    ];
+    let table = Table::new(rows, widths).column_spacing(1).header(
+      Row::new(vec![
+        "Cgroup".to_string(),
+        format!("{:>6}", "CPU"),
+        format!("{:>6}", "Memory"),
+        format!("{:>6}", "I/O"),
+      ])
+      .style(Style::default().bold()),
+    );
+    frame.render_widget(table, area);
+  }
+}
+
+impl Component for PressureComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.pressure {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ToggleView if self.mode == Mode::Home => self.show_cgroups = !self.show_cgroups,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let pressure_area = HomeLayout::new(area).pressure;
+    let title = if self.show_cgroups { "Pressure by cgroup (some avg10 %)" } else { "Pressure (% of time stalled)" };
+    let block = Block::bordered().title(title);
+    let inner = block.inner(pressure_area);
+    frame.render_widget(block, pressure_area);
+
+    if self.show_cgroups {
+      self.draw_cgroups(frame, inner);
+    } else {
+      self.draw_system(frame, inner);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_pressure_style() {
+    assert_eq!(pressure_style(0.0), Style::default().dark_gray());
+    assert_eq!(pressure_style(1.2), Style::default().green());
+    assert_eq!(pressure_style(12.0), Style::default().yellow());
+    assert_eq!(pressure_style(60.0), Style::default().red().bold());
+  }
+}
@@ -1,4 +1,7 @@
-use std::time::Instant;
+use std::{
+  collections::{HashMap, HashSet},
+  time::Instant,
+};
 
 use color_eyre::{
   eyre::{Ok, Result},
@@ -8,15 +11,81 @@ use ratatui::{prelude::*, widgets::*};
 
 use super::Component;
 use crate::{
-  data_services::processes::{ProcessData, ProcessDataCollection},
-  tui::{action::Action, ui::Frame},
+  data_services::{
+    cgroups::{Attribution, CgroupData},
+    processes::{ProcessData, ProcessDataCollection},
+  },
+  tui::{action::Action, layout::HomeLayout, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
 };
 
+/// Limits the process table to the processes of a cgroup subtree or of a single unit or container.
+#[derive(Debug, Clone, PartialEq)]
+enum ProcessFilter {
+  // Path of the cgroup, processes of its descendants are included
+  Cgroup(String),
+  Attribution(Option<Attribution>),
+}
+
+impl ProcessFilter {
+  fn describe(&self) -> String {
+    match self {
+      ProcessFilter::Cgroup(path) => path.clone(),
+      ProcessFilter::Attribution(attribution) => attribution_label(attribution),
+    }
+  }
+}
+
+/// The processes of one unit or container, shown when the table is grouped.
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessGroup {
+  attribution: Option<Attribution>,
+  processes: usize,
+  cpu_usage: f32,
+}
+
+impl From<&ProcessGroup> for Row<'static> {
+  fn from(val: &ProcessGroup) -> Self {
+    Row::new(vec![attribution_label(&val.attribution), val.processes.to_string(), format!("{:.3}", val.cpu_usage)])
+  }
+}
+
+fn attribution_label(attribution: &Option<Attribution>) -> String {
+  attribution.as_ref().map_or_else(|| "-".to_string(), Attribution::to_string)
+}
+
+/// Sums up the processes per unit or container, the busiest first.
+fn group_processes(processes: &[ProcessData]) -> Vec<ProcessGroup> {
+  let mut groups: HashMap<Option<Attribution>, ProcessGroup> = HashMap::new();
+  for process in processes {
+    let group = groups
+      .entry(process.attribution.clone())
+      .or_insert_with(|| ProcessGroup { attribution: process.attribution.clone(), processes: 0, cpu_usage: 0.0 });
+    group.processes += 1;
+    group.cpu_usage += process.cpu_usage;
+  }
+  let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+  groups.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then_with(|| a.attribution.cmp(&b.attribution)));
+  groups
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProcessTable {
   app_start_time: Instant,
   render_start_time: Instant,
   collected_data: ProcessDataCollection,
+  // Every process, `collected_data` only holds the ones passing the filter
+  all_processes: ProcessDataCollection,
+  cgroups: CgroupData,
+  filter: Option<ProcessFilter>,
+  // Show one row per unit or container instead of one per process
+  grouped: bool,
+  groups: Vec<ProcessGroup>,
+  mode: Mode,
+  selection: TableSelection,
+  // The selection follows a PID rather than a row so it stays on the same process as the table refreshes
+  selected_pid: Option<u32>,
+  // Likewise the grouped table follows a unit or container, `Some(None)` is the group of unattributed processes
+  selected_group: Option<Option<Attribution>>,
 }
 
 impl From<ProcessData> for Row<'static> {
@@ -30,6 +99,7 @@ impl From<ProcessData> for Row<'static> {
       val.name,
       val.status,
       format!("{:.3}", val.cpu_usage),
+      attribution_label(&val.attribution),
     ])
   }
 }
@@ -37,7 +107,7 @@ impl From<ProcessData> for Row<'static> {
 impl ProcessData {
   // TODO: Better way to create headers from struct
   fn headers() -> Vec<&'static str> {
-    vec!["PID", "Parent", "Name", "Status", "CPU Usage"]
+    vec!["PID", "Parent", "Name", "Status", "CPU Usage", "Unit / Container"]
   }
 
   fn column_widths() -> Vec<Constraint> {
@@ -46,8 +116,9 @@ impl ProcessData {
       Constraint::Length(4),  // PID column minimum width
       Constraint::Length(6),  // Parent column minimum width
       Constraint::Length(12), // Name column minimum width
-      Constraint::Length(12), // Status column minimum width
+      Constraint::Length(9),  // Status column minimum width
       Constraint::Length(10), // CPU Usage column minimum width
+      Constraint::Min(10),    // Unit / Container column minimum width
     ]
   }
 }
@@ -60,64 +131,289 @@ impl Default for ProcessTable {
 
 impl ProcessTable {
   pub fn new() -> Self {
-    Self { app_start_time: Instant::now(), render_start_time: Instant::now(), collected_data: [].to_vec() }
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      all_processes: [].to_vec(),
+      cgroups: CgroupData::default(),
+      filter: None,
+      grouped: false,
+      groups: Vec::new(),
+      mode: Mode::Home,
+      selection: TableSelection::default(),
+      selected_pid: None,
+      selected_group: None,
+    }
   }
 
   fn update_data_stats(&mut self, new_data: ProcessDataCollection) {
-    self.collected_data = new_data;
+    self.all_processes = new_data;
+    self.all_processes.sort_by_key(|p| p.pid);
+    self.apply_filter();
+  }
+
+  fn apply_filter(&mut self) {
+    self.collected_data = match &self.filter {
+      Some(ProcessFilter::Cgroup(path)) => {
+        let members: HashSet<u32> = self.cgroups.member_pids(path);
+        self.all_processes.iter().filter(|p| members.contains(&p.pid)).cloned().collect()
+      },
+      Some(ProcessFilter::Attribution(attribution)) => {
+        self.all_processes.iter().filter(|p| p.attribution == *attribution).cloned().collect()
+      },
+      None => self.all_processes.clone(),
+    };
+    if self.grouped {
+      self.groups = group_processes(&self.collected_data);
+      self.sync_group_selection();
+    } else {
+      self.sync_selection();
+    }
+  }
+
+  fn set_filter(&mut self, filter: Option<ProcessFilter>) {
+    self.filter = filter;
+    self.apply_filter();
+  }
+
+  fn toggle_grouping(&mut self) {
+    self.grouped = !self.grouped;
+    self.selection.select(None);
+    self.selected_group = None;
+    self.apply_filter();
+  }
+
+  /// Leaves the grouped table showing only the processes of the selected group.
+  fn filter_selected_group(&mut self) {
+    let Some(attribution) = self.selected_group.take() else {
+      return;
+    };
+    self.grouped = false;
+    self.selection.select(None);
+    self.set_filter(Some(ProcessFilter::Attribution(attribution)));
+  }
+
+  /// Points the selection at the row of the selected group, as groups are re-sorted by CPU usage on every refresh.
+  fn sync_group_selection(&mut self) {
+    let index = self
+      .selected_group
+      .as_ref()
+      .and_then(|attribution| self.groups.iter().position(|group| group.attribution == *attribution));
+    match index {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.groups.len());
+        self.selected_group = self.selection.selected().map(|i| self.groups[i].attribution.clone());
+      },
+    }
+  }
+
+  /// Points the table selection at the row of the selected PID, keeping the current row if the process exited.
+  fn sync_selection(&mut self) {
+    match self.selected_pid.and_then(|pid| self.collected_data.iter().position(|p| p.pid == pid)) {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.collected_data.len());
+        self.selected_pid = self.selection.selected().map(|i| self.collected_data[i].pid);
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if self.grouped {
+      if down {
+        self.selection.next(self.groups.len());
+      } else {
+        self.selection.previous(self.groups.len());
+      }
+      self.selected_group = self.selection.selected().map(|i| self.groups[i].attribution.clone());
+      return;
+    }
+    if down {
+      self.selection.next(self.collected_data.len());
+    } else {
+      self.selection.previous(self.collected_data.len());
+    }
+    self.selected_pid = self.selection.selected().map(|i| self.collected_data[i].pid);
+  }
+}
+
+impl ProcessTable {
+  fn draw_groups(&mut self, frame: &mut Frame<'_>, area: Rect, title: String) {
+    let rows: Vec<Row> = self.groups.iter().map(Row::from).collect();
+    let widths = vec![Constraint::Min(20), Constraint::Length(9), Constraint::Length(10)];
+    let header = Row::new(vec!["Unit / Container", "Processes", "CPU Usage"])
+      .style(Style::default().bold().underlined())
+      .bottom_margin(1);
+
+    let table = Table::new(rows, widths)
+      .block(Block::bordered().title(format!("{} by unit or container (Enter filters)", title)))
+      .column_spacing(3)
+      .style(Style::default().white())
+      .header(header)
+      .highlight_style(Style::default().reversed())
+      .highlight_symbol(">>");
+
+    frame.render_stateful_widget(table, HomeLayout::new(area).processes, self.selection.state_mut());
   }
 }
 
 impl Component for ProcessTable {
   fn update(&mut self, action: Action) -> Result<Option<Action>> {
-    if let Action::DataUpdate(data) = action {
-      match data.processes {
-        Some(d) => self.update_data_stats(d),
-        None => {
-          log::debug!("Received Action with no data.")
-        },
-      }
+    match action {
+      Action::DataUpdate(data) => {
+        // Membership is refreshed before the processes so both come from the same collection
+        if let Some(cgroups) = data.cgroups {
+          self.cgroups = cgroups;
+        }
+        match data.processes {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Home => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Home => self.scroll(false),
+      Action::SelectProcess(pid) => {
+        // The process might be filtered out or hidden in a group
+        self.grouped = false;
+        self.selected_pid = Some(pid);
+        self.set_filter(None);
+      },
+      Action::FilterCgroup(path) => self.set_filter(Some(ProcessFilter::Cgroup(path))),
+      Action::ClearFilter if self.mode == Mode::Home => self.set_filter(None),
+      Action::ToggleGrouping if self.mode == Mode::Home => self.toggle_grouping(),
+      Action::Select if self.mode == Mode::Home && self.grouped => self.filter_selected_group(),
+      _ => {},
     }
     Ok(None)
   }
 
   fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
-    // TODO: DRY for Layout
-    // Option 1: Each component creates Layout the same way and each uses a different rect (rects[0], ...)
-    // Option 2: App creates Layout and passes rects down to components, each component knows which rect to use
-    // Option 3: App creates Layout and passes a single rect down that the component uses
-    let rects = Layout::default()
-      .direction(Direction::Vertical)
-      .constraints(vec![
-        Constraint::Percentage(100), // Top row spans whole width
-        Constraint::Percentage(50),  // Bottom row split 50/50
-      ])
-      .split(area);
-
-    let bottom_row_rects = Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(vec![
-        Constraint::Percentage(33), // Left half
-        Constraint::Percentage(33), // Right half
-        Constraint::Percentage(33), // Right half
-      ])
-      .split(rects[1]);
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
 
+    let title = match &self.filter {
+      Some(filter) => format!("Processes in {} ({}, Esc shows all)", filter.describe(), self.collected_data.len()),
+      None => "Processes".to_string(),
+    };
+    if self.grouped {
+      self.draw_groups(frame, area, title);
+      return Ok(());
+    }
     // TODO: Do we need to clone?
     let rows: Vec<Row> = self.collected_data.clone().into_iter().map(Into::into).collect();
     let col_widths = ProcessData::column_widths();
     let header = Row::new(ProcessData::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
 
     let table = Table::new(rows, col_widths)
-      .block(Block::bordered().title("Processes"))
-      .column_spacing(3)
+      .block(Block::bordered().title(title))
+      .column_spacing(2)
       .style(Style::default().white())
       .header(header)
       .highlight_style(Style::default().reversed())
       .highlight_symbol(">>");
 
-    frame.render_widget(table, bottom_row_rects[0]);
+    frame.render_stateful_widget(table, HomeLayout::new(area).processes, self.selection.state_mut());
 
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  fn process(pid: u32, cpu_usage: f32, unit: Option<&str>) -> ProcessData {
+    ProcessData {
+      pid,
+      parent: None,
+      name: format!("process-{}", pid),
+      status: "Sleep".to_string(),
+      cpu_usage,
+      attribution: unit.map(|unit| Attribution::Unit(unit.to_string())),
+    }
+  }
+
+  fn unit(name: &str) -> Option<Attribution> {
+    Some(Attribution::Unit(name.to_string()))
+  }
+
+  fn group_attributions(table: &ProcessTable) -> Vec<Option<Attribution>> {
+    table.groups.iter().map(|group| group.attribution.clone()).collect()
+  }
+
+  #[test]
+  fn test_group_processes_sums_per_attribution() {
+    let groups = group_processes(&[
+      process(1, 1.0, Some("a.service")),
+      process(2, 4.0, Some("b.service")),
+      process(3, 2.0, Some("a.service")),
+      process(4, 0.5, None),
+    ]);
+    assert_eq!(groups, vec![
+      ProcessGroup { attribution: unit("b.service"), processes: 1, cpu_usage: 4.0 },
+      ProcessGroup { attribution: unit("a.service"), processes: 2, cpu_usage: 3.0 },
+      ProcessGroup { attribution: None, processes: 1, cpu_usage: 0.5 },
+    ]);
+  }
+
+  #[test]
+  fn test_selection_follows_group() {
+    let mut table = ProcessTable::new();
+    table.update_data_stats(vec![process(1, 2.0, Some("a.service")), process(2, 1.0, Some("b.service"))]);
+    table.toggle_grouping();
+    table.scroll(true);
+    table.scroll(true);
+    assert_eq!(table.selected_group, Some(unit("b.service")));
+
+    // b.service becomes the busiest and moves to the top
+    table.update_data_stats(vec![process(1, 2.0, Some("a.service")), process(2, 5.0, Some("b.service"))]);
+    assert_eq!(group_attributions(&table), vec![unit("b.service"), unit("a.service")]);
+    assert_eq!(table.selection.selected(), Some(0));
+    assert_eq!(table.selected_group, Some(unit("b.service")));
+  }
+
+  #[test]
+  fn test_select_group_filters_by_attribution() {
+    let mut table = ProcessTable::new();
+    table.update_data_stats(vec![
+      process(1, 2.0, Some("a.service")),
+      process(2, 1.0, None),
+      process(3, 0.5, Some("a.service")),
+    ]);
+    table.toggle_grouping();
+    table.scroll(true);
+    table.scroll(true);
+    assert_eq!(table.selected_group, Some(None));
+
+    table.filter_selected_group();
+    assert!(!table.grouped);
+    assert_eq!(table.filter, Some(ProcessFilter::Attribution(None)));
+    assert_eq!(table.collected_data.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![2]);
+
+    table.set_filter(Some(ProcessFilter::Attribution(unit("a.service"))));
+    assert_eq!(table.collected_data.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![1, 3]);
+  }
+
+  #[test]
+  fn test_toggle_grouping_restores_processes() {
+    let mut table = ProcessTable::new();
+    table.update_data_stats(vec![process(2, 1.0, Some("a.service")), process(1, 2.0, Some("a.service"))]);
+    table.toggle_grouping();
+    assert_eq!(group_attributions(&table), vec![unit("a.service")]);
+    table.scroll(true);
+    assert_eq!(table.selected_group, Some(unit("a.service")));
+
+    table.toggle_grouping();
+    assert!(!table.grouped);
+    assert_eq!(table.selected_group, None);
+    assert_eq!(table.collected_data.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![1, 2]);
+  }
+}
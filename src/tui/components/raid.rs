@@ -0,0 +1,220 @@
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::raid::{DeviceMapperData, MdArray, RaidData},
+  tui::{
+    action::Action,
+    layout::{alert_area, BlockDevicesLayout},
+    mode::Mode,
+    ui::Frame,
+  },
+  utils::format_bytes,
+};
+
+#[derive(Debug, Clone)]
+pub struct RaidComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: RaidData,
+  mode: Mode,
+}
+
+impl From<&MdArray> for Row<'static> {
+  fn from(val: &MdArray) -> Self {
+    let (state, state_style) = if val.is_degraded() {
+      ("degraded", Style::default().red().bold())
+    } else if !val.active {
+      ("inactive", Style::default().yellow())
+    } else if val.read_only {
+      ("read-only", Style::default().yellow())
+    } else {
+      ("clean", Style::default().green())
+    };
+    let devices = match (val.working_devices, val.total_devices, &val.slot_status) {
+      (Some(working), Some(total), Some(slots)) => format!("{}/{} [{}]", working, total, slots),
+      _ => val.members.len().to_string(),
+    };
+    let members = val
+      .members
+      .iter()
+      .map(|member| {
+        let flag = if member.faulty {
+          "(F)"
+        } else if member.spare {
+          "(S)"
+        } else {
+          ""
+        };
+        format!("{}{}", member.device, flag)
+      })
+      .join(" ");
+    let sync = match &val.sync {
+      Some(sync) => {
+        match sync.percent {
+          Some(percent) => {
+            [
+              Some(format!("{} {:.1}%", sync.action, percent)),
+              sync.speed_kib_per_sec.map(|speed| format!("{}/s", format_bytes(speed * 1024))),
+              sync.finish_minutes.map(|finish| format!("eta {:.0}min", finish)),
+            ]
+            .into_iter()
+            .flatten()
+            .join(" ")
+          },
+          None => format!("{} pending", sync.action),
+        }
+      },
+      None => "-".to_string(),
+    };
+
+    Row::new(vec![
+      Cell::from(val.name.clone()),
+      Cell::from(val.level.clone().unwrap_or_else(|| "-".to_string())),
+      Cell::from(state).style(state_style),
+      Cell::from(devices),
+      Cell::from(format_bytes(val.size_kib * 1024)),
+      Cell::from(members),
+      Cell::from(sync),
+    ])
+  }
+}
+
+impl MdArray {
+  fn headers() -> Vec<&'static str> {
+    vec!["Array", "Level", "State", "Devices", "Size", "Members", "Sync"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(6),
+      Constraint::Length(7),
+      Constraint::Length(9),
+      Constraint::Length(12),
+      Constraint::Length(10),
+      Constraint::Min(16),
+      Constraint::Min(20),
+    ]
+  }
+}
+
+impl From<&DeviceMapperData> for Row<'static> {
+  fn from(val: &DeviceMapperData) -> Self {
+    Row::new(vec![
+      Cell::from(val.device.clone()),
+      Cell::from(val.name.clone()),
+      Cell::from(val.kind.to_string()),
+      Cell::from(format_bytes(val.size_bytes)),
+      Cell::from(val.slaves.join(" ")),
+      if val.suspended { Cell::from("suspended").yellow() } else { Cell::from("active") },
+    ])
+  }
+}
+
+impl DeviceMapperData {
+  fn headers() -> Vec<&'static str> {
+    vec!["Device", "Name", "Type", "Size", "On", "State"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(6),
+      Constraint::Min(16),
+      Constraint::Length(5),
+      Constraint::Length(10),
+      Constraint::Min(10),
+      Constraint::Length(9),
+    ]
+  }
+}
+
+impl Default for RaidComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl RaidComponent {
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: RaidData::default(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: RaidData) {
+    self.collected_data = new_data;
+  }
+
+  /// Draws a banner naming the degraded arrays on top of whatever view is shown.
+  fn draw_degraded_alert(&self, frame: &mut Frame<'_>, area: Rect) {
+    let degraded = self
+      .collected_data
+      .degraded_arrays()
+      .map(|array| format!("{} [{}]", array.name, array.slot_status.as_deref().unwrap_or("?")))
+      .join(", ");
+    if degraded.is_empty() {
+      return;
+    }
+    let text = format!(" RAID DEGRADED: {} ", degraded);
+    let area = alert_area(area, text.chars().count() as u16);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).style(Style::default().white().on_red().bold()), area);
+  }
+}
+
+impl Component for RaidComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.raid {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    self.draw_degraded_alert(frame, area);
+    if self.mode != Mode::BlockDevices {
+      return Ok(());
+    }
+
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+      .split(BlockDevicesLayout::new(area).raid);
+
+    let header = Row::new(MdArray::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
+    let rows: Vec<Row> = self.collected_data.md_arrays.iter().map(Into::into).collect();
+    let md_table = Table::new(rows, MdArray::column_widths())
+      .block(Block::bordered().title("Software RAID (/proc/mdstat)"))
+      .column_spacing(1)
+      .style(Style::default().white())
+      .header(header);
+    frame.render_widget(md_table, columns[0]);
+
+    let header = Row::new(DeviceMapperData::headers()).style(Style::default().bold().underlined()).bottom_margin(1);
+    let rows: Vec<Row> = self.collected_data.device_mappers.iter().map(Into::into).collect();
+    let dm_table = Table::new(rows, DeviceMapperData::column_widths())
+      .block(Block::bordered().title("Device Mapper"))
+      .column_spacing(1)
+      .style(Style::default().white())
+      .header(header);
+    frame.render_widget(dm_table, columns[1]);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,131 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::rapl::{RaplData, RaplZoneData},
+  tui::{action::Action, layout::PowerLayout, mode::Mode, ui::Frame},
+};
+
+const LABEL_WIDTH: u16 = 16;
+const VALUE_WIDTH: u16 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaplComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: RaplData,
+  // Power in mW per zone id, oldest first
+  history: HashMap<String, VecDeque<u64>>,
+  mode: Mode,
+}
+
+impl Default for RaplComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl RaplComponent {
+  pub const WINDOW_SIZE: usize = 120;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: RaplData::default(),
+      history: HashMap::new(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: RaplData) {
+    self.history.retain(|id, _| new_data.zones.iter().any(|zone| zone.id == *id));
+    for zone in &new_data.zones {
+      let Some(power_w) = zone.power_w else { continue };
+      let history = self.history.entry(zone.id.clone()).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back((power_w * 1000.0).round() as u64);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn draw_zone(&mut self, frame: &mut Frame<'_>, row: Rect, zone: &RaplZoneData, max: u64) {
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Length(LABEL_WIDTH), Constraint::Length(VALUE_WIDTH), Constraint::Min(0)])
+      .split(row);
+    // Subzones are part of their package's consumption, so they are indented below it
+    let label = match zone.parent {
+      Some(_) => Line::from(format!("  {}", zone.name)),
+      None => Line::from(zone.name.clone()).bold(),
+    };
+    let value = zone.power_w.map_or_else(|| "-".to_string(), |power| format!("{:>7.2} W", power));
+
+    frame.render_widget(Paragraph::new(label), columns[0]);
+    frame.render_widget(Paragraph::new(value).magenta(), columns[1]);
+    if let Some(history) = self.history.get_mut(&zone.id) {
+      frame.render_widget(Sparkline::default().data(history.make_contiguous()).max(max).magenta(), columns[2]);
+    }
+  }
+}
+
+impl Component for RaplComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.rapl {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Power {
+      return Ok(());
+    }
+
+    let rapl_area = PowerLayout::new(area).rapl;
+    let mut title = vec![Span::from("Power consumption (RAPL)")];
+    if let Some(total) = self.collected_data.package_power_w() {
+      title.push(Span::from(format!(" {:.2} W", total)).magenta().bold());
+    }
+    let block = Block::bordered().title(Line::from(title));
+    if let Some(unavailable) = &self.collected_data.unavailable {
+      frame.render_widget(
+        Paragraph::new(unavailable.to_string()).yellow().wrap(Wrap { trim: true }).block(block),
+        rapl_area,
+      );
+      return Ok(());
+    }
+    let inner = block.inner(rapl_area);
+    frame.render_widget(block, rapl_area);
+
+    let zones = self.collected_data.zones.clone();
+    // All zones share one scale so the subzones read as parts of their package
+    let max = self.history.values().flatten().copied().max().unwrap_or_default();
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(2); zones.len()])
+      .split(inner);
+    for (row, zone) in rows.iter().zip(zones.iter()) {
+      self.draw_zone(frame, *row, zone, max);
+    }
+
+    Ok(())
+  }
+}
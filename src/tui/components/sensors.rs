@@ -0,0 +1,194 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::sensors::{SensorDataCollection, SensorKind, SensorReading},
+  tui::{action::Action, layout::SensorsLayout, mode::Mode, ui::Frame},
+};
+
+const LABEL_WIDTH: u16 = 20;
+const VALUE_WIDTH: u16 = 12;
+// Key of the CPU load history drawn above the sensors, so fan ramps can be compared against it
+const CPU_HISTORY_ID: &str = "cpu";
+
+fn format_reading(kind: SensorKind, value: f64) -> String {
+  match kind {
+    SensorKind::Fan => format!("{:>6.0} {}", value, kind.unit()),
+    SensorKind::Voltage => format!("{:>7.3} {}", value, kind.unit()),
+    SensorKind::Power | SensorKind::Current => format!("{:>7.2} {}", value, kind.unit()),
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorsComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: SensorDataCollection,
+  cpu_usage: f64,
+  // Readings scaled by 1000 per reading id, oldest first
+  history: HashMap<String, VecDeque<u64>>,
+  mode: Mode,
+}
+
+impl Default for SensorsComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SensorsComponent {
+  pub const WINDOW_SIZE: usize = 60;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      cpu_usage: 0.0,
+      history: HashMap::new(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn record(&mut self, id: &str, value: f64) {
+    let history = self.history.entry(id.to_string()).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+    if history.len() == Self::WINDOW_SIZE {
+      history.pop_front();
+    }
+    history.push_back((value.max(0.0) * 1000.0).round() as u64);
+  }
+
+  fn update_data_stats(&mut self, new_data: SensorDataCollection) {
+    let ids: Vec<String> =
+      new_data.iter().flat_map(|chip| chip.readings.iter().map(|reading| reading.id.clone())).collect();
+    self.history.retain(|id, _| id == CPU_HISTORY_ID || ids.contains(id));
+    for reading in new_data.iter().flat_map(|chip| chip.readings.iter()) {
+      self.record(&reading.id, reading.value);
+    }
+    self.collected_data = new_data;
+  }
+
+  fn draw_line(&mut self, frame: &mut Frame<'_>, row: Rect, label: Line<'_>, value: Span<'_>, history_id: &str) {
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Length(LABEL_WIDTH), Constraint::Length(VALUE_WIDTH), Constraint::Min(0)])
+      .split(row);
+    let style = value.style;
+
+    frame.render_widget(Paragraph::new(label), columns[0]);
+    frame.render_widget(Paragraph::new(value), columns[1]);
+    if let Some(history) = self.history.get_mut(history_id) {
+      frame.render_widget(Sparkline::default().data(history.make_contiguous()).style(style), columns[2]);
+    }
+  }
+}
+
+impl Component for SensorsComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        if let Some(cpus) = &data.cpu {
+          if !cpus.is_empty() {
+            self.cpu_usage = cpus.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / cpus.len() as f64;
+            self.record(CPU_HISTORY_ID, self.cpu_usage);
+          }
+        }
+        match data.sensors {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Sensors {
+      return Ok(());
+    }
+
+    let sensors_area = SensorsLayout::new(area).sensors;
+    let block = Block::bordered().title("Fans, Voltages, Power & Currents");
+    let inner = block.inner(sensors_area);
+    frame.render_widget(block, sensors_area);
+
+    let line_count = 1 + self.collected_data.iter().map(|chip| chip.readings.len() + 1).sum::<usize>();
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); line_count.max(2)])
+      .split(inner);
+    let mut rows = rows.iter();
+
+    if let Some(row) = rows.next() {
+      let cpu_usage = self.cpu_usage;
+      self.draw_line(
+        frame,
+        *row,
+        Line::from("CPU load").bold(),
+        Span::from(format!("{:>7.1} %", cpu_usage)).cyan(),
+        CPU_HISTORY_ID,
+      );
+    }
+    if self.collected_data.is_empty() {
+      if let Some(row) = rows.next() {
+        frame.render_widget(Paragraph::new("No fan, voltage or power sensors found").dark_gray(), *row);
+      }
+      return Ok(());
+    }
+
+    let chips = self.collected_data.clone();
+    for chip in &chips {
+      let Some(row) = rows.next() else {
+        break;
+      };
+      frame.render_widget(
+        Paragraph::new(Line::from(vec![
+          Span::from(chip.name.clone()).bold().underlined(),
+          Span::from(format!(" ({})", chip.id)).dark_gray(),
+        ])),
+        *row,
+      );
+      for reading in &chip.readings {
+        let Some(row) = rows.next() else {
+          break;
+        };
+        let style = reading_style(reading);
+        self.draw_line(
+          frame,
+          *row,
+          Line::from(format!("  {}", reading.label)),
+          Span::styled(format_reading(reading.kind, reading.value), style),
+          &reading.id,
+        );
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Flags readings outside the limits set by the driver or firmware, e.g. a stalled fan below its minimum speed.
+fn reading_style(reading: &SensorReading) -> Style {
+  let below_min = reading.min.is_some_and(|min| min > 0.0 && reading.value < min);
+  let above_max = reading.max.is_some_and(|max| max > 0.0 && reading.value > max);
+  if below_min || above_max {
+    Style::default().red().bold()
+  } else {
+    match reading.kind {
+      SensorKind::Fan => Style::default().green(),
+      SensorKind::Voltage => Style::default().yellow(),
+      SensorKind::Power => Style::default().magenta(),
+      SensorKind::Current => Style::default().cyan(),
+    }
+  }
+}
@@ -0,0 +1,159 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Instant,
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::temperatures::{TemperatureData, TemperatureDataCollection},
+  tui::{
+    action::Action,
+    layout::SensorsLayout,
+    mode::Mode,
+    ui::Frame,
+    widgets::{table_selection::TableSelection, usage::threshold_style},
+  },
+};
+
+const LABEL_WIDTH: u16 = 32;
+const VALUE_WIDTH: u16 = 9;
+const LIMITS_WIDTH: u16 = 22;
+
+/// Colors a temperature by how close it is to the sensor's critical point. Sensors without one are compared against
+/// fixed limits that suit most CPUs and drives.
+fn temperature_style(temperature: &TemperatureData) -> Style {
+  match temperature.limit() {
+    Some(limit) => threshold_style(temperature.celsius / limit * 100.0, 80.0, 95.0),
+    None => threshold_style(temperature.celsius, 70.0, 85.0),
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemperatureComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: TemperatureDataCollection,
+  // Whole degrees per sensor id, oldest first
+  history: HashMap<String, VecDeque<u64>>,
+  selection: TableSelection,
+  mode: Mode,
+}
+
+impl Default for TemperatureComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl TemperatureComponent {
+  pub const WINDOW_SIZE: usize = 60;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      history: HashMap::new(),
+      selection: TableSelection::default(),
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: TemperatureDataCollection) {
+    // Forget sensors that went away, e.g. an unplugged drive
+    self.history.retain(|id, _| new_data.iter().any(|temperature| &temperature.id == id));
+    for temperature in &new_data {
+      let history =
+        self.history.entry(temperature.id.clone()).or_insert_with(|| VecDeque::with_capacity(Self::WINDOW_SIZE));
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back(temperature.celsius.max(0.0).round() as u64);
+    }
+    self.collected_data = new_data;
+    self.selection.clamp(self.collected_data.len());
+  }
+}
+
+impl Component for TemperatureComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.temperatures {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      Action::ScrollDown if self.mode == Mode::Sensors => self.selection.next(self.collected_data.len()),
+      Action::ScrollUp if self.mode == Mode::Sensors => self.selection.previous(self.collected_data.len()),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Sensors {
+      return Ok(());
+    }
+
+    let temperatures_area = SensorsLayout::new(area).temperatures;
+    let block = Block::bordered().title(format!("Temperatures ({})", self.collected_data.len()));
+    let inner = block.inner(temperatures_area);
+    frame.render_widget(block, temperatures_area);
+
+    if self.collected_data.is_empty() {
+      frame.render_widget(Paragraph::new("No temperature sensors found").dark_gray(), inner);
+      return Ok(());
+    }
+
+    // Keep the selected sensor on screen when there are more sensors than rows
+    let visible_rows = inner.height as usize;
+    let offset = self.selection.selected().map_or(0, |selected| (selected + 1).saturating_sub(visible_rows));
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); visible_rows])
+      .split(inner);
+
+    for (row, (i, temperature)) in rows.iter().zip(self.collected_data.iter().enumerate().skip(offset)) {
+      let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+          Constraint::Length(LABEL_WIDTH),
+          Constraint::Length(VALUE_WIDTH),
+          Constraint::Length(LIMITS_WIDTH),
+          Constraint::Min(0),
+        ])
+        .split(*row);
+
+      let style = temperature_style(temperature);
+      let label_style =
+        if self.selection.selected() == Some(i) { Style::default().reversed() } else { Style::default() };
+      let limits = match (temperature.high, temperature.critical) {
+        (Some(high), Some(critical)) => format!("high {:.0}° crit {:.0}°", high, critical),
+        (None, Some(critical)) => format!("crit {:.0}°", critical),
+        (Some(high), None) => format!("high {:.0}°", high),
+        (None, None) => String::new(),
+      };
+
+      frame.render_widget(
+        Paragraph::new(format!("{} {}", temperature.chip, temperature.label)).style(label_style),
+        columns[0],
+      );
+      frame.render_widget(Paragraph::new(format!("{:>6.1}°C", temperature.celsius)).style(style.bold()), columns[1]);
+      frame.render_widget(Paragraph::new(limits).dark_gray(), columns[2]);
+      if let Some(history) = self.history.get_mut(&temperature.id) {
+        // Scale the sparkline to the critical point so a flat line near the top means trouble
+        let max = temperature.limit().map_or(100, |limit| limit.round() as u64);
+        frame.render_widget(Sparkline::default().data(history.make_contiguous()).max(max).style(style), columns[3]);
+      }
+    }
+
+    Ok(())
+  }
+}
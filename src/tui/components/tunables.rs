@@ -0,0 +1,276 @@
+use std::{collections::HashMap, time::Instant};
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::{
+    pseudo_fs::PseudoFsRoots,
+    tunables::{get_wifi_tunables, set_tunable, Tunable, TunableCollection, TunableTarget},
+  },
+  tui::{action::Action, mode::Mode, ui::Frame, widgets::table_selection::TableSelection},
+};
+
+impl From<&Tunable> for Row<'static> {
+  fn from(val: &Tunable) -> Self {
+    let status = if val.good { Cell::from("Good").green() } else { Cell::from("Bad").red().bold() };
+    Row::new(vec![
+      status,
+      Cell::from(val.category.to_string()),
+      Cell::from(val.description.clone()),
+      Cell::from(val.current.clone()),
+      Cell::from(if val.good { String::new() } else { val.suggested.clone() }).dark_gray(),
+    ])
+  }
+}
+
+impl Tunable {
+  fn headers() -> Vec<&'static str> {
+    vec!["Status", "Category", "Tunable", "Current", "Suggested"]
+  }
+
+  fn column_widths() -> Vec<Constraint> {
+    vec![
+      Constraint::Length(6),
+      Constraint::Length(10),
+      Constraint::Min(40),
+      Constraint::Length(20),
+      Constraint::Length(20),
+    ]
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunablesComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  // Bad tunables first so the ones worth changing are at the top
+  collected_data: TunableCollection,
+  // Collected file tunables and the Wi-Fi ones, which are only queried when the view opens or on request
+  file_tunables: TunableCollection,
+  wifi_tunables: TunableCollection,
+  // Values tunables had before they were changed from here, so toggling again restores them
+  original_values: HashMap<String, String>,
+  // Outcome of the last change, shown below the table
+  message: Option<Result<String, String>>,
+  read_only: bool,
+  roots: PseudoFsRoots,
+  selection: TableSelection,
+  // Follows the highlighted tunable, rows move between Bad and Good when they are changed
+  selected_target: Option<TunableTarget>,
+  mode: Mode,
+}
+
+impl TunablesComponent {
+  pub fn new(read_only: bool, roots: PseudoFsRoots) -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: [].to_vec(),
+      file_tunables: [].to_vec(),
+      wifi_tunables: [].to_vec(),
+      original_values: HashMap::new(),
+      message: None,
+      read_only,
+      roots,
+      selection: TableSelection::default(),
+      selected_target: None,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: TunableCollection) {
+    self.file_tunables = new_data;
+    self.merge_tunables();
+  }
+
+  /// Queries the Wi-Fi tunables again. `iw` only describes the live machine, so captures have none.
+  fn refresh_wifi(&mut self) {
+    if self.roots.is_live() {
+      self.wifi_tunables = get_wifi_tunables();
+      self.merge_tunables();
+    }
+  }
+
+  fn merge_tunables(&mut self) {
+    self.collected_data = self.file_tunables.iter().chain(self.wifi_tunables.iter()).cloned().collect();
+    // Stable sort, so tunables keep their category order within Bad and Good
+    self.collected_data.sort_by_key(|tunable| tunable.good);
+    self.sync_selection();
+  }
+
+  /// Keeps the selection on the same tunable after the list changed, or clamps it if that tunable is gone.
+  fn sync_selection(&mut self) {
+    let index = self
+      .selected_target
+      .as_ref()
+      .and_then(|target| self.collected_data.iter().position(|tunable| tunable.target == *target));
+    match index {
+      Some(index) => self.selection.select(Some(index)),
+      None => {
+        self.selection.clamp(self.collected_data.len());
+        self.selected_target = self.selection.selected().map(|i| self.collected_data[i].target.clone());
+      },
+    }
+  }
+
+  fn scroll(&mut self, down: bool) {
+    if down {
+      self.selection.next(self.collected_data.len());
+    } else {
+      self.selection.previous(self.collected_data.len());
+    }
+    self.selected_target = self.selection.selected().map(|i| self.collected_data[i].target.clone());
+  }
+
+  /// Writes the suggested value of the selected tunable, or restores the value it had before if it was changed from
+  /// here already.
+  fn toggle_selected(&mut self) {
+    let Some(tunable) = self
+      .selected_target
+      .as_ref()
+      .and_then(|target| self.collected_data.iter().find(|tunable| tunable.target == *target))
+      .cloned()
+    else {
+      return;
+    };
+    if self.read_only {
+      self.message = Some(Err("Read-only mode, restart without --read-only to change tunables".to_string()));
+      return;
+    }
+    let key = tunable.target.to_string();
+    let value = match (tunable.good, self.original_values.get(&key)) {
+      (false, _) => tunable.suggested.clone(),
+      (true, Some(original)) => original.clone(),
+      (true, None) => {
+        self.message = Some(Ok(format!("{} is already set to a power saving value", tunable.description)));
+        return;
+      },
+    };
+    self.message = Some(match set_tunable(&tunable.target, &value, &self.roots) {
+      Ok(()) => {
+        if tunable.good {
+          self.original_values.remove(&key);
+        } else {
+          self.original_values.insert(key, tunable.current.clone());
+        }
+        Ok(format!("Set {} to {}", tunable.description, value))
+      },
+      Err(error) => Err(error.to_string()),
+    });
+    if let TunableTarget::WifiInterface(_) = tunable.target {
+      self.refresh_wifi();
+    }
+  }
+}
+
+impl Component for TunablesComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.tunables {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => {
+        if mode == Mode::Tunables && self.mode != Mode::Tunables {
+          self.refresh_wifi();
+        }
+        self.mode = mode;
+      },
+      Action::ScrollDown if self.mode == Mode::Tunables => self.scroll(true),
+      Action::ScrollUp if self.mode == Mode::Tunables => self.scroll(false),
+      Action::Select if self.mode == Mode::Tunables => self.toggle_selected(),
+      Action::Refresh if self.mode == Mode::Tunables => self.refresh_wifi(),
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    if self.mode != Mode::Tunables {
+      return Ok(());
+    }
+
+    let bad = self.collected_data.iter().filter(|tunable| !tunable.good).count();
+    let mut title = vec![
+      Span::from("Tunables - "),
+      Span::from(format!("{} bad", bad)).style(if bad > 0 { Style::default().red() } else { Style::default().green() }),
+      Span::from(format!(", {} good", self.collected_data.len() - bad)),
+    ];
+    if self.read_only {
+      title.push(Span::from(" (read-only)").dark_gray());
+    } else if !self.roots.is_live() {
+      title.push(Span::from(" (capture, read-only, no Wi-Fi)").dark_gray());
+    }
+    let block = Block::bordered().title(Line::from(title));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+      .split(inner);
+
+    let table_rows = self.collected_data.iter().map(Row::from).collect_vec();
+    let table = Table::new(table_rows, Tunable::column_widths())
+      .column_spacing(2)
+      .header(Row::new(Tunable::headers()).style(Style::default().bold().underlined()))
+      .highlight_style(Style::default().reversed());
+    frame.render_stateful_widget(table, rows[0], self.selection.state_mut());
+
+    let status = match &self.message {
+      Some(Ok(message)) => Line::from(message.clone()).green(),
+      Some(Err(message)) => Line::from(message.clone()).red(),
+      None if self.read_only => Line::from("Read-only mode, tunables cannot be changed").dark_gray(),
+      None if !self.roots.is_live() => Line::from("Tunables of a capture cannot be changed").dark_gray(),
+      None => {
+        Line::from("Press Enter to apply the suggested value of the selected tunable, Ctrl-r to query Wi-Fi again")
+          .dark_gray()
+      },
+    };
+    frame.render_widget(Paragraph::new(status), rows[1]);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::data_services::tunables::TunableCategory;
+
+  fn tunable(path: &str, good: bool) -> Tunable {
+    Tunable {
+      category: TunableCategory::RuntimePm,
+      description: path.to_string(),
+      target: TunableTarget::File(path.into()),
+      current: if good { "auto" } else { "on" }.to_string(),
+      suggested: "auto".to_string(),
+      good,
+    }
+  }
+
+  #[test]
+  fn test_selection_follows_tunable() {
+    let mut component = TunablesComponent::new(false, PseudoFsRoots::new("/capture/proc", "/capture/sys"));
+    component.update_data_stats(vec![tunable("a", false), tunable("b", false), tunable("c", true)]);
+    component.scroll(true);
+    component.scroll(true);
+    assert_eq!(component.selected_target, Some(TunableTarget::File("b".into())));
+
+    // `a` was changed and moves below `b`
+    component.update_data_stats(vec![tunable("a", true), tunable("b", false), tunable("c", true)]);
+    assert_eq!(component.selection.selected(), Some(0));
+
+    // Captures refuse writes instead of changing the copy
+    component.toggle_selected();
+    assert_eq!(component.message, Some(Err("Tunables of a capture cannot be changed".to_string())));
+  }
+}
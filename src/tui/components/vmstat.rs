@@ -0,0 +1,159 @@
+use std::{
+  collections::VecDeque,
+  time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+
+use super::Component;
+use crate::{
+  data_services::vmstat::{VmStatCounter, VmStatData},
+  tui::{
+    action::Action,
+    layout::{notice_area, HomeLayout},
+    mode::Mode,
+    ui::Frame,
+  },
+};
+
+const LABEL_WIDTH: u16 = 18;
+const RATE_WIDTH: u16 = 10;
+// How long the OOM kill notice stays up after the last kill
+const OOM_NOTICE_DURATION: Duration = Duration::from_secs(60);
+
+/// Colors a counter rate: swapping and OOM kills mean the system is short on memory, major faults mean processes are
+/// waiting on disk for pages that were evicted or never loaded.
+fn rate_style(counter: &VmStatCounter) -> Style {
+  if counter.is_thrashing() {
+    Style::default().red().bold()
+  } else if counter.key == "pgmajfault" && counter.rate > 0.0 {
+    Style::default().yellow()
+  } else if counter.rate > 0.0 {
+    Style::default()
+  } else {
+    Style::default().dark_gray()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VmStatComponent {
+  app_start_time: Instant,
+  render_start_time: Instant,
+  collected_data: VmStatData,
+  // Rate history per counter, scaled by 100 so fractional rates still show up in the sparkline
+  history: Vec<VecDeque<u64>>,
+  // When the OOM killer last struck and how many processes it killed since the monitor started
+  last_oom_kill: Option<Instant>,
+  oom_kills: u64,
+  mode: Mode,
+}
+
+impl Default for VmStatComponent {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl VmStatComponent {
+  pub const WINDOW_SIZE: usize = 30;
+
+  pub fn new() -> Self {
+    Self {
+      app_start_time: Instant::now(),
+      render_start_time: Instant::now(),
+      collected_data: VmStatData::default(),
+      history: Vec::new(),
+      last_oom_kill: None,
+      oom_kills: 0,
+      mode: Mode::Home,
+    }
+  }
+
+  fn update_data_stats(&mut self, new_data: VmStatData) {
+    // The set of counters is fixed, it only changes if the kernel stops exposing one of them
+    if self.history.len() != new_data.counters.len() {
+      self.history = vec![VecDeque::with_capacity(Self::WINDOW_SIZE); new_data.counters.len()];
+    }
+    for (history, counter) in self.history.iter_mut().zip(new_data.counters.iter()) {
+      if history.len() == Self::WINDOW_SIZE {
+        history.pop_front();
+      }
+      history.push_back((counter.rate * 100.0).round() as u64);
+    }
+    if new_data.oom_kills > 0 {
+      self.last_oom_kill = Some(Instant::now());
+      self.oom_kills += new_data.oom_kills;
+    }
+    self.collected_data = new_data;
+  }
+
+  /// Draws a notice about recent OOM kills on top of whatever view is shown.
+  fn draw_oom_notice(&self, frame: &mut Frame<'_>, area: Rect) {
+    let Some(last_oom_kill) = self.last_oom_kill else {
+      return;
+    };
+    let elapsed = last_oom_kill.elapsed();
+    if elapsed > OOM_NOTICE_DURATION {
+      return;
+    }
+    let text = format!(
+      " OOM killer: {} process{} killed, last {}s ago ",
+      self.oom_kills,
+      if self.oom_kills == 1 { "" } else { "es" },
+      elapsed.as_secs()
+    );
+    let area = notice_area(area, text.chars().count() as u16);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).style(Style::default().white().on_red().bold()), area);
+  }
+}
+
+impl Component for VmStatComponent {
+  fn update(&mut self, action: Action) -> Result<Option<Action>> {
+    match action {
+      Action::DataUpdate(data) => {
+        match data.vmstat {
+          Some(d) => self.update_data_stats(d),
+          None => {
+            log::debug!("Received Action with no data.")
+          },
+        }
+      },
+      Action::SwitchMode(mode) => self.mode = mode,
+      _ => {},
+    }
+    Ok(None)
+  }
+
+  fn draw(&mut self, frame: &mut Frame<'_>, area: Rect) -> Result<()> {
+    self.draw_oom_notice(frame, area);
+    if self.mode != Mode::Home {
+      return Ok(());
+    }
+
+    let vm_area = HomeLayout::new(area).vm_activity;
+    let block = Block::bordered().title("Virtual Memory (per sec)");
+    let inner = block.inner(vm_area);
+    frame.render_widget(block, vm_area);
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(1); self.collected_data.counters.len()])
+      .split(inner);
+
+    for ((counter, history), row) in self.collected_data.counters.iter().zip(self.history.iter_mut()).zip(rows.iter()) {
+      let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Length(LABEL_WIDTH), Constraint::Length(RATE_WIDTH), Constraint::Min(0)])
+        .split(*row);
+
+      let style = rate_style(counter);
+      frame.render_widget(Paragraph::new(counter.label.clone()), columns[0]);
+      frame.render_widget(Paragraph::new(format!("{:>9.1}", counter.rate)).style(style), columns[1]);
+      frame.render_widget(Sparkline::default().data(history.make_contiguous()).style(style), columns[2]);
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,146 @@
+use ratatui::prelude::*;
+
+/// Areas of the `Home` dashboard. Each dashboard component computes this layout from the full frame and draws into
+/// its own area, so adding a widget only requires a new field here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HomeLayout {
+  pub header: Rect,
+  pub cpu: Rect,
+  pub pressure: Rect,
+  pub memory: Rect,
+  pub vm_activity: Rect,
+  pub processes: Rect,
+  pub disks: Rect,
+  pub disk_io: Rect,
+  pub network: Rect,
+  pub net_stats: Rect,
+}
+
+impl HomeLayout {
+  // Two lines of host summary inside a border
+  const HEADER_HEIGHT: u16 = 4;
+  // CPU, memory and I/O rows below a header line, or up to four top level cgroups
+  const PRESSURE_HEIGHT: u16 = 7;
+
+  pub fn new(area: Rect) -> Self {
+    let sections = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Length(Self::HEADER_HEIGHT), Constraint::Min(0)])
+      .split(area);
+
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(sections[1]);
+
+    let top_row = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(rows[0]);
+
+    let bottom_row = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Percentage(33), Constraint::Percentage(33), Constraint::Percentage(34)])
+      .split(rows[1]);
+
+    let cpu_column = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Min(0), Constraint::Length(Self::PRESSURE_HEIGHT)])
+      .split(top_row[0]);
+
+    let memory_column = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+      .split(top_row[1]);
+
+    let disks_column = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(bottom_row[1]);
+
+    let network_column = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+      .split(bottom_row[2]);
+
+    Self {
+      header: sections[0],
+      cpu: cpu_column[0],
+      pressure: cpu_column[1],
+      memory: memory_column[0],
+      vm_activity: memory_column[1],
+      processes: bottom_row[0],
+      disks: disks_column[0],
+      disk_io: disks_column[1],
+      network: network_column[0],
+      net_stats: network_column[1],
+    }
+  }
+}
+
+/// Areas of the `BlockDevices` view: the device tree on top of the software RAID and device-mapper status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockDevicesLayout {
+  pub tree: Rect,
+  pub raid: Rect,
+}
+
+impl BlockDevicesLayout {
+  pub fn new(area: Rect) -> Self {
+    let rows = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+      .split(area);
+
+    Self { tree: rows[0], raid: rows[1] }
+  }
+}
+
+/// Single line at the top right of the screen where alerts that need attention in every view are drawn, e.g. a
+/// degraded RAID array.
+pub fn alert_area(area: Rect, width: u16) -> Rect {
+  let width = width.min(area.width);
+  Rect { x: area.x + area.width - width, y: area.y, width, height: area.height.min(1) }
+}
+
+/// Single line at the bottom right of the screen for transient notices shown in every view, e.g. an OOM kill.
+pub fn notice_area(area: Rect, width: u16) -> Rect {
+  let width = width.min(area.width);
+  Rect { x: area.x + area.width - width, y: area.y + area.height.saturating_sub(1), width, height: area.height.min(1) }
+}
+
+/// Areas of the `Sensors` view: temperatures next to the fan, voltage and power readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensorsLayout {
+  pub temperatures: Rect,
+  pub sensors: Rect,
+}
+
+impl SensorsLayout {
+  pub fn new(area: Rect) -> Self {
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Percentage(55), Constraint::Percentage(45)])
+      .split(area);
+
+    Self { temperatures: columns[0], sensors: columns[1] }
+  }
+}
+
+/// Areas of the `Power` view: battery status next to the RAPL power consumption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerLayout {
+  pub battery: Rect,
+  pub rapl: Rect,
+}
+
+impl PowerLayout {
+  pub fn new(area: Rect) -> Self {
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(area);
+
+    Self { battery: columns[0], rapl: columns[1] }
+  }
+}
@@ -1,4 +1,6 @@
 pub mod action;
 pub mod components;
+pub mod layout;
 pub mod mode;
 pub mod ui;
+pub mod widgets;
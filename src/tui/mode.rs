@@ -1,7 +1,40 @@
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
 
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// The full screen views of the application. Each mode has its own set of keybindings, falling back to the
+/// `Home` keybindings for keys it does not define.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter)]
 pub enum Mode {
   #[default]
   Home,
+  Connections,
+  Disks,
+  BlockDevices,
+  Sensors,
+  Power,
+  IdleStats,
+  Frequency,
+  Overview,
+  Tunables,
+  DevicePower,
+  Limits,
+  Interrupts,
+  Cgroups,
+}
+
+impl Mode {
+  /// Returns the mode after this one, wrapping around to the first mode.
+  pub fn next(self) -> Self {
+    let modes = Mode::iter().collect_vec();
+    let index = modes.iter().position(|mode| *mode == self).unwrap_or_default();
+    modes[(index + 1) % modes.len()]
+  }
+
+  /// Returns the mode before this one, wrapping around to the last mode.
+  pub fn previous(self) -> Self {
+    let modes = Mode::iter().collect_vec();
+    let index = modes.iter().position(|mode| *mode == self).unwrap_or_default();
+    modes[(index + modes.len() - 1) % modes.len()]
+  }
 }
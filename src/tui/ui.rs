@@ -21,7 +21,10 @@ use tokio::{
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::data_services::data_collector::{DataCollected, DataCollector, SysinfoSource};
+use crate::data_services::{
+  data_collector::{DataCollected, DataCollector, SysinfoSource},
+  pseudo_fs::PseudoFsRoots,
+};
 
 pub type IO = std::io::Stdout;
 pub fn io() -> IO {
@@ -67,6 +70,7 @@ pub struct Tui {
   pub tick_rate: f64,
   pub mouse: bool,
   pub paste: bool,
+  pub roots: PseudoFsRoots,
 }
 
 impl Tui {
@@ -80,6 +84,7 @@ impl Tui {
     let data_collection_task = tokio::spawn(async {});
     let mouse = false;
     let paste = false;
+    let roots = PseudoFsRoots::default();
     Ok(Self {
       terminal,
       task,
@@ -91,6 +96,7 @@ impl Tui {
       tick_rate,
       mouse,
       paste,
+      roots,
     })
   }
 
@@ -114,6 +120,11 @@ impl Tui {
     self
   }
 
+  pub fn roots(mut self, roots: PseudoFsRoots) -> Self {
+    self.roots = roots;
+    self
+  }
+
   pub fn start(&mut self) {
     self.cancel();
     self.cancellation_token = CancellationToken::new();
@@ -190,8 +201,9 @@ impl Tui {
   fn spawn_data_collection_task(&mut self) {
     let data_event_tx = self.event_tx.clone();
     let data_collection_token = self.cancellation_token.clone();
+    let roots = self.roots.clone();
     self.data_collection_task = tokio::spawn(async move {
-      let mut data_state: DataCollector = DataCollector::new();
+      let mut data_state: DataCollector = DataCollector::with_roots(roots);
 
       loop {
         // Check for cancellation
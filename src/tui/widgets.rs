@@ -0,0 +1,3 @@
+pub mod donut;
+pub mod table_selection;
+pub mod usage;
@@ -0,0 +1,101 @@
+use std::f64::consts::PI;
+
+use ratatui::{
+  prelude::*,
+  widgets::{
+    canvas::{Canvas, Points},
+    Block,
+  },
+};
+
+// Inner and outer radius of the ring on a canvas spanning -1.0..=1.0
+const INNER_RADIUS: f64 = 0.55;
+const OUTER_RADIUS: f64 = 0.95;
+const RADIUS_STEPS: usize = 8;
+const ANGLE_STEPS: usize = 180;
+
+type CanvasPoints = Vec<(f64, f64)>;
+
+/// A ring chart showing a single ratio, in the style of the gtop disk display. The filled part starts at twelve
+/// o'clock and runs clockwise, with the label drawn in the middle.
+pub struct Donut<'a> {
+  percent: f64,
+  label: String,
+  filled_color: Color,
+  empty_color: Color,
+  block: Option<Block<'a>>,
+}
+
+impl<'a> Donut<'a> {
+  pub fn new(percent: f64) -> Self {
+    Self {
+      percent: percent.clamp(0.0, 100.0),
+      label: format!("{:.0}%", percent),
+      filled_color: Color::Green,
+      empty_color: Color::DarkGray,
+      block: None,
+    }
+  }
+
+  pub fn filled_color(mut self, color: Color) -> Self {
+    self.filled_color = color;
+    self
+  }
+
+  pub fn block(mut self, block: Block<'a>) -> Self {
+    self.block = Some(block);
+    self
+  }
+
+  /// Splits the ring into the points of the filled and the empty segment.
+  fn ring_points(&self) -> (CanvasPoints, CanvasPoints) {
+    let mut filled = Vec::with_capacity(ANGLE_STEPS * RADIUS_STEPS);
+    let mut empty = Vec::with_capacity(ANGLE_STEPS * RADIUS_STEPS);
+    let filled_steps = (self.percent / 100.0 * ANGLE_STEPS as f64).round() as usize;
+
+    for step in 0..ANGLE_STEPS {
+      // Start at twelve o'clock and go clockwise
+      let angle = PI / 2.0 - 2.0 * PI * step as f64 / ANGLE_STEPS as f64;
+      for r in 0..=RADIUS_STEPS {
+        let radius = INNER_RADIUS + (OUTER_RADIUS - INNER_RADIUS) * r as f64 / RADIUS_STEPS as f64;
+        let point = (radius * angle.cos(), radius * angle.sin());
+        if step < filled_steps {
+          filled.push(point);
+        } else {
+          empty.push(point);
+        }
+      }
+    }
+    (filled, empty)
+  }
+}
+
+impl Widget for Donut<'_> {
+  fn render(self, area: Rect, buf: &mut Buffer) {
+    let (filled, empty) = self.ring_points();
+    let label = self.label.clone();
+    let (filled_color, empty_color) = (self.filled_color, self.empty_color);
+
+    // Terminal cells are roughly twice as tall as they are wide, widen the x bounds to keep the ring round
+    let inner = self.block.as_ref().map_or(area, |block| block.inner(area));
+    if inner.width == 0 || inner.height == 0 {
+      return;
+    }
+    let aspect = inner.width as f64 / (inner.height as f64 * 2.0);
+    // Width of a single cell in canvas units, used to center the label
+    let cell_width = 2.0 * aspect / inner.width as f64;
+    let canvas =
+      Canvas::default().marker(symbols::Marker::Braille).x_bounds([-aspect, aspect]).y_bounds([-1.0, 1.0]).paint(
+        move |ctx| {
+          ctx.draw(&Points { coords: &empty, color: empty_color });
+          ctx.draw(&Points { coords: &filled, color: filled_color });
+          ctx.print(-(label.len() as f64) * cell_width / 2.0, 0.0, label.clone().bold());
+        },
+      );
+
+    match self.block {
+      Some(block) => canvas.block(block).render(area, buf),
+      None => canvas.render(area, buf),
+    }
+  }
+}
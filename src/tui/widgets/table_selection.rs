@@ -0,0 +1,86 @@
+use ratatui::widgets::TableState;
+
+/// Keeps track of the selected row (and scroll offset) of a table whose rows are replaced on every data update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableSelection {
+  state: TableState,
+}
+
+impl TableSelection {
+  pub fn selected(&self) -> Option<usize> {
+    self.state.selected()
+  }
+
+  pub fn select(&mut self, index: Option<usize>) {
+    self.state.select(index);
+  }
+
+  /// Moves the selection down one row, stopping at the last of `len` rows.
+  pub fn next(&mut self, len: usize) {
+    let index = match self.state.selected() {
+      Some(i) => (i + 1).min(len.saturating_sub(1)),
+      None => 0,
+    };
+    self.state.select((len > 0).then_some(index));
+  }
+
+  /// Moves the selection up one row, stopping at the first row.
+  pub fn previous(&mut self, len: usize) {
+    let index = self.state.selected().map_or(0, |i| i.saturating_sub(1));
+    self.state.select((len > 0).then_some(index));
+  }
+
+  /// Keeps the selection within the bounds of a table that now has `len` rows.
+  pub fn clamp(&mut self, len: usize) {
+    if let Some(i) = self.state.selected() {
+      self.state.select(if len == 0 { None } else { Some(i.min(len - 1)) });
+    }
+  }
+
+  pub fn state_mut(&mut self) -> &mut TableState {
+    &mut self.state
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_next_stops_at_last_row() {
+    let mut selection = TableSelection::default();
+    selection.next(2);
+    assert_eq!(selection.selected(), Some(0));
+    selection.next(2);
+    selection.next(2);
+    assert_eq!(selection.selected(), Some(1));
+  }
+
+  #[test]
+  fn test_previous_stops_at_first_row() {
+    let mut selection = TableSelection::default();
+    selection.select(Some(1));
+    selection.previous(3);
+    selection.previous(3);
+    assert_eq!(selection.selected(), Some(0));
+  }
+
+  #[test]
+  fn test_clamp_to_shorter_table() {
+    let mut selection = TableSelection::default();
+    selection.select(Some(5));
+    selection.clamp(3);
+    assert_eq!(selection.selected(), Some(2));
+    selection.clamp(0);
+    assert_eq!(selection.selected(), None);
+  }
+
+  #[test]
+  fn test_empty_table_has_no_selection() {
+    let mut selection = TableSelection::default();
+    selection.next(0);
+    assert_eq!(selection.selected(), None);
+  }
+}
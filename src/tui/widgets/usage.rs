@@ -0,0 +1,65 @@
+use ratatui::style::{Style, Stylize};
+
+const BAR_FILLED: char = '█';
+const BAR_EMPTY: char = '░';
+
+/// Colors a percentage green, yellow from `warning` and red from `critical`.
+pub fn threshold_style(percent: f64, warning: f64, critical: f64) -> Style {
+  if percent >= critical {
+    Style::default().red()
+  } else if percent >= warning {
+    Style::default().yellow()
+  } else {
+    Style::default().green()
+  }
+}
+
+/// Like `threshold_style`, with values at the critical level also in bold as they need attention right away.
+pub fn alert_style(percent: f64, warning: f64, critical: f64) -> Style {
+  let style = threshold_style(percent, warning, critical);
+  if percent >= critical {
+    style.bold()
+  } else {
+    style
+  }
+}
+
+/// Colors a usage percentage using the default thresholds of 75% and 90%.
+pub fn usage_style(percent: f64) -> Style {
+  threshold_style(percent, 75.0, 90.0)
+}
+
+/// Renders a percentage as a text bar `width` characters wide, for use inside table cells where a `Gauge` widget
+/// can not be rendered.
+pub fn usage_bar(percent: f64, width: usize) -> String {
+  let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+  format!("{}{}", BAR_FILLED.to_string().repeat(filled), BAR_EMPTY.to_string().repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_alert_style() {
+    assert_eq!(alert_style(10.0, 50.0, 80.0), Style::default().green());
+    assert_eq!(alert_style(60.0, 50.0, 80.0), Style::default().yellow());
+    assert_eq!(alert_style(80.0, 50.0, 80.0), Style::default().red().bold());
+  }
+
+  #[test]
+  fn test_usage_style() {
+    assert_eq!(usage_style(10.0), Style::default().green());
+    assert_eq!(usage_style(80.0), Style::default().yellow());
+    assert_eq!(usage_style(95.0), Style::default().red());
+  }
+
+  #[test]
+  fn test_usage_bar() {
+    assert_eq!(usage_bar(0.0, 4), "░░░░");
+    assert_eq!(usage_bar(50.0, 4), "██░░");
+    assert_eq!(usage_bar(150.0, 4), "████");
+  }
+}
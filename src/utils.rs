@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use color_eyre::eyre::Result;
 use tracing::error;
 
@@ -50,3 +52,76 @@ pub fn initialize_panic_handler() -> Result<()> {
   }));
   Ok(())
 }
+
+/// Formats a byte count using binary (1024 based) units, e.g. `1536` becomes `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+  const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+  let mut value = bytes as f64;
+  let mut unit = 0;
+  while value >= 1024.0 && unit < UNITS.len() - 1 {
+    value /= 1024.0;
+    unit += 1;
+  }
+
+  if unit == 0 {
+    format!("{} {}", bytes, UNITS[unit])
+  } else {
+    format!("{:.1} {}", value, UNITS[unit])
+  }
+}
+
+/// Formats a duration using its two largest units, e.g. `2d 3h`, `3h 12m` or `45s`.
+pub fn format_duration(duration: Duration) -> String {
+  let secs = duration.as_secs();
+  let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+  if days > 0 {
+    format!("{}d {}h", days, hours)
+  } else if hours > 0 {
+    format!("{}h {}m", hours, minutes)
+  } else if minutes > 0 {
+    format!("{}m {}s", minutes, secs % 60)
+  } else {
+    format!("{}s", secs)
+  }
+}
+
+/// Formats seconds since the Unix epoch as local time, e.g. `2026-10-17 08:12`.
+#[cfg(unix)]
+pub fn format_timestamp(epoch_secs: u64) -> String {
+  let time = epoch_secs as libc::time_t;
+  // SAFETY: tm is plain old data, zero is a valid bit pattern for all of its fields.
+  let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+  // SAFETY: `time` and `tm` are valid for the duration of the call, localtime_r does not keep either.
+  if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+    return epoch_secs.to_string();
+  }
+  format!("{:04}-{:02}-{:02} {:02}:{:02}", tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday, tm.tm_hour, tm.tm_min)
+}
+
+#[cfg(not(unix))]
+pub fn format_timestamp(epoch_secs: u64) -> String {
+  epoch_secs.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn test_format_bytes() {
+    assert_eq!(format_bytes(0), "0 B");
+    assert_eq!(format_bytes(1023), "1023 B");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+  }
+
+  #[test]
+  fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+    assert_eq!(format_duration(Duration::from_secs(3 * 3600 + 12 * 60 + 5)), "3h 12m");
+    assert_eq!(format_duration(Duration::from_secs(2 * 86_400 + 3 * 3600)), "2d 3h");
+  }
+}